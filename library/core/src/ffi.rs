@@ -34,6 +34,7 @@ use crate::ops::{Deref, DerefMut};
 //     be UB.
 #[repr(u8)]
 #[stable(feature = "core_c_void", since = "1.30.0")]
+#[rustc_diagnostic_item = "c_void"]
 pub enum c_void {
     #[unstable(
         feature = "c_void_variant",
@@ -121,6 +121,7 @@
 #![feature(rustc_attrs)]
 #![feature(simd_ffi)]
 #![feature(min_specialization)]
+#![cfg_attr(not(bootstrap), feature(must_not_suspend))]
 #![feature(staged_api)]
 #![feature(std_internals)]
 #![feature(stmt_expr_attributes)]
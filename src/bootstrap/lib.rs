@@ -178,6 +178,7 @@ const LLVM_TOOLS: &[&str] = &[
     "llvm-size", // used to prints the size of the linker sections of a program
     "llvm-strip", // used to discard symbols from binary files to reduce their size
     "llvm-ar", // used for creating and modifying archive files
+    "llvm-dwp", // used to package split DWARF objects into a DWARF package
 ];
 
 /// A structure representing a Rust compiler.
@@ -982,6 +982,8 @@ pub struct Local {
     pub ty: Option<P<Ty>>,
     /// Initializer expression to set the value, if any.
     pub init: Option<P<Expr>>,
+    /// The diverging `else` block of a `let PAT = EXPR else { .. };`, if any.
+    pub els: Option<P<Block>>,
     pub span: Span,
     pub attrs: AttrVec,
 }
@@ -1022,6 +1024,17 @@ pub struct Field {
     pub is_placeholder: bool,
 }
 
+/// What comes after the fields of a struct expression.
+#[derive(Clone, Encodable, Decodable, Debug)]
+pub enum StructRest {
+    /// `..x`, the base of a functional record update.
+    Base(P<Expr>),
+    /// `..`, only allowed on the left-hand side of a destructuring assignment.
+    Rest(Span),
+    /// Neither of the above.
+    None,
+}
+
 #[derive(Clone, PartialEq, Encodable, Decodable, Debug, Copy)]
 pub enum BlockCheckMode {
     Default,
@@ -1164,6 +1177,7 @@ impl Expr {
         match self.kind {
             ExprKind::Box(_) => ExprPrecedence::Box,
             ExprKind::Array(_) => ExprPrecedence::Array,
+            ExprKind::ConstBlock(_) => ExprPrecedence::ConstBlock,
             ExprKind::Call(..) => ExprPrecedence::Call,
             ExprKind::MethodCall(..) => ExprPrecedence::MethodCall,
             ExprKind::Tup(_) => ExprPrecedence::Tup,
@@ -1199,6 +1213,7 @@ impl Expr {
             ExprKind::Paren(..) => ExprPrecedence::Paren,
             ExprKind::Try(..) => ExprPrecedence::Try,
             ExprKind::Yield(..) => ExprPrecedence::Yield,
+            ExprKind::Underscore => ExprPrecedence::Path,
             ExprKind::Err => ExprPrecedence::Err,
         }
     }
@@ -1219,6 +1234,8 @@ pub enum ExprKind {
     Box(P<Expr>),
     /// An array (`[a, b, c, d]`)
     Array(Vec<P<Expr>>),
+    /// Allow anonymous constants from an inline `const` block
+    ConstBlock(AnonConst),
     /// A function call
     ///
     /// The first field resolves to the function itself,
@@ -1333,9 +1350,8 @@ pub enum ExprKind {
 
     /// A struct literal expression.
     ///
-    /// E.g., `Foo {x: 1, y: 2}`, or `Foo {x: 1, .. base}`,
-    /// where `base` is the `Option<Expr>`.
-    Struct(Path, Vec<Field>, Option<P<Expr>>),
+    /// E.g., `Foo {x: 1, y: 2}`, or `Foo {x: 1, .. rest}`.
+    Struct(Path, Vec<Field>, StructRest),
 
     /// An array literal constructed from one repeated element.
     ///
@@ -1352,6 +1368,9 @@ pub enum ExprKind {
     /// A `yield`, with an optional value to be yielded.
     Yield(Option<P<Expr>>),
 
+    /// A `_` on the left-hand side of a destructuring assignment.
+    Underscore,
+
     /// Placeholder for an expression that wasn't syntactically well formed in some way.
     Err,
 }
@@ -273,6 +273,7 @@ pub enum ExprPrecedence {
     Mac,
 
     Array,
+    ConstBlock,
     Repeat,
     Tup,
     Lit,
@@ -337,6 +338,7 @@ impl ExprPrecedence {
 
             // Never need parens
             ExprPrecedence::Array |
+            ExprPrecedence::ConstBlock |
             ExprPrecedence::Repeat |
             ExprPrecedence::Tup |
             ExprPrecedence::Lit |
@@ -1704,12 +1704,14 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
             )
         });
         let init = l.init.as_ref().map(|e| self.lower_expr(e));
+        let els = l.els.as_ref().map(|b| self.lower_block(b, false));
         (
             hir::Local {
                 hir_id: self.lower_node_id(l.id),
                 ty,
                 pat: self.lower_pat(&l.pat),
                 init,
+                els,
                 span: l.span,
                 attrs: l.attrs.clone(),
                 source: hir::LocalSource::Normal,
@@ -2429,7 +2431,16 @@ impl<'a, 'hir> LoweringContext<'a, 'hir> {
         pat: &'hir hir::Pat<'hir>,
         source: hir::LocalSource,
     ) -> hir::Stmt<'hir> {
-        let local = hir::Local { attrs, hir_id: self.next_id(), init, pat, source, span, ty: None };
+        let local = hir::Local {
+            attrs,
+            hir_id: self.next_id(),
+            init,
+            els: None,
+            pat,
+            source,
+            span,
+            ty: None,
+        };
         self.stmt(span, hir::StmtKind::Local(self.arena.alloc(local)))
     }
 
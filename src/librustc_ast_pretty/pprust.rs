@@ -1486,6 +1486,12 @@ impl<'a> State<'a> {
                     self.word_space("=");
                     self.print_expr(init);
                 }
+                if let Some(ref els) = loc.els {
+                    self.cbox(INDENT_UNIT);
+                    self.ibox(INDENT_UNIT);
+                    self.s.word(" else ");
+                    self.print_block(els);
+                }
                 self.s.word(";");
                 self.end();
             }
@@ -1695,7 +1701,7 @@ impl<'a> State<'a> {
         &mut self,
         path: &ast::Path,
         fields: &[ast::Field],
-        wth: &Option<P<ast::Expr>>,
+        rest: &ast::StructRest,
         attrs: &[ast::Attribute],
     ) {
         self.print_path(path, true, 0);
@@ -1716,18 +1722,20 @@ impl<'a> State<'a> {
             },
             |f| f.span,
         );
-        match *wth {
-            Some(ref expr) => {
+        match rest {
+            ast::StructRest::Base(_) | ast::StructRest::Rest(_) => {
                 self.ibox(INDENT_UNIT);
                 if !fields.is_empty() {
                     self.s.word(",");
                     self.s.space();
                 }
                 self.s.word("..");
-                self.print_expr(expr);
+                if let ast::StructRest::Base(ref expr) = *rest {
+                    self.print_expr(expr);
+                }
                 self.end();
             }
-            _ => {
+            ast::StructRest::None => {
                 if !fields.is_empty() {
                     self.s.word(",")
                 }
@@ -1851,11 +1859,15 @@ impl<'a> State<'a> {
             ast::ExprKind::Array(ref exprs) => {
                 self.print_expr_vec(&exprs[..], attrs);
             }
+            ast::ExprKind::ConstBlock(ref anon_const) => {
+                self.word_space("const");
+                self.print_expr(&anon_const.value);
+            }
             ast::ExprKind::Repeat(ref element, ref count) => {
                 self.print_expr_repeat(element, count, attrs);
             }
-            ast::ExprKind::Struct(ref path, ref fields, ref wth) => {
-                self.print_expr_struct(path, &fields[..], wth, attrs);
+            ast::ExprKind::Struct(ref path, ref fields, ref rest) => {
+                self.print_expr_struct(path, &fields[..], rest, attrs);
             }
             ast::ExprKind::Tup(ref exprs) => {
                 self.print_expr_tup(&exprs[..], attrs);
@@ -2254,6 +2266,7 @@ impl<'a> State<'a> {
                 self.s.space();
                 self.print_block_with_attrs(blk, attrs)
             }
+            ast::ExprKind::Underscore => self.s.word("_"),
             ast::ExprKind::Err => {
                 self.popen();
                 self.s.word("/*ERROR*/");
@@ -11,7 +11,7 @@ use rustc_middle::middle::codegen_fn_attrs::CodegenFnAttrFlags;
 use rustc_middle::ty::layout::HasTyCtxt;
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::{self, TyCtxt};
-use rustc_session::config::{OptLevel, SanitizerSet};
+use rustc_session::config::{OptLevel, SanitizerSet, StackProtector};
 use rustc_session::Session;
 
 use crate::attributes;
@@ -146,6 +146,17 @@ fn set_probestack(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
     );
 }
 
+/// Tell LLVM which functions to protect with stack canaries.
+fn set_stack_protector(cx: &CodegenCx<'ll, '_>, llfn: &'ll Value) {
+    let attr = match cx.sess().opts.debugging_opts.stack_protector {
+        StackProtector::None => return,
+        StackProtector::Basic => Attribute::StackProtect,
+        StackProtector::Strong => Attribute::StackProtectStrong,
+        StackProtector::All => Attribute::StackProtectReq,
+    };
+    attr.apply_llfn(Function, llfn);
+}
+
 fn translate_obsolete_target_features(feature: &str) -> &str {
     const LLVM9_FEATURE_CHANGES: &[(&str, &str)] =
         &[("+fp-only-sp", "-fp64"), ("-fp-only-sp", "+fp64"), ("+d16", "-d32"), ("-d16", "+d32")];
@@ -275,6 +286,10 @@ pub fn from_fn_attrs(cx: &CodegenCx<'ll, 'tcx>, llfn: &'ll Value, instance: ty::
     set_frame_pointer_elimination(cx, llfn);
     set_instrument_function(cx, llfn);
     set_probestack(cx, llfn);
+    // The canary is set up in the prologue, which naked functions don't have.
+    if !codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::NAKED) {
+        set_stack_protector(cx, llfn);
+    }
 
     if codegen_fn_attrs.flags.contains(CodegenFnAttrFlags::COLD) {
         Attribute::Cold.apply_llfn(Function, llfn);
@@ -10,6 +10,7 @@ use crate::type_of::LayoutLlvmExt;
 use crate::value::Value;
 
 use rustc_ast::Mutability;
+use rustc_codegen_ssa::meth;
 use rustc_codegen_ssa::mir::place::PlaceRef;
 use rustc_codegen_ssa::traits::*;
 use rustc_middle::bug;
@@ -265,6 +266,9 @@ impl ConstMethods<'tcx> for CodegenCx<'ll, 'tcx> {
                         assert!(!self.tcx.is_thread_local_static(def_id));
                         (self.get_static(def_id), AddressSpace::DATA)
                     }
+                    GlobalAlloc::Vtable(_, ty, trait_ref) => {
+                        (meth::get_vtable(self, ty, trait_ref), AddressSpace::DATA)
+                    }
                 };
                 let llval = unsafe {
                     llvm::LLVMConstInBoundsGEP(
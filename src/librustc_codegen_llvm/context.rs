@@ -420,6 +420,25 @@ impl MiscMethods<'tcx> for CodegenCx<'ll, 'tcx> {
         attributes::apply_target_cpu_attr(self, llfn)
     }
 
+    fn instruction_count(&self, llfn: &'ll Value) -> usize {
+        let mut count = 0;
+        unsafe {
+            if llvm::LLVMCountBasicBlocks(llfn) == 0 {
+                return 0;
+            }
+            let mut block = Some(llvm::LLVMGetFirstBasicBlock(llfn));
+            while let Some(bb) = block {
+                let mut instruction = llvm::LLVMGetFirstInstruction(bb);
+                while let Some(inst) = instruction {
+                    count += 1;
+                    instruction = llvm::LLVMGetNextInstruction(inst);
+                }
+                block = llvm::LLVMGetNextBasicBlock(bb);
+            }
+        }
+        count
+    }
+
     fn create_used_variable(&self) {
         let name = const_cstr!("llvm.used");
         let section = const_cstr!("llvm.metadata");
@@ -805,6 +824,7 @@ impl CodegenCx<'b, 'tcx> {
 
         ifn!("llvm.assume", fn(i1) -> void);
         ifn!("llvm.prefetch", fn(i8p, t_i32, t_i32, t_i32) -> void);
+        ifn!("llvm.type.test", fn(i8p, self.type_metadata()) -> i1);
 
         // variadic intrinsics
         ifn!("llvm.va_start", fn(i8p) -> void);
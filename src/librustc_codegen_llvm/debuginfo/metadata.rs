@@ -999,7 +999,13 @@ pub fn compile_unit_metadata(
     let name_in_debuginfo = name_in_debuginfo.to_string_lossy();
     let work_dir = tcx.sess.working_dir.0.to_string_lossy();
     let flags = "\0";
-    let split_name = "";
+
+    let split_name = tcx
+        .output_filenames(LOCAL_CRATE)
+        .split_dwarf_path(tcx.sess.split_debuginfo(), Some(codegen_unit_name))
+        .unwrap_or_default();
+    let split_name = tcx.sess.source_map().path_mapping().map_prefix(split_name).0;
+    let split_name = split_name.to_string_lossy();
 
     // FIXME(#60020):
     //
@@ -1043,6 +1049,10 @@ pub fn compile_unit_metadata(
             split_name.as_ptr().cast(),
             split_name.len(),
             kind,
+            0,
+            // Only keep the skeleton unit in the object file, and not also the
+            // inlining information that tools could use without the `.dwo` file.
+            false,
         );
 
         if tcx.sess.opts.debugging_opts.profile {
@@ -10,6 +10,8 @@ pub use rustc_middle::mir::mono::MonoItem;
 use rustc_middle::mir::mono::{Linkage, Visibility};
 use rustc_middle::ty::layout::FnAbiExt;
 use rustc_middle::ty::{self, Instance, TypeFoldable};
+use rustc_session::config::SanitizerSet;
+use rustc_symbol_mangling::typeid::typeid_for_fnabi;
 use rustc_target::abi::LayoutOf;
 use tracing::debug;
 
@@ -79,6 +81,10 @@ impl PreDefineMethods<'tcx> for CodegenCx<'ll, 'tcx> {
 
         attributes::from_fn_attrs(self, lldecl, instance);
 
+        if self.tcx.sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI) {
+            self.add_type_metadata(lldecl, typeid_for_fnabi(self.tcx, &fn_abi));
+        }
+
         self.instances.borrow_mut().insert(instance, lldecl);
     }
 }
@@ -4,7 +4,7 @@ use rustc_fs_util::fix_windows_verbatim_for_gcc;
 use rustc_hir::def_id::CrateNum;
 use rustc_middle::middle::cstore::{EncodedMetadata, LibSource, NativeLib};
 use rustc_middle::middle::dependency_format::Linkage;
-use rustc_session::config::{self, CFGuard, CrateType, DebugInfo};
+use rustc_session::config::{self, CFGuard, CrateType, DebugInfo, SplitDebuginfo};
 use rustc_session::config::{OutputFilenames, OutputType, PrintRequest, SanitizerSet};
 use rustc_session::output::{check_file_is_writeable, invalid_output_for_target, out_filename};
 use rustc_session::search_paths::PathKind;
@@ -766,6 +766,52 @@ fn link_natively<'a, B: ArchiveBuilder<'a>>(
             sess.fatal(&format!("failed to run dsymutil: {}", e))
         }
     }
+
+    if sess.split_debuginfo() == SplitDebuginfo::Packed {
+        link_dwarf_package(sess, out_filename, codegen_results);
+    }
+}
+
+/// Packages the DWARF objects (`.dwo` files) of the codegen units of an executable or
+/// dynamic library into a DWARF package next to it, using `llvm-dwp`. The `.dwo` files
+/// are left in place, as other artifacts may still refer to them.
+fn link_dwarf_package(sess: &Session, out_filename: &Path, codegen_results: &CodegenResults) {
+    let dwarf_objects: Vec<_> =
+        codegen_results.modules.iter().filter_map(|m| m.dwarf_object.as_ref()).collect();
+    if dwarf_objects.is_empty() {
+        return;
+    }
+
+    let mut dwp_out_filename = out_filename.as_os_str().to_owned();
+    dwp_out_filename.push(".dwp");
+
+    // The skeleton units refer to the `.dwo` files by their paths after
+    // `--remap-path-prefix`, which may not exist, so `llvm-dwp` isn't asked to
+    // find them through the executable with `-e`.
+    let mut cmd = Command::new("llvm-dwp");
+    cmd.args(dwarf_objects).arg("-o").arg(&dwp_out_filename);
+
+    // Prefer an `llvm-dwp` bundled with the compiler over the one in the PATH.
+    let mut new_path = sess.host_filesearch(PathKind::All).get_tools_search_paths(false);
+    if let Some(path) = env::var_os("PATH") {
+        new_path.extend(env::split_paths(&path));
+    }
+    cmd.env("PATH", env::join_paths(new_path).unwrap());
+
+    info!("{:?}", &cmd);
+    match sess.time("run_dwp", || cmd.output()) {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => {
+            sess.struct_err(&format!(
+                "packaging the DWARF objects of `{}` failed: {}",
+                out_filename.display(),
+                output.status
+            ))
+            .note(&String::from_utf8_lossy(&output.stderr))
+            .emit();
+        }
+        Err(e) => sess.fatal(&format!("failed to run `llvm-dwp`: {}", e)),
+    }
 }
 
 fn link_sanitizers(sess: &Session, crate_type: CrateType, linker: &mut dyn Linker) {
@@ -4,5 +4,6 @@ pub mod link;
 pub mod linker;
 pub mod lto;
 pub mod rpath;
+pub mod stack_usage;
 pub mod symbol_export;
 pub mod write;
@@ -0,0 +1,369 @@
+//! Worst-case stack usage for `-Z print-stack-usage`.
+//!
+//! LLVM records the frame size of every function it emits in the `.stack_sizes`
+//! section of ELF object files. Combining those with the call graph recorded by the
+//! mono item collector, and with the functions referenced by the relocations of each
+//! function's section, gives an upper bound on the stack depth reached from a root,
+//! as long as there is no recursion, no call through a function pointer or a vtable,
+//! and every function called was codegened in this crate.
+
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
+use rustc_data_structures::stack::ensure_sufficient_stack;
+use rustc_serialize::leb128;
+use rustc_session::{CallGraphNode, Session};
+
+use object::{Object, ObjectSection, RelocationTarget, SymbolKind, SymbolSection};
+
+use crate::CodegenResults;
+
+use std::collections::BTreeSet;
+use std::{cmp, fs};
+
+/// Why the stack usage reached from a function has no upper bound.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Unbounded {
+    /// The function is part of a cycle in the call graph.
+    Recursion(String),
+    /// The function calls through a function pointer or a vtable.
+    IndirectCall(String),
+    /// The frame of the function is not known, for example because it was not
+    /// codegened in this crate.
+    UnknownCallee(String),
+}
+
+#[derive(Copy, Clone, Debug)]
+enum Frame {
+    Bytes(u64),
+    /// The function has no frame of its own, as every call to it was inlined.
+    Inlined,
+    Unknown,
+}
+
+#[derive(Clone, Default, Debug)]
+struct StackUsage {
+    bytes: u64,
+    /// The deepest call path, starting with the function itself.
+    path: Vec<(String, Frame)>,
+    unbounded: BTreeSet<Unbounded>,
+}
+
+/// What the object files tell about the functions codegened in them.
+#[derive(Default)]
+struct ObjectInfo {
+    frame_sizes: FxHashMap<String, u64>,
+    /// The functions defined in the object files.
+    defined: FxHashSet<String>,
+    /// The symbols referenced by the section of each function. They include the
+    /// functions LLVM calls on its own, like `memcpy`, `__rust_probestack` or the
+    /// arithmetic functions of `compiler_builtins`, which the mono item collector
+    /// doesn't see.
+    references: FxHashMap<String, BTreeSet<String>>,
+    /// Whether every object file could be read, so that a function missing from all
+    /// of them is known to have been inlined.
+    complete: bool,
+}
+
+struct StackUsageAnalysis<'a> {
+    nodes: FxHashMap<&'a str, &'a CallGraphNode>,
+    /// The names of the functions in the call graph, by symbol.
+    names: FxHashMap<&'a str, &'a str>,
+    objects: &'a ObjectInfo,
+    results: FxHashMap<&'a str, StackUsage>,
+    /// The functions on the path currently being explored, used to detect recursion.
+    active: FxHashSet<&'a str>,
+}
+
+impl<'a> StackUsageAnalysis<'a> {
+    fn stack_usage(&mut self, symbol: &'a str) -> StackUsage {
+        if let Some(usage) = self.results.get(symbol) {
+            return usage.clone();
+        }
+
+        let node = self.nodes.get(symbol).copied();
+        let name = self.names.get(symbol).copied().unwrap_or(symbol);
+
+        if !self.active.insert(symbol) {
+            // The function is already on the path, so it calls itself. Only the result of
+            // the call that is in progress gets cached.
+            let mut usage = StackUsage::default();
+            usage.unbounded.insert(Unbounded::Recursion(name.to_string()));
+            return usage;
+        }
+
+        let mut deepest = StackUsage::default();
+        let mut unbounded = BTreeSet::new();
+        if node.map_or(false, |node| node.calls_indirectly) {
+            unbounded.insert(Unbounded::IndirectCall(name.to_string()));
+        }
+        let objects = self.objects;
+        let callees: BTreeSet<&'a str> = node
+            .into_iter()
+            .flat_map(|node| node.callees.iter().map(|(callee, _)| &callee[..]))
+            .chain(objects.references.get(symbol).into_iter().flatten().map(|callee| &callee[..]))
+            .collect();
+        for callee in callees {
+            let usage = ensure_sufficient_stack(|| self.stack_usage(callee));
+            unbounded.extend(usage.unbounded.iter().cloned());
+            if usage.bytes > deepest.bytes || deepest.path.is_empty() {
+                deepest = usage;
+            }
+        }
+        self.active.remove(symbol);
+
+        let frame = match objects.frame_sizes.get(symbol) {
+            Some(&bytes) => Frame::Bytes(bytes),
+            // A function that was codegened but isn't defined in any object file has no
+            // code of its own. One that is defined but has no frame size, for example
+            // because its object file was reused from the incremental compilation cache
+            // without `.stack_sizes`, can't be counted as 0 bytes.
+            None if node.is_some() && objects.complete && !objects.defined.contains(symbol) => {
+                Frame::Inlined
+            }
+            None => {
+                unbounded.insert(Unbounded::UnknownCallee(name.to_string()));
+                Frame::Unknown
+            }
+        };
+        let bytes = if let Frame::Bytes(bytes) = frame { bytes } else { 0 };
+        let mut path = vec![(name.to_string(), frame)];
+        path.extend(deepest.path);
+
+        let usage = StackUsage { bytes: bytes + deepest.bytes, path, unbounded };
+        self.results.insert(symbol, usage.clone());
+        usage
+    }
+}
+
+pub fn print_stack_usage(sess: &Session, codegen_results: &CodegenResults, roots: &[String]) {
+    let mut objects = ObjectInfo { complete: true, ..ObjectInfo::default() };
+    for obj in codegen_results.modules.iter().filter_map(|m| m.object.as_ref()) {
+        let result = fs::read(obj)
+            .map_err(|err| err.to_string())
+            .and_then(|data| read_object(&data, &mut objects));
+        if let Err(err) = result {
+            sess.warn(&format!("failed to read the stack sizes in {}: {}", obj.display(), err));
+            objects.complete = false;
+        }
+    }
+    if objects.frame_sizes.is_empty() {
+        sess.warn(
+            "`-Z print-stack-usage` found no frame sizes, \
+             LLVM only emits them for ELF targets",
+        );
+        return;
+    }
+
+    let call_graph = sess.code_stats.call_graph();
+    let mut names = FxHashMap::default();
+    for node in call_graph.iter() {
+        names.insert(&node.symbol[..], &node.name[..]);
+        names.extend(node.callees.iter().map(|(symbol, name)| (&symbol[..], &name[..])));
+    }
+    let mut analysis = StackUsageAnalysis {
+        nodes: call_graph.iter().map(|node| (&node.symbol[..], node)).collect(),
+        names,
+        objects: &objects,
+        results: FxHashMap::default(),
+        active: FxHashSet::default(),
+    };
+
+    for root in roots {
+        let node = match call_graph.iter().find(|node| node.name == *root || node.symbol == *root) {
+            Some(node) => node,
+            None => {
+                sess.warn(&format!(
+                    "`-Z print-stack-usage`: no function named `{}` was codegened",
+                    root
+                ));
+                continue;
+            }
+        };
+
+        let usage = analysis.stack_usage(&node.symbol);
+        println!(
+            "print-stack-usage `{}`: {}{} bytes",
+            node.name,
+            if usage.unbounded.is_empty() { "" } else { "at least " },
+            usage.bytes
+        );
+        let indent = "    ";
+        for (name, frame) in &usage.path {
+            match frame {
+                Frame::Bytes(bytes) => {
+                    println!("print-stack-usage {}`{}`: {} bytes", indent, name, bytes)
+                }
+                Frame::Inlined => println!("print-stack-usage {}`{}`: inlined", indent, name),
+                Frame::Unknown => println!("print-stack-usage {}`{}`: unknown", indent, name),
+            }
+        }
+        for reason in &usage.unbounded {
+            match reason {
+                Unbounded::Recursion(name) => {
+                    println!("print-stack-usage {}unbounded: `{}` is recursive", indent, name)
+                }
+                Unbounded::IndirectCall(name) => println!(
+                    "print-stack-usage {}unbounded: `{}` calls through a function pointer \
+                     or vtable",
+                    indent, name
+                ),
+                Unbounded::UnknownCallee(name) => println!(
+                    "print-stack-usage {}unbounded: the stack usage of `{}` is unknown",
+                    indent, name
+                ),
+            }
+        }
+    }
+}
+
+/// Reads the functions defined in an object file, the symbols referenced by the
+/// section of each of them and their frame sizes.
+fn read_object(data: &[u8], objects: &mut ObjectInfo) -> Result<(), String> {
+    let file = object::File::parse(data).map_err(|err| err.to_string())?;
+
+    let mut functions: FxHashMap<usize, Vec<&str>> = FxHashMap::default();
+    for (_, symbol) in file.symbols() {
+        if let (SymbolKind::Text, SymbolSection::Section(section), Some(name)) =
+            (symbol.kind(), symbol.section(), symbol.name())
+        {
+            functions.entry(section.0).or_default().push(name);
+            objects.defined.insert(name.to_string());
+        }
+    }
+
+    for section in file.sections() {
+        let callers = match functions.get(&section.index().0) {
+            Some(callers) => callers,
+            None => continue,
+        };
+        for (_, relocation) in section.relocations() {
+            let callees = match referenced_functions(&file, &functions, relocation.target()) {
+                Some(callees) => callees,
+                None => continue,
+            };
+            for caller in callers {
+                // Calls within a section need no relocation, so the recursive calls are
+                // the ones found by the mono item collector.
+                let callees = callees.iter().filter(|callee| *callee != caller);
+                objects
+                    .references
+                    .entry(caller.to_string())
+                    .or_default()
+                    .extend(callees.map(|callee| callee.to_string()));
+            }
+        }
+    }
+
+    read_stack_sizes(&file, &mut objects.frame_sizes)
+}
+
+/// Finds the functions a relocation in a function's section may refer to. A relocation
+/// against a section refers to any function in it. References to undefined symbols,
+/// which may be functions of other crates or of the C library, are kept as well.
+fn referenced_functions<'data>(
+    file: &object::File<'data>,
+    functions: &FxHashMap<usize, Vec<&'data str>>,
+    target: RelocationTarget,
+) -> Option<Vec<&'data str>> {
+    let section = match target {
+        RelocationTarget::Symbol(index) => {
+            let symbol = file.symbol_by_index(index).ok()?;
+            match (symbol.kind(), symbol.section()) {
+                (SymbolKind::Section, SymbolSection::Section(section)) => section,
+                // The GOT is referenced by position-independent code on some targets.
+                (_, SymbolSection::Undefined) if symbol.name() == Some("_GLOBAL_OFFSET_TABLE_") => {
+                    return None;
+                }
+                (SymbolKind::Text, _) | (SymbolKind::Unknown, SymbolSection::Undefined) => {
+                    return symbol.name().map(|name| vec![name]);
+                }
+                _ => return None,
+            }
+        }
+        RelocationTarget::Section(section) => section,
+        _ => return None,
+    };
+    functions.get(&section.0).cloned()
+}
+
+/// Reads the `.stack_sizes` sections of an object file, one of which follows each
+/// function's text section. Each entry is the address of a function, relocated against
+/// its symbol, followed by the ULEB128 size of its frame.
+fn read_stack_sizes(
+    file: &object::File<'_>,
+    frame_sizes: &mut FxHashMap<String, u64>,
+) -> Result<(), String> {
+    let address_size = if file.is_64() { 8 } else { 4 };
+
+    for section in file.sections().filter(|section| matches!(section.name(), Ok(".stack_sizes"))) {
+        let contents = section.data().map_err(|err| err.to_string())?;
+        let relocations: FxHashMap<u64, object::Relocation> = section.relocations().collect();
+
+        let mut offset = 0;
+        while offset < contents.len() {
+            let size_offset = offset + address_size;
+            // `read_u64_leb128` panics instead of stopping at the end of the slice.
+            let size_is_complete = contents
+                .get(size_offset..)
+                .map_or(false, |rest| rest.iter().any(|&byte| byte & 0x80 == 0));
+            if !size_is_complete {
+                return Err("truncated `.stack_sizes` section".to_string());
+            }
+            let (size, size_len) = leb128::read_u64_leb128(&contents[size_offset..]);
+
+            if let Some(relocation) = relocations.get(&(offset as u64)) {
+                let addend = if relocation.has_implicit_addend() {
+                    read_address(&contents[offset..size_offset], file.is_little_endian())
+                } else {
+                    relocation.addend() as u64
+                };
+                if let Some(name) = function_symbol(file, relocation.target(), addend) {
+                    let frame_size = frame_sizes.entry(name.to_string()).or_insert(0);
+                    *frame_size = cmp::max(*frame_size, size);
+                }
+            }
+            offset = size_offset + size_len;
+        }
+    }
+    Ok(())
+}
+
+fn read_address(bytes: &[u8], little_endian: bool) -> u64 {
+    let push_byte = |address: u64, &byte: &u8| address << 8 | u64::from(byte);
+    if little_endian {
+        bytes.iter().rev().fold(0, push_byte)
+    } else {
+        bytes.iter().fold(0, push_byte)
+    }
+}
+
+/// Finds the function a relocation points to. Assemblers may relocate against the
+/// symbol of the section the function is in rather than against the function itself.
+fn function_symbol<'data>(
+    file: &object::File<'data>,
+    target: RelocationTarget,
+    addend: u64,
+) -> Option<&'data str> {
+    let section = match target {
+        RelocationTarget::Symbol(index) => {
+            let symbol = file.symbol_by_index(index).ok()?;
+            if symbol.kind() != SymbolKind::Section {
+                return symbol.name();
+            }
+            match symbol.section() {
+                SymbolSection::Section(section) => section,
+                _ => return None,
+            }
+        }
+        RelocationTarget::Section(section) => section,
+        _ => return None,
+    };
+
+    // The lowest bit of the address of a Thumb function is set, so it is ignored.
+    file.symbols()
+        .find(|(_, symbol)| {
+            symbol.kind() == SymbolKind::Text
+                && symbol.section() == SymbolSection::Section(section)
+                && symbol.address() & !1 == addend & !1
+        })
+        .and_then(|(_, symbol)| symbol.name())
+}
@@ -28,7 +28,7 @@ use rustc_middle::middle::exported_symbols::SymbolExportLevel;
 use rustc_middle::ty::TyCtxt;
 use rustc_session::cgu_reuse_tracker::CguReuseTracker;
 use rustc_session::config::{self, CrateType, Lto, OutputFilenames, OutputType};
-use rustc_session::config::{Passes, SanitizerSet, SwitchWithOptPath};
+use rustc_session::config::{Passes, SanitizerSet, SwitchWithOptPath, DWARF_OBJECT_EXT};
 use rustc_session::Session;
 use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::{sym, Symbol};
@@ -275,9 +275,27 @@ impl ModuleConfig {
     }
 }
 
+/// Configuration passed to the function returned by `target_machine_factory`.
+pub struct TargetMachineFactoryConfig {
+    /// LLVM splits DWARF out of an object file if the target machine it is emitted with
+    /// knows the path of the DWARF object file, so that path can't be chosen later.
+    pub split_dwarf_file: Option<PathBuf>,
+}
+
+impl TargetMachineFactoryConfig {
+    pub fn new(
+        cgcx: &CodegenContext<impl WriteBackendMethods>,
+        module_name: &str,
+    ) -> TargetMachineFactoryConfig {
+        let split_dwarf_file =
+            cgcx.output_filenames.split_dwarf_path(cgcx.split_debuginfo, Some(module_name));
+        TargetMachineFactoryConfig { split_dwarf_file }
+    }
+}
+
 // HACK(eddyb) work around `#[derive]` producing wrong bounds for `Clone`.
 pub struct TargetMachineFactory<B: WriteBackendMethods>(
-    pub Arc<dyn Fn() -> Result<B::TargetMachine, String> + Send + Sync>,
+    pub Arc<dyn Fn(TargetMachineFactoryConfig) -> Result<B::TargetMachine, String> + Send + Sync>,
 );
 
 impl<B: WriteBackendMethods> Clone for TargetMachineFactory<B> {
@@ -311,6 +329,7 @@ pub struct CodegenContext<B: WriteBackendMethods> {
     pub target_pointer_width: String,
     pub target_arch: String,
     pub debuginfo: config::DebugInfo,
+    pub split_debuginfo: config::SplitDebuginfo,
 
     // Number of cgus excluding the allocator/metadata modules
     pub total_cgus: usize,
@@ -491,10 +510,14 @@ fn copy_all_cgu_workproducts_to_incr_comp_cache_dir(
 
     for module in compiled_modules.modules.iter().filter(|m| m.kind == ModuleKind::Regular) {
         let path = module.object.as_ref().cloned();
-
-        if let Some((id, product)) =
-            copy_cgu_workproduct_to_incr_comp_cache_dir(sess, &module.name, &path)
-        {
+        let dwarf_object_path = module.dwarf_object.as_ref().cloned();
+
+        if let Some((id, product)) = copy_cgu_workproduct_to_incr_comp_cache_dir(
+            sess,
+            &module.name,
+            &path,
+            &dwarf_object_path,
+        ) {
             work_products.insert(id, product);
         }
     }
@@ -834,27 +857,38 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
     module_config: &ModuleConfig,
 ) -> Result<WorkItemResult<B>, FatalError> {
     let incr_comp_session_dir = cgcx.incr_comp_session_dir.as_ref().unwrap();
-    let mut object = None;
-    if let Some(saved_file) = module.source.saved_file {
-        let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
-        object = Some(obj_out.clone());
-        let source_file = in_incr_comp_dir(&incr_comp_session_dir, &saved_file);
+    let load_from_incr_comp_dir = |output_path: &Path, saved_file: &str| {
+        let source_file = in_incr_comp_dir(&incr_comp_session_dir, saved_file);
         debug!(
             "copying pre-existing module `{}` from {:?} to {}",
             module.name,
             source_file,
-            obj_out.display()
+            output_path.display()
         );
-        if let Err(err) = link_or_copy(&source_file, &obj_out) {
+        if let Err(err) = link_or_copy(&source_file, output_path) {
             let diag_handler = cgcx.create_diag_handler();
             diag_handler.err(&format!(
                 "unable to copy {} to {}: {}",
                 source_file.display(),
-                obj_out.display(),
+                output_path.display(),
                 err
             ));
         }
-    }
+    };
+
+    let object = module.source.saved_file.as_ref().map(|saved_file| {
+        let obj_out = cgcx.output_filenames.temp_path(OutputType::Object, Some(&module.name));
+        load_from_incr_comp_dir(&obj_out, saved_file);
+        obj_out
+    });
+
+    // The skeleton units in the object file refer to the DWARF object at the
+    // same path it was written to originally.
+    let dwarf_object = module.source.saved_dwarf_object.as_ref().map(|saved_dwarf_object| {
+        let dwo_out = cgcx.output_filenames.temp_path_ext(DWARF_OBJECT_EXT, Some(&module.name));
+        load_from_incr_comp_dir(&dwo_out, saved_dwarf_object);
+        dwo_out
+    });
 
     assert_eq!(object.is_some(), module_config.emit_obj != EmitObj::None);
 
@@ -862,6 +896,7 @@ fn execute_copy_from_cache_work_item<B: ExtraBackendMethods>(
         name: module.name,
         kind: ModuleKind::Regular,
         object,
+        dwarf_object,
         bytecode: None,
     }))
 }
@@ -1022,6 +1057,7 @@ fn start_executing_work<B: ExtraBackendMethods>(
         target_pointer_width: tcx.sess.target.target.target_pointer_width.clone(),
         target_arch: tcx.sess.target.target.arch.clone(),
         debuginfo: tcx.sess.opts.debuginfo,
+        split_debuginfo: tcx.sess.split_debuginfo(),
     };
 
     // This is the "main loop" of parallel work happening for parallel codegen.
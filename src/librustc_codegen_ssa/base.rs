@@ -45,7 +45,7 @@ use rustc_middle::ty::{self, Instance, Ty, TyCtxt};
 use rustc_session::cgu_reuse_tracker::CguReuse;
 use rustc_session::config::{self, EntryFnType};
 use rustc_session::utils::NativeLibKind;
-use rustc_session::Session;
+use rustc_session::{MonoItemSizeInfo, Session};
 use rustc_span::Span;
 use rustc_symbol_mangling::test as symbol_names_test;
 use rustc_target::abi::{Abi, Align, LayoutOf, Scalar, VariantIdx};
@@ -386,6 +386,26 @@ pub fn codegen_instance<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
     info!("codegen_instance({})", instance);
 
     mir::codegen_mir::<Bx>(cx, instance);
+
+    if cx.sess().opts.debugging_opts.print_mono_item_sizes {
+        record_mono_item_size::<Bx>(cx, instance);
+    }
+}
+
+fn record_mono_item_size<'a, 'tcx: 'a, Bx: BuilderMethods<'a, 'tcx>>(
+    cx: &'a Bx::CodegenCx,
+    instance: Instance<'tcx>,
+) {
+    let tcx = cx.tcx();
+    let mir = tcx.instance_mir(instance.def);
+    let mir_statements: usize = mir.basic_blocks().iter().map(|data| data.statements.len()).sum();
+    cx.sess().code_stats.record_mono_item_size(MonoItemSizeInfo {
+        def_path: tcx.def_path_str(instance.def_id()),
+        instance: instance.to_string(),
+        cgu: cx.codegen_unit().name().to_string(),
+        mir_statements: mir_statements as u64,
+        llvm_instructions: cx.instruction_count(cx.get_fn(instance)) as u64,
+    });
 }
 
 /// Creates the `main` function which will initialize the rust runtime and call
@@ -660,6 +680,16 @@ pub fn codegen_crate<B: ExtraBackendMethods>(
             });
             // Pre compile some CGUs
             pre_compiled_cgus = Some(pre_compile_cgus(&cgu_reuse));
+
+            // Reused CGUs aren't codegened, so their functions can't be measured.
+            if tcx.sess.opts.debugging_opts.print_mono_item_sizes
+                && cgu_reuse.iter().any(|reuse| *reuse != CguReuse::No)
+            {
+                tcx.sess.warn(
+                    "`-Z print-mono-item-sizes` does not include the functions of codegen units \
+                     reused from the incremental compilation cache",
+                );
+            }
         }
 
         let cgu_reuse = cgu_reuse[i];
@@ -1,6 +1,8 @@
 use crate::traits::*;
 
 use rustc_middle::ty::{self, Instance, Ty};
+use rustc_session::config::SanitizerSet;
+use rustc_symbol_mangling::typeid::typeid_for_vtable;
 use rustc_target::abi::call::FnAbi;
 
 #[derive(Copy, Clone, Debug)]
@@ -121,6 +123,11 @@ pub fn get_vtable<'tcx, Cx: CodegenMethods<'tcx>>(
 
     cx.create_vtable_metadata(ty, vtable);
 
+    // Calls through trait objects check the vtable they load the method from.
+    if tcx.sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI) {
+        cx.add_type_metadata(vtable, typeid_for_vtable(tcx, trait_ref));
+    }
+
     cx.vtables().borrow_mut().insert((ty, trait_ref), vtable);
     vtable
 }
@@ -17,8 +17,10 @@ use rustc_middle::mir::interpret::{AllocId, ConstValue, Pointer, Scalar};
 use rustc_middle::mir::AssertKind;
 use rustc_middle::ty::layout::{FnAbiExt, HasTyCtxt};
 use rustc_middle::ty::{self, Instance, Ty, TypeFoldable};
+use rustc_session::config::SanitizerSet;
 use rustc_span::source_map::Span;
 use rustc_span::{sym, Symbol};
+use rustc_symbol_mangling::typeid::{typeid_for_fnabi, typeid_for_vtable};
 use rustc_target::abi::call::{ArgAbi, FnAbi, PassMode};
 use rustc_target::abi::{self, LayoutOf};
 use rustc_target::spec::abi::Abi;
@@ -342,6 +344,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                 let fn_abi = FnAbi::of_instance(&bx, virtual_drop, &[]);
                 let vtable = args[1];
                 args = &args[..1];
+                bx = self.cfi_vtable_test(bx, vtable, ty);
                 (meth::DESTRUCTOR.get_fn(&mut bx, vtable, &fn_abi), fn_abi)
             }
             _ => (bx.get_fn_addr(drop_fn), FnAbi::of_instance(&bx, drop_fn, &[])),
@@ -747,6 +750,8 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     // the data pointer as the first argument
                     match op.val {
                         Pair(data_ptr, meta) => {
+                            bx =
+                                self.cfi_vtable_test(bx, meta, instance.unwrap().substs.type_at(0));
                             llfn = Some(
                                 meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi),
                             );
@@ -757,6 +762,7 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
                     }
                 } else if let Ref(data_ptr, Some(meta), _) = op.val {
                     // by-value dynamic dispatch
+                    bx = self.cfi_vtable_test(bx, meta, instance.unwrap().substs.type_at(0));
                     llfn = Some(meth::VirtualIndex::from_index(idx).get_fn(&mut bx, meta, &fn_abi));
                     llargs.push(data_ptr);
                     continue;
@@ -812,6 +818,14 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
             _ => span_bug!(span, "no llfn for call"),
         };
 
+        // Virtual calls were checked when their vtable was loaded.
+        if let ty::FnPtr(_) = callee.layout.ty.kind {
+            if self.cfi_enabled() {
+                let typeid = typeid_for_fnabi(bx.tcx(), &fn_abi);
+                bx = self.cfi_type_test(bx, fn_ptr, typeid);
+            }
+        }
+
         if let Some((_, target)) = destination.as_ref() {
             helper.maybe_sideeffect(self.mir, &mut bx, &[*target]);
         }
@@ -1244,6 +1258,42 @@ impl<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>> FunctionCx<'a, 'tcx, Bx> {
         cx.type_struct(&[cx.type_i8p(), cx.type_i32()], false)
     }
 
+    /// Whether indirect calls in this function are checked by `-Zsanitizer=cfi`.
+    fn cfi_enabled(&self) -> bool {
+        let tcx = self.cx.tcx();
+        tcx.sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI)
+            && !tcx.codegen_fn_attrs(self.instance.def_id()).no_sanitize.contains(SanitizerSet::CFI)
+    }
+
+    /// Aborts unless `pointer` points to a function or vtable of the type `typeid`, and returns
+    /// the builder that continues with the call.
+    fn cfi_type_test(&self, mut bx: Bx, pointer: Bx::Value, typeid: String) -> Bx {
+        let typeid = bx.typeid_metadata(typeid);
+        let is_member = bx.type_test(pointer, typeid);
+
+        let pass = bx.build_sibling_block("cfi.pass");
+        let mut fail = bx.build_sibling_block("cfi.fail");
+        bx.cond_br(is_member, pass.llbb(), fail.llbb());
+
+        fail.abort();
+        fail.unreachable();
+        pass
+    }
+
+    /// Aborts unless `vtable` is a vtable of the trait object type `object_ty`, so that the
+    /// methods loaded from it can be called without testing them.
+    fn cfi_vtable_test(&self, bx: Bx, vtable: Bx::Value, object_ty: Ty<'tcx>) -> Bx {
+        if !self.cfi_enabled() {
+            return bx;
+        }
+        let trait_ref = match object_ty.kind {
+            ty::Dynamic(data, _) => data.principal(),
+            _ => bug!("expected a trait object, got {:?}", object_ty),
+        };
+        let typeid = typeid_for_vtable(bx.tcx(), trait_ref);
+        self.cfi_type_test(bx, vtable, typeid)
+    }
+
     fn unreachable_block(&mut self) -> Bx::BasicBlock {
         self.unreachable_block.unwrap_or_else(|| {
             let mut bx = self.new_block("unreachable");
@@ -18,5 +18,8 @@ pub trait MiscMethods<'tcx>: BackendTypes {
     fn used_statics(&self) -> &RefCell<Vec<Self::Value>>;
     fn set_frame_pointer_elimination(&self, llfn: Self::Function);
     fn apply_target_cpu_attr(&self, llfn: Self::Function);
+    /// Returns the number of backend instructions in the body of `llfn`, before
+    /// any backend optimizations have run.
+    fn instruction_count(&self, llfn: Self::Function) -> usize;
     fn create_used_variable(&self);
 }
@@ -132,6 +132,20 @@ pub trait ArgAbiMethods<'tcx>: HasCodegen<'tcx> {
     fn arg_memory_ty(&self, arg_abi: &ArgAbi<'tcx, Ty<'tcx>>) -> Self::Type;
 }
 
-pub trait TypeMethods<'tcx>: DerivedTypeMethods<'tcx> + LayoutTypeMethods<'tcx> {}
+/// Type membership, which `-Zsanitizer=cfi` uses to check indirect calls.
+pub trait TypeMembershipMethods<'tcx>: Backend<'tcx> {
+    /// Tags the function or vtable `global` as a member of the type `typeid`.
+    fn add_type_metadata(&self, global: Self::Value, typeid: String);
+    /// Returns the value that identifies the type `typeid` in a type test.
+    fn typeid_metadata(&self, typeid: String) -> Self::Value;
+}
+
+pub trait TypeMethods<'tcx>:
+    DerivedTypeMethods<'tcx> + LayoutTypeMethods<'tcx> + TypeMembershipMethods<'tcx>
+{
+}
 
-impl<T> TypeMethods<'tcx> for T where Self: DerivedTypeMethods<'tcx> + LayoutTypeMethods<'tcx> {}
+impl<T> TypeMethods<'tcx> for T where
+    Self: DerivedTypeMethods<'tcx> + LayoutTypeMethods<'tcx> + TypeMembershipMethods<'tcx>
+{
+}
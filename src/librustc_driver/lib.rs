@@ -33,7 +33,7 @@ use rustc_save_analysis as save;
 use rustc_save_analysis::DumpHandler;
 use rustc_serialize::json::{self, ToJson};
 use rustc_session::config::nightly_options;
-use rustc_session::config::{ErrorOutputType, Input, OutputType, PrintRequest};
+use rustc_session::config::{ErrorOutputType, Input, OutputType, PrintRequest, TypeSizesFormat};
 use rustc_session::getopts;
 use rustc_session::lint::{Lint, LintId};
 use rustc_session::{config, DiagnosticOutput, Session};
@@ -388,7 +388,15 @@ pub fn run_compiler(
             queries.ongoing_codegen()?;
 
             if sess.opts.debugging_opts.print_type_sizes {
-                sess.code_stats.print_type_sizes();
+                let filter = sess.opts.debugging_opts.print_type_sizes_filter.as_deref();
+                match sess.opts.debugging_opts.print_type_sizes_format {
+                    TypeSizesFormat::Human => sess.code_stats.print_type_sizes(filter),
+                    TypeSizesFormat::Json => sess.code_stats.print_type_sizes_json(filter),
+                }
+            }
+
+            if sess.opts.debugging_opts.print_mono_item_sizes {
+                sess.code_stats.print_mono_item_sizes();
             }
 
             let linker = queries.linker()?;
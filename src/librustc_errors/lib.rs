@@ -26,7 +26,7 @@ use rustc_span::{Loc, MultiSpan, Span};
 use std::borrow::Cow;
 use std::panic;
 use std::path::Path;
-use std::{error, fmt};
+use std::{error, fmt, mem};
 
 use termcolor::{Color, ColorSpec};
 
@@ -321,6 +321,10 @@ struct HandlerInner {
 
     /// The warning count, used for a recap upon finishing
     deduplicated_warn_count: usize,
+
+    /// If true, diagnostics are counted but not emitted
+    /// (see `Handler::with_diagnostics_discarded`).
+    discard_diagnostics: bool,
 }
 
 /// A key denoting where from a diagnostic was stashed.
@@ -430,6 +434,7 @@ impl Handler {
                 emitted_diagnostic_codes: Default::default(),
                 emitted_diagnostics: Default::default(),
                 stashed_diagnostics: Default::default(),
+                discard_diagnostics: false,
             }),
         }
     }
@@ -459,6 +464,29 @@ impl Handler {
         inner.stashed_diagnostics = Default::default();
     }
 
+    /// Runs `f` without emitting any of the diagnostics it reports, and returns whether one of
+    /// them was an error. Once `f` returns, its errors no longer count towards the error count,
+    /// and the bugs it delayed are dropped if it reported an error.
+    ///
+    /// NOTE: this is only meant for tools like rustdoc, which run queries whose errors
+    /// don't concern their output.
+    pub fn with_diagnostics_discarded<R>(&self, f: impl FnOnce() -> R) -> (R, bool) {
+        let (err_count, delayed_span_bugs, discard_diagnostics) = {
+            let mut inner = self.inner.borrow_mut();
+            let discard_diagnostics = mem::replace(&mut inner.discard_diagnostics, true);
+            (inner.err_count, inner.delayed_span_bugs.len(), discard_diagnostics)
+        };
+        let result = f();
+        let mut inner = self.inner.borrow_mut();
+        inner.discard_diagnostics = discard_diagnostics;
+        let had_errors = inner.err_count > err_count;
+        if had_errors {
+            inner.err_count = err_count;
+            inner.delayed_span_bugs.truncate(delayed_span_bugs);
+        }
+        (result, had_errors)
+    }
+
     /// Stash a given diagnostic with the given `Span` and `StashKey` as the key for later stealing.
     pub fn stash_diagnostic(&self, span: Span, key: StashKey, diag: Diagnostic) {
         let mut inner = self.inner.borrow_mut();
@@ -742,6 +770,13 @@ impl HandlerInner {
             return;
         }
 
+        if self.discard_diagnostics {
+            if diagnostic.is_error() {
+                self.err_count += 1;
+            }
+            return;
+        }
+
         (*TRACK_DIAGNOSTICS)(diagnostic);
 
         if let Some(ref code) = diagnostic.code {
@@ -172,6 +172,7 @@ impl<'a> ExtCtxt<'a> {
             pat,
             ty: None,
             init: Some(ex),
+            els: None,
             id: ast::DUMMY_NODE_ID,
             span: sp,
             attrs: AttrVec::new(),
@@ -185,6 +186,7 @@ impl<'a> ExtCtxt<'a> {
             pat: self.pat_wild(span),
             ty: Some(ty),
             init: None,
+            els: None,
             id: ast::DUMMY_NODE_ID,
             span,
             attrs: AttrVec::new(),
@@ -294,7 +296,7 @@ impl<'a> ExtCtxt<'a> {
         path: ast::Path,
         fields: Vec<ast::Field>,
     ) -> P<ast::Expr> {
-        self.expr(span, ast::ExprKind::Struct(path, fields, None))
+        self.expr(span, ast::ExprKind::Struct(path, fields, ast::StructRest::None))
     }
     pub fn expr_struct_ident(
         &self,
@@ -585,6 +585,25 @@ declare_features! (
     /// Allows `if let` guard in match arms.
     (active, if_let_guard, "1.47.0", Some(51114), None),
 
+    /// Allows rustdoc to show the `#[cfg]` of items, and `#[doc(cfg_hide(...))]`.
+    (active, doc_auto_cfg, "1.48.0", Some(43781), None),
+
+    /// Allows `let PAT = EXPR else { .. };` with a refutable pattern and a diverging `else`.
+    (active, let_else, "1.48.0", None, None),
+
+    /// Allows destructuring assignments such as `(a, b) = (b, a)`.
+    (active, destructuring_assignment, "1.48.0", None, None),
+
+    /// Allows closures to capture disjoint fields and paths of a variable
+    /// instead of the whole variable.
+    (active, capture_disjoint_fields, "1.48.0", None, None),
+
+    /// Allows inline `const { ... }` blocks in expressions and patterns.
+    (active, inline_const, "1.48.0", None, None),
+
+    /// Allows the `#[must_not_suspend]` attribute and the `must_not_suspend` lint.
+    (active, must_not_suspend, "1.48.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
@@ -604,4 +623,6 @@ pub const INCOMPLETE_FEATURES: &[Symbol] = &[
     sym::const_trait_bound_opt_out,
     sym::lazy_normalization_consts,
     sym::specialization,
+    sym::capture_disjoint_fields,
+    sym::inline_const,
 ];
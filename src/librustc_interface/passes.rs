@@ -991,6 +991,11 @@ pub fn start_codegen<'tcx>(
         codegen_backend.codegen_crate(tcx, metadata, need_metadata_module)
     });
 
+    // All lints have been emitted at this point, including the MIR lints of `optimized_mir` and
+    // of the monomorphization collector, so `#[expect]` attributes whose lint never fired can be
+    // reported.
+    tcx.sess.time("lint_expectation_checking", || rustc_lint::check_expectations(tcx));
+
     info!("Post-codegen\n{:?}", tcx.debug_stats());
 
     if tcx.sess.opts.output_types.contains_key(&OutputType::Mir) {
@@ -311,6 +311,7 @@ impl LintStore {
                     Level::Warn => "-W",
                     Level::Deny => "-D",
                     Level::Forbid => "-F",
+                    Level::Expect(_) => unreachable!(),
                 },
                 lint_name
             );
@@ -56,7 +56,7 @@ impl<'a, T: EarlyLintPass> EarlyContextAndPass<'a, T> {
         F: FnOnce(&mut Self),
     {
         let is_crate_node = id == ast::CRATE_NODE_ID;
-        let push = self.context.builder.push(attrs, &self.context.lint_store, is_crate_node);
+        let push = self.context.builder.push(attrs, &self.context.lint_store, is_crate_node, None);
         self.check_id(id);
         self.enter_attrs(attrs);
         f(self);
@@ -0,0 +1,49 @@
+use rustc_hir::def_id::LOCAL_CRATE;
+use rustc_middle::lint::struct_lint_level;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::lint::builtin::UNFULFILLED_LINT_EXPECTATIONS;
+
+/// Reports `unfulfilled_lint_expectations` for every lint name in an `#[expect]` attribute
+/// that the lint was never emitted for.
+///
+/// This has to run after all early, late and MIR lints have been emitted, since those fulfill
+/// the expectations as they are emitted. Some MIR lints are only emitted during codegen, from
+/// `optimized_mir` and the monomorphization collector, so this runs once codegen is done.
+pub fn check_expectations(tcx: TyCtxt<'_>) {
+    let lint_levels = tcx.lint_levels(LOCAL_CRATE);
+
+    let mut fulfilled = tcx.sess.fulfilled_lint_expectations();
+    for (unstable_id, stable_id) in &lint_levels.unstable_to_stable_ids {
+        if fulfilled.contains(unstable_id) {
+            fulfilled.insert(*stable_id);
+        }
+    }
+
+    for (id, expectation) in &lint_levels.expectations {
+        if fulfilled.contains(id) {
+            continue;
+        }
+
+        let (level, src) = lint_levels.sets.get_lint_level(
+            UNFULFILLED_LINT_EXPECTATIONS,
+            expectation.lint_set,
+            None,
+            tcx.sess,
+        );
+        struct_lint_level(
+            tcx.sess,
+            UNFULFILLED_LINT_EXPECTATIONS,
+            level,
+            src,
+            Some(expectation.emission_span.into()),
+            |lint| {
+                let mut db = lint.build("this lint expectation is unfulfilled");
+                db.note(&format!("the `{}` lint was never emitted here", expectation.lint_name));
+                if let Some(rationale) = expectation.reason {
+                    db.note(&rationale.as_str());
+                }
+                db.emit();
+            },
+        );
+    }
+}
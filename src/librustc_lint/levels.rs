@@ -11,10 +11,12 @@ use rustc_hir::def_id::{CrateNum, LOCAL_CRATE};
 use rustc_hir::{intravisit, HirId};
 use rustc_middle::hir::map::Map;
 use rustc_middle::lint::LintDiagnosticBuilder;
-use rustc_middle::lint::{struct_lint_level, LintLevelMap, LintLevelSets, LintSet, LintSource};
+use rustc_middle::lint::{
+    struct_lint_level, LintExpectation, LintLevelMap, LintLevelSets, LintSet, LintSource,
+};
 use rustc_middle::ty::query::Providers;
 use rustc_middle::ty::TyCtxt;
-use rustc_session::lint::{builtin, Level, Lint, LintId};
+use rustc_session::lint::{builtin, Level, Lint, LintExpectationId, LintId};
 use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::symbol::{sym, Symbol};
@@ -29,7 +31,7 @@ fn lint_levels(tcx: TyCtxt<'_>, cnum: CrateNum) -> LintLevelMap {
     let mut builder = LintLevelMapBuilder { levels, tcx, store };
     let krate = tcx.hir().krate();
 
-    let push = builder.levels.push(&krate.item.attrs, &store, true);
+    let push = builder.levels.push(&krate.item.attrs, &store, true, Some(hir::CRATE_HIR_ID));
     builder.levels.register_id(hir::CRATE_HIR_ID);
     for macro_def in krate.exported_macros {
         builder.levels.register_id(macro_def.hir_id);
@@ -44,6 +46,8 @@ pub struct LintLevelsBuilder<'s> {
     sess: &'s Session,
     sets: LintLevelSets,
     id_to_set: FxHashMap<HirId, u32>,
+    expectations: Vec<(LintExpectationId, LintExpectation)>,
+    unstable_to_stable_ids: FxHashMap<LintExpectationId, LintExpectationId>,
     cur: u32,
     warn_about_weird_lints: bool,
 }
@@ -60,6 +64,8 @@ impl<'s> LintLevelsBuilder<'s> {
             sets: LintLevelSets::new(),
             cur: 0,
             id_to_set: Default::default(),
+            expectations: Vec::new(),
+            unstable_to_stable_ids: Default::default(),
             warn_about_weird_lints,
         };
         builder.process_command_line(sess, store);
@@ -107,6 +113,10 @@ impl<'s> LintLevelsBuilder<'s> {
     /// * Lint levels will be updated based on the attributes provided
     /// * Lint attributes are validated, e.g., a `#[forbid]` can't be switched to
     ///   `#[allow]`
+    /// * The lint names in `#[expect]` attributes are recorded as expectations
+    ///
+    /// `source_hir_id` is the HIR node the attributes are on, when building the lint levels of
+    /// the HIR, which gives the expectations a stable id.
     ///
     /// Don't forget to call `pop`!
     pub fn push(
@@ -114,14 +124,26 @@ impl<'s> LintLevelsBuilder<'s> {
         attrs: &[ast::Attribute],
         store: &LintStore,
         is_crate_node: bool,
+        source_hir_id: Option<HirId>,
     ) -> BuilderPush {
         let mut specs = FxHashMap::default();
+        let mut expectations = Vec::new();
         let sess = self.sess;
         let bad_attr = |span| struct_span_err!(sess, span, E0452, "malformed lint attribute input");
-        for attr in attrs {
-            let level = match Level::from_symbol(attr.name_or_empty()) {
-                None => continue,
-                Some(lvl) => lvl,
+        for (attr_index, attr) in attrs.iter().enumerate() {
+            let attr_level = match attr.name_or_empty() {
+                sym::expect => Level::Expect(match source_hir_id {
+                    Some(hir_id) => LintExpectationId::Stable {
+                        hir_id,
+                        attr_index: attr_index as u16,
+                        lint_index: 0,
+                    },
+                    None => LintExpectationId::Unstable { attr_id: attr.id, lint_index: 0 },
+                }),
+                name => match Level::from_symbol(name) {
+                    None => continue,
+                    Some(lvl) => lvl,
+                },
             };
 
             let meta = unwrap_or!(attr.meta(), continue);
@@ -175,7 +197,26 @@ impl<'s> LintLevelsBuilder<'s> {
                 }
             }
 
-            for li in metas {
+            for (lint_index, li) in metas.iter().enumerate() {
+                // Every lint name in an `#[expect]` is a separate expectation.
+                let level = match attr_level {
+                    Level::Expect(id) => Level::Expect(id.with_lint_index(lint_index as u16)),
+                    level => level,
+                };
+                // The early lints fulfill the expectations with the id of the AST attribute.
+                if let (Level::Expect(id), Some(_)) = (level, source_hir_id) {
+                    let unstable_id = LintExpectationId::Unstable {
+                        attr_id: attr.id,
+                        lint_index: lint_index as u16,
+                    };
+                    self.unstable_to_stable_ids.insert(unstable_id, id);
+                }
+                let mut expect = |lint_name| {
+                    if let Level::Expect(id) = level {
+                        expectations.push((id, lint_name, reason, li.span()));
+                    }
+                };
+
                 let meta_item = match li.meta_item() {
                     Some(meta_item) if meta_item.is_word() => meta_item,
                     _ => {
@@ -218,6 +259,7 @@ impl<'s> LintLevelsBuilder<'s> {
                 let name = meta_item.path.segments.last().expect("empty lint name").ident.name;
                 match store.check_lint_name(&name.as_str(), tool_name) {
                     CheckLintNameResult::Ok(ids) => {
+                        expect(name);
                         let src = LintSource::Node(name, li.span(), reason);
                         for &id in ids {
                             self.check_gated_lint(id, attr.span);
@@ -229,11 +271,9 @@ impl<'s> LintLevelsBuilder<'s> {
                         match result {
                             Ok(ids) => {
                                 let complete_name = &format!("{}::{}", tool_name.unwrap(), name);
-                                let src = LintSource::Node(
-                                    Symbol::intern(complete_name),
-                                    li.span(),
-                                    reason,
-                                );
+                                let complete_name = Symbol::intern(complete_name);
+                                expect(complete_name);
+                                let src = LintSource::Node(complete_name, li.span(), reason);
                                 for id in ids {
                                     specs.insert(*id, (level, src));
                                 }
@@ -266,11 +306,9 @@ impl<'s> LintLevelsBuilder<'s> {
                                     },
                                 );
 
-                                let src = LintSource::Node(
-                                    Symbol::intern(&new_lint_name),
-                                    li.span(),
-                                    reason,
-                                );
+                                let new_lint_name = Symbol::intern(&new_lint_name);
+                                expect(new_lint_name);
+                                let src = LintSource::Node(new_lint_name, li.span(), reason);
                                 for id in ids {
                                     specs.insert(*id, (level, src));
                                 }
@@ -279,7 +317,8 @@ impl<'s> LintLevelsBuilder<'s> {
                                 // If Tool(Err(None, _)) is returned, then either the lint does not
                                 // exist in the tool or the code was not compiled with the tool and
                                 // therefore the lint was never added to the `LintStore`. To detect
-                                // this is the responsibility of the lint tool.
+                                // this is the responsibility of the lint tool. No expectation is
+                                // recorded either, as the lint can't be emitted without the tool.
                             }
                         }
                     }
@@ -422,6 +461,12 @@ impl<'s> LintLevelsBuilder<'s> {
             self.sets.list.push(LintSet::Node { specs, parent: prev });
         }
 
+        for (id, lint_name, reason, emission_span) in expectations {
+            let expectation =
+                LintExpectation { lint_name, reason, emission_span, lint_set: self.cur };
+            self.expectations.push((id, expectation));
+        }
+
         BuilderPush { prev, changed: prev != self.cur }
     }
 
@@ -473,7 +518,12 @@ impl<'s> LintLevelsBuilder<'s> {
     }
 
     pub fn build_map(self) -> LintLevelMap {
-        LintLevelMap { sets: self.sets, id_to_set: self.id_to_set }
+        LintLevelMap {
+            sets: self.sets,
+            id_to_set: self.id_to_set,
+            expectations: self.expectations,
+            unstable_to_stable_ids: self.unstable_to_stable_ids,
+        }
     }
 }
 
@@ -489,7 +539,7 @@ impl LintLevelMapBuilder<'_, '_> {
         F: FnOnce(&mut Self),
     {
         let is_crate_hir = id == hir::CRATE_HIR_ID;
-        let push = self.levels.push(attrs, self.store, is_crate_hir);
+        let push = self.levels.push(attrs, self.store, is_crate_hir, Some(id));
         if push.changed {
             self.levels.register_id(id);
         }
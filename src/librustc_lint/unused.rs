@@ -593,7 +593,8 @@ trait UnusedDelimLint {
     fn check_stmt(&mut self, cx: &EarlyContext<'_>, s: &ast::Stmt) {
         match s.kind {
             StmtKind::Local(ref local) if Self::LINT_EXPR_IN_PATTERN_MATCHING_CTX => {
-                if let Some(ref value) = local.init {
+                // The parser requires delimiters around some initializers of a `let...else`.
+                if let (Some(ref value), None) = (&local.init, &local.els) {
                     self.check_unused_delims_expr(
                         cx,
                         &value,
@@ -813,7 +814,14 @@ declare_lint! {
     "unnecessary braces around an expression"
 }
 
-declare_lint_pass!(UnusedBraces => [UNUSED_BRACES]);
+#[derive(Default)]
+pub struct UnusedBraces {
+    /// The anonymous constant of the inline `const` block being checked,
+    /// whose braces are required.
+    inline_const: Option<ast::NodeId>,
+}
+
+impl_lint_pass!(UnusedBraces => [UNUSED_BRACES]);
 
 impl UnusedDelimLint for UnusedBraces {
     const DELIM_STR: &'static str = "braces";
@@ -897,10 +905,17 @@ impl UnusedDelimLint for UnusedBraces {
 
 impl EarlyLintPass for UnusedBraces {
     fn check_expr(&mut self, cx: &EarlyContext<'_>, e: &ast::Expr) {
+        if let ExprKind::ConstBlock(ref anon_const) = e.kind {
+            self.inline_const = Some(anon_const.id);
+        }
         <Self as UnusedDelimLint>::check_expr(self, cx, e)
     }
 
     fn check_anon_const(&mut self, cx: &EarlyContext<'_>, c: &ast::AnonConst) {
+        if self.inline_const == Some(c.id) {
+            self.inline_const = None;
+            return;
+        }
         self.check_unused_delims_expr(cx, &c.value, UnusedDelimsCtx::AnonConst, false, None, None);
     }
 
@@ -5,7 +5,7 @@ use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher};
 use rustc_errors::{DiagnosticBuilder, DiagnosticId};
 use rustc_hir::HirId;
-use rustc_session::lint::{builtin, Level, Lint, LintId};
+use rustc_session::lint::{builtin, Level, Lint, LintExpectationId, LintId};
 use rustc_session::{DiagnosticMessageId, Session};
 use rustc_span::hygiene::MacroKind;
 use rustc_span::source_map::{DesugaringKind, ExpnKind, MultiSpan};
@@ -121,6 +121,26 @@ impl LintLevelSets {
 pub struct LintLevelMap {
     pub sets: LintLevelSets,
     pub id_to_set: FxHashMap<HirId, u32>,
+    /// The lint names of all `#[expect]` attributes, in the order they were found.
+    pub expectations: Vec<(LintExpectationId, LintExpectation)>,
+    /// The stable id of each expectation, keyed by the unstable id the early lints fulfill it
+    /// with. It's not hashed, as it's only valid for the current session.
+    pub unstable_to_stable_ids: FxHashMap<LintExpectationId, LintExpectationId>,
+}
+
+/// A lint name in an `#[expect]` attribute, which should cause the lint to be emitted at least
+/// once.
+#[derive(Clone, Copy, Debug, HashStable)]
+pub struct LintExpectation {
+    /// The lint name as written in the attribute, including the tool name if any.
+    pub lint_name: Symbol,
+    /// The RFC 2383 reason of the attribute.
+    pub reason: Option<Symbol>,
+    /// The span of the lint name, where `unfulfilled_lint_expectations` is reported.
+    pub emission_span: Span,
+    /// The lint set created by the attribute, which decides the level of
+    /// `unfulfilled_lint_expectations` for this expectation.
+    pub lint_set: u32,
 }
 
 impl LintLevelMap {
@@ -144,7 +164,8 @@ impl LintLevelMap {
 impl<'a> HashStable<StableHashingContext<'a>> for LintLevelMap {
     #[inline]
     fn hash_stable(&self, hcx: &mut StableHashingContext<'a>, hasher: &mut StableHasher) {
-        let LintLevelMap { ref sets, ref id_to_set } = *self;
+        let LintLevelMap { ref sets, ref id_to_set, ref expectations, unstable_to_stable_ids: _ } =
+            *self;
 
         id_to_set.hash_stable(hcx, hasher);
 
@@ -170,6 +191,8 @@ impl<'a> HashStable<StableHashingContext<'a>> for LintLevelMap {
                     }
                 }
             }
+
+            expectations.hash_stable(hcx, hasher);
         })
     }
 }
@@ -211,6 +234,11 @@ pub fn struct_lint_level<'s, 'd>(
             (Level::Allow, _) => {
                 return;
             }
+            (Level::Expect(expectation_id), _) => {
+                // The lint is suppressed, but the `#[expect]` it came from is now fulfilled.
+                sess.fulfill_lint_expectation(expectation_id);
+                return;
+            }
             (Level::Warn, Some(span)) => sess.struct_span_warn(span, ""),
             (Level::Warn, None) => sess.struct_warn(""),
             (Level::Deny | Level::Forbid, Some(span)) => sess.struct_span_err(span, ""),
@@ -255,7 +283,7 @@ pub fn struct_lint_level<'s, 'd>(
                     Level::Warn => "-W",
                     Level::Deny => "-D",
                     Level::Forbid => "-F",
-                    Level::Allow => panic!(),
+                    Level::Allow | Level::Expect(_) => panic!(),
                 };
                 let hyphen_case_lint_name = name.replace("_", "-");
                 if lint_flag_val.as_str() == name {
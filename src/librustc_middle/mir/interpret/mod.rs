@@ -198,6 +198,7 @@ enum AllocDiscriminant {
     Alloc,
     Fn,
     Static,
+    Vtable,
 }
 
 pub fn specialized_encode_alloc_id<'tcx, E: TyEncoder<'tcx>>(
@@ -223,6 +224,13 @@ pub fn specialized_encode_alloc_id<'tcx, E: TyEncoder<'tcx>>(
             AllocDiscriminant::Static.encode(encoder)?;
             did.encode(encoder)?;
         }
+        GlobalAlloc::Vtable(alloc, ty, trait_ref) => {
+            trace!("encoding {:?} with vtable of {:?} for {:?}", alloc_id, ty, trait_ref);
+            AllocDiscriminant::Vtable.encode(encoder)?;
+            alloc.encode(encoder)?;
+            ty.encode(encoder)?;
+            trait_ref.encode(encoder)?;
+        }
     }
     Ok(())
 }
@@ -298,7 +306,7 @@ impl<'s> AllocDecodingSession<'s> {
                 ref mut entry @ State::Empty => {
                     // We are allowed to decode.
                     match alloc_kind {
-                        AllocDiscriminant::Alloc => {
+                        AllocDiscriminant::Alloc | AllocDiscriminant::Vtable => {
                             // If this is an allocation, we need to reserve an
                             // `AllocId` so we can decode cyclic graphs.
                             let alloc_id = decoder.tcx().reserve_alloc_id();
@@ -348,6 +356,19 @@ impl<'s> AllocDecodingSession<'s> {
                     decoder.tcx().set_alloc_id_same_memory(alloc_id, alloc);
                     Ok(alloc_id)
                 }
+                AllocDiscriminant::Vtable => {
+                    let alloc = <&'tcx Allocation as Decodable<_>>::decode(decoder)?;
+                    let ty = <Ty<'tcx> as Decodable<D>>::decode(decoder)?;
+                    let trait_ref =
+                        <Option<ty::PolyExistentialTraitRef<'tcx>> as Decodable<D>>::decode(
+                            decoder,
+                        )?;
+                    // We already have a reserved `AllocId`.
+                    let alloc_id = alloc_id.unwrap();
+                    trace!("decoded vtable alloc {:?} of {:?} for {:?}", alloc_id, ty, trait_ref);
+                    decoder.tcx().set_alloc_id_same_vtable(alloc_id, alloc, ty, trait_ref);
+                    Ok(alloc_id)
+                }
                 AllocDiscriminant::Fn => {
                     assert!(alloc_id.is_none());
                     trace!("creating fn alloc ID");
@@ -376,7 +397,7 @@ impl<'s> AllocDecodingSession<'s> {
 }
 
 /// An allocation in the global (tcx-managed) memory can be either a function pointer,
-/// a static, or a "real" allocation with some data in it.
+/// a static, a "real" allocation with some data in it, or a vtable.
 #[derive(Debug, Clone, Eq, PartialEq, Hash, TyDecodable, TyEncodable, HashStable)]
 pub enum GlobalAlloc<'tcx> {
     /// The alloc ID is used as a function pointer.
@@ -386,15 +407,20 @@ pub enum GlobalAlloc<'tcx> {
     Static(DefId),
     /// The alloc ID points to memory.
     Memory(&'tcx Allocation),
+    /// The alloc ID points to the vtable of a type for a trait, created by the interpreter.
+    /// Codegen emits the vtable it creates for the same type and trait instead of the memory,
+    /// so that it is the same to `-Zsanitizer=cfi`.
+    Vtable(&'tcx Allocation, Ty<'tcx>, Option<ty::PolyExistentialTraitRef<'tcx>>),
 }
 
 impl GlobalAlloc<'tcx> {
-    /// Panics if the `GlobalAlloc` does not refer to an `GlobalAlloc::Memory`
+    /// Panics if the `GlobalAlloc` does not refer to an `GlobalAlloc::Memory` or a
+    /// `GlobalAlloc::Vtable`
     #[track_caller]
     #[inline]
     pub fn unwrap_memory(&self) -> &'tcx Allocation {
         match *self {
-            GlobalAlloc::Memory(mem) => mem,
+            GlobalAlloc::Memory(mem) | GlobalAlloc::Vtable(mem, ..) => mem,
             _ => bug!("expected memory, got {:?}", self),
         }
     }
@@ -457,7 +483,9 @@ impl<'tcx> TyCtxt<'tcx> {
         let mut alloc_map = self.alloc_map.lock();
         match alloc {
             GlobalAlloc::Function(..) | GlobalAlloc::Static(..) => {}
-            GlobalAlloc::Memory(..) => bug!("Trying to dedup-reserve memory with real data!"),
+            GlobalAlloc::Memory(..) | GlobalAlloc::Vtable(..) => {
+                bug!("Trying to dedup-reserve memory with real data!")
+            }
         }
         if let Some(&alloc_id) = alloc_map.dedup.get(&alloc) {
             return alloc_id;
@@ -548,6 +576,34 @@ impl<'tcx> TyCtxt<'tcx> {
     fn set_alloc_id_same_memory(&self, id: AllocId, mem: &'tcx Allocation) {
         self.alloc_map.lock().alloc_map.insert_same(id, GlobalAlloc::Memory(mem));
     }
+
+    /// Freezes an `AllocId` created with `reserve` by pointing it at the `Allocation` of the
+    /// vtable of `ty` for `trait_ref`. Trying to call this function twice, even with the same
+    /// vtable will ICE the compiler.
+    pub fn set_alloc_id_vtable(
+        &self,
+        id: AllocId,
+        mem: &'tcx Allocation,
+        ty: Ty<'tcx>,
+        trait_ref: Option<ty::PolyExistentialTraitRef<'tcx>>,
+    ) {
+        let vtable = GlobalAlloc::Vtable(mem, ty, trait_ref);
+        if let Some(old) = self.alloc_map.lock().alloc_map.insert(id, vtable) {
+            bug!("tried to set allocation ID {}, but it was already existing as {:#?}", id, old);
+        }
+    }
+
+    /// Freezes an `AllocId` created with `reserve` by pointing it at the `Allocation` of a vtable.
+    /// May be called twice for the same vtable.
+    fn set_alloc_id_same_vtable(
+        &self,
+        id: AllocId,
+        mem: &'tcx Allocation,
+        ty: Ty<'tcx>,
+        trait_ref: Option<ty::PolyExistentialTraitRef<'tcx>>,
+    ) {
+        self.alloc_map.lock().alloc_map.insert_same(id, GlobalAlloc::Vtable(mem, ty, trait_ref));
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -3,6 +3,7 @@
 use crate::arena::Arena;
 use crate::dep_graph::{self, DepConstructor, DepGraph};
 use crate::hir::exports::ExportMap;
+use crate::hir::place::{Projection as HirProjection, ProjectionKind as HirProjectionKind};
 use crate::ich::{NodeIdHashingMode, StableHashingContext};
 use crate::infer::canonical::{Canonical, CanonicalVarInfo, CanonicalVarInfos};
 use crate::lint::{struct_lint_level, LintDiagnosticBuilder, LintSource};
@@ -376,6 +377,11 @@ pub struct TypeckResults<'tcx> {
     /// Borrows
     pub upvar_capture_map: ty::UpvarCaptureMap<'tcx>,
 
+    /// Records the path below the root variable of each upvar that is only
+    /// partially captured (see the `capture_disjoint_fields` feature).
+    /// Upvars that are captured as a whole are not present in the map.
+    pub upvar_capture_paths: ty::UpvarCapturePathMap<'tcx>,
+
     /// Records the reasons that we picked the kind of each closure;
     /// not all closures are present in the map.
     closure_kind_origins: ItemLocalMap<(Span, Symbol)>,
@@ -436,6 +442,7 @@ impl<'tcx> TypeckResults<'tcx> {
             pat_binding_modes: Default::default(),
             pat_adjustments: Default::default(),
             upvar_capture_map: Default::default(),
+            upvar_capture_paths: Default::default(),
             closure_kind_origins: Default::default(),
             liberated_fn_sigs: Default::default(),
             fru_field_types: Default::default(),
@@ -623,6 +630,36 @@ impl<'tcx> TypeckResults<'tcx> {
         self.upvar_capture_map[&upvar_id]
     }
 
+    /// Returns the projections that are applied to the root variable of
+    /// `upvar_id` to get the place that is captured by the closure. This is
+    /// empty if the whole variable is captured.
+    pub fn upvar_capture_path(&self, upvar_id: ty::UpvarId) -> &[HirProjection<'tcx>] {
+        self.upvar_capture_paths.get(&upvar_id).map_or(&[], |path| &path[..])
+    }
+
+    /// Returns whether the place captured for `upvar_id` could be mutated in
+    /// the closure's parent, taking the binding mode of the root variable and
+    /// any dereferences along the capture path into account.
+    pub fn upvar_capture_mutability(&self, upvar_id: ty::UpvarId) -> hir::Mutability {
+        let var_hir_id = upvar_id.var_path.hir_id;
+        let mut mutability = match self.pat_binding_modes().get(var_hir_id) {
+            Some(ty::BindByValue(hir::Mutability::Mut)) => hir::Mutability::Mut,
+            _ => hir::Mutability::Not,
+        };
+        let mut ty = self.node_type(var_hir_id);
+        for projection in self.upvar_capture_path(upvar_id) {
+            if projection.kind == HirProjectionKind::Deref {
+                mutability = match ty.kind {
+                    ty::Ref(_, _, mutbl) => mutbl,
+                    ty::Adt(def, _) if def.is_box() => mutability,
+                    _ => bug!("unexpected dereference of `{:?}` in capture path", ty),
+                };
+            }
+            ty = projection.ty;
+        }
+        mutability
+    }
+
     pub fn closure_kind_origins(&self) -> LocalTableInContext<'_, (Span, Symbol)> {
         LocalTableInContext { hir_owner: self.hir_owner, data: &self.closure_kind_origins }
     }
@@ -675,6 +712,7 @@ impl<'a, 'tcx> HashStable<StableHashingContext<'a>> for TypeckResults<'tcx> {
             ref pat_binding_modes,
             ref pat_adjustments,
             ref upvar_capture_map,
+            ref upvar_capture_paths,
             ref closure_kind_origins,
             ref liberated_fn_sigs,
             ref fru_field_types,
@@ -709,6 +747,17 @@ impl<'a, 'tcx> HashStable<StableHashingContext<'a>> for TypeckResults<'tcx> {
                     hcx.local_def_path_hash(closure_expr_id),
                 )
             });
+            hash_stable_hashmap(hcx, hasher, upvar_capture_paths, |up_var_id, hcx| {
+                let ty::UpvarId { var_path, closure_expr_id } = *up_var_id;
+
+                assert_eq!(var_path.hir_id.owner, hir_owner);
+
+                (
+                    hcx.local_def_path_hash(var_path.hir_id.owner),
+                    var_path.hir_id.local_id,
+                    hcx.local_def_path_hash(closure_expr_id),
+                )
+            });
 
             closure_kind_origins.hash_stable(hcx, hasher);
             liberated_fn_sigs.hash_stable(hcx, hasher);
@@ -2544,7 +2593,11 @@ impl<'tcx> TyCtxt<'tcx> {
                 return bound;
             }
 
-            if hir.attrs(id).iter().any(|attr| Level::from_symbol(attr.name_or_empty()).is_some()) {
+            let is_lint_level_attr = |attr: &ast::Attribute| {
+                let name = attr.name_or_empty();
+                name == sym::expect || Level::from_symbol(name).is_some()
+            };
+            if hir.attrs(id).iter().any(is_lint_level_attr) {
                 return id;
             }
             let next = hir.get_parent_node(id);
@@ -11,7 +11,7 @@ use rustc_hir as hir;
 use rustc_hir::lang_items::LangItem;
 use rustc_index::bit_set::BitSet;
 use rustc_index::vec::{Idx, IndexVec};
-use rustc_session::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
+use rustc_session::{DataTypeKind, FieldInfo, NicheInfo, SizeKind, VariantInfo};
 use rustc_span::symbol::{Ident, Symbol};
 use rustc_span::DUMMY_SP;
 use rustc_target::abi::call::{
@@ -1613,6 +1613,11 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
         // (delay format until we actually need it)
         let record = |kind, packed, opt_discr_size, variants| {
             let type_desc = format!("{:?}", layout.ty);
+            let niche = layout.largest_niche.as_ref().map(|niche| NicheInfo {
+                offset: niche.offset.bytes(),
+                size: niche.scalar.value.size(self).bytes(),
+                available: niche.available(self),
+            });
             self.tcx.sess.code_stats.record_type_size(
                 kind,
                 type_desc,
@@ -1620,6 +1625,7 @@ impl<'tcx> LayoutCx<'tcx, TyCtxt<'tcx>> {
                 layout.size,
                 packed,
                 opt_discr_size,
+                niche,
                 variants,
             );
         };
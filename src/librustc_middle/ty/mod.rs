@@ -747,6 +747,11 @@ pub struct UpvarBorrow<'tcx> {
 pub type UpvarListMap = FxHashMap<DefId, FxIndexMap<hir::HirId, UpvarId>>;
 pub type UpvarCaptureMap<'tcx> = FxHashMap<UpvarId, UpvarCapture<'tcx>>;
 
+/// For each upvar that is not captured as a whole, the projections that lead
+/// from the root variable to the place that the closure actually captures.
+pub type UpvarCapturePathMap<'tcx> =
+    FxHashMap<UpvarId, Vec<crate::hir::place::Projection<'tcx>>>;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum IntVarValue {
     IntType(ast::IntTy),
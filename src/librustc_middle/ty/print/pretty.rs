@@ -973,6 +973,7 @@ pub trait PrettyPrinter<'tcx>:
                 // FIXME: for statics and functions, we could in principle print more detail.
                 Some(GlobalAlloc::Static(def_id)) => p!(write("<static({:?})>", def_id)),
                 Some(GlobalAlloc::Function(_)) => p!(write("<function>")),
+                Some(GlobalAlloc::Vtable(..)) => p!(write("<vtable>")),
                 None => p!(write("<dangling pointer>")),
             },
             // Bool
@@ -136,10 +136,27 @@ fn intern_shallow<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx>>(
     // link the alloc id to the actual allocation
     let alloc = tcx.intern_const_alloc(alloc);
     leftover_allocations.extend(alloc.relocations().iter().map(|&(_, ((), reloc))| reloc));
-    tcx.set_alloc_id_memory(alloc_id, alloc);
+    set_alloc_id(ecx, alloc_id, kind, alloc);
     None
 }
 
+/// Links `alloc_id` to the interned `alloc`, as a vtable if the interpreter created it as one.
+fn set_alloc_id<'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx>>(
+    ecx: &InterpCx<'mir, 'tcx, M>,
+    alloc_id: AllocId,
+    kind: MemoryKind<!>,
+    alloc: &'tcx Allocation,
+) {
+    if let MemoryKind::Vtable = kind {
+        let vtable = ecx.vtables.iter().find(|(_, vtable)| vtable.alloc_id == alloc_id);
+        if let Some((&(ty, trait_ref), _)) = vtable {
+            ecx.tcx.set_alloc_id_vtable(alloc_id, alloc, ty, trait_ref);
+            return;
+        }
+    }
+    ecx.tcx.set_alloc_id_memory(alloc_id, alloc);
+}
+
 impl<'rt, 'mir, 'tcx, M: CompileTimeMachine<'mir, 'tcx>> InternVisitor<'rt, 'mir, 'tcx, M> {
     fn intern_shallow(
         &mut self,
@@ -379,7 +396,7 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx>>(
 
     let mut todo: Vec<_> = leftover_allocations.iter().cloned().collect();
     while let Some(alloc_id) = todo.pop() {
-        if let Some((_, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
+        if let Some((kind, mut alloc)) = ecx.memory.alloc_map.remove(&alloc_id) {
             // We can't call the `intern_shallow` method here, as its logic is tailored to safe
             // references and a `leftover_allocations` set (where we only have a todo-list here).
             // So we hand-roll the interning logic here again.
@@ -414,7 +431,7 @@ pub fn intern_const_alloc_recursive<M: CompileTimeMachine<'mir, 'tcx>>(
                 }
             }
             let alloc = tcx.intern_const_alloc(alloc);
-            tcx.set_alloc_id_memory(alloc_id, alloc);
+            set_alloc_id(ecx, alloc_id, kind, alloc);
             for &(_, ((), reloc)) in alloc.relocations().iter() {
                 if leftover_allocations.insert(reloc) {
                     todo.push(reloc);
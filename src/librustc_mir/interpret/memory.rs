@@ -286,9 +286,9 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> Memory<'mir, 'tcx, M> {
                 // Deallocating global memory -- always an error
                 return Err(match self.tcx.get_global_alloc(ptr.alloc_id) {
                     Some(GlobalAlloc::Function(..)) => err_ub_format!("deallocating a function"),
-                    Some(GlobalAlloc::Static(..) | GlobalAlloc::Memory(..)) => {
-                        err_ub_format!("deallocating static memory")
-                    }
+                    Some(
+                        GlobalAlloc::Static(..) | GlobalAlloc::Memory(..) | GlobalAlloc::Vtable(..),
+                    ) => err_ub_format!("deallocating static memory"),
                     None => err_ub!(PointerUseAfterFree(ptr.alloc_id)),
                 }
                 .into());
@@ -457,8 +457,9 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> Memory<'mir, 'tcx, M> {
         is_write: bool,
     ) -> InterpResult<'tcx, Cow<'tcx, Allocation<M::PointerTag, M::AllocExtra>>> {
         let (alloc, def_id) = match tcx.get_global_alloc(id) {
-            Some(GlobalAlloc::Memory(mem)) => {
-                // Memory of a constant or promoted or anonymous memory referenced by a static.
+            Some(GlobalAlloc::Memory(mem) | GlobalAlloc::Vtable(mem, ..)) => {
+                // Memory of a constant or promoted or anonymous memory referenced by a static,
+                // or a vtable.
                 (mem, None)
             }
             Some(GlobalAlloc::Function(..)) => throw_ub!(DerefFunctionPointer(id)),
@@ -607,7 +608,7 @@ impl<'mir, 'tcx, M: Machine<'mir, 'tcx>> Memory<'mir, 'tcx, M> {
                 let layout = self.tcx.layout_of(ParamEnv::empty().and(ty)).unwrap();
                 Ok((layout.size, layout.align.abi))
             }
-            Some(GlobalAlloc::Memory(alloc)) => {
+            Some(GlobalAlloc::Memory(alloc) | GlobalAlloc::Vtable(alloc, ..)) => {
                 // Need to duplicate the logic here, because the global allocations have
                 // different associated types than the interpreter-local ones.
                 Ok((alloc.size, alloc.align))
@@ -760,7 +761,7 @@ impl<'a, 'mir, 'tcx, M: Machine<'mir, 'tcx>> std::fmt::Debug for DumpAllocs<'a,
                 None => {
                     // global alloc
                     match self.mem.tcx.get_global_alloc(id) {
-                        Some(GlobalAlloc::Memory(alloc)) => {
+                        Some(GlobalAlloc::Memory(alloc) | GlobalAlloc::Vtable(alloc, ..)) => {
                             write!(fmt, " (unchanged global, ")?;
                             write_allocation_track_relocs(
                                 &mut *fmt,
@@ -178,7 +178,7 @@ use crate::monomorphize;
 
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::{par_iter, MTLock, MTRef, ParallelIterator};
-use rustc_errors::{ErrorReported, FatalError};
+use rustc_errors::{pluralize, ErrorReported, FatalError};
 use rustc_hir as hir;
 use rustc_hir::def_id::{DefId, DefIdMap, LocalDefId, LOCAL_CRATE};
 use rustc_hir::itemlikevisit::ItemLikeVisitor;
@@ -193,8 +193,10 @@ use rustc_middle::mir::{self, Local, Location};
 use rustc_middle::ty::adjustment::{CustomCoerceUnsized, PointerCast};
 use rustc_middle::ty::print::obsolete::DefPathBasedNames;
 use rustc_middle::ty::subst::{GenericArgKind, InternalSubsts};
-use rustc_middle::ty::{self, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable};
+use rustc_middle::ty::{self, DefIdTree, GenericParamDefKind, Instance, Ty, TyCtxt, TypeFoldable};
 use rustc_session::config::EntryFnType;
+use rustc_session::lint::builtin::{LARGE_ASSIGNMENTS, LARGE_FUTURES};
+use rustc_session::CallGraphNode;
 use rustc_span::source_map::{dummy_spanned, respan, Span, Spanned, DUMMY_SP};
 use smallvec::SmallVec;
 use std::iter;
@@ -525,6 +527,50 @@ impl<'a, 'tcx> MirNeighborCollector<'a, 'tcx> {
             self.tcx.normalize_erasing_regions(ty::ParamEnv::reveal_all(), value)
         }
     }
+
+    /// Lints moves and copies of values larger than `-Z move-size-limit`.
+    fn check_move_size(&self, operand: &mir::Operand<'tcx>, location: Location) {
+        let limit = match self.tcx.sess.opts.debugging_opts.move_size_limit {
+            Some(limit) => limit as u64,
+            None => return,
+        };
+        // The operands of aggregates and calls are compiler temporaries that the value is first
+        // moved into, where it's already reported, so moving them out again is skipped.
+        if let mir::Operand::Move(place) = operand {
+            if let Some(local) = place.as_local() {
+                if !self.body.local_decls[local].is_user_variable() {
+                    return;
+                }
+            }
+        }
+        let ty = self.monomorphize(operand.ty(self.body, self.tcx));
+        let size = match self.tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)) {
+            Ok(layout) => layout.size.bytes(),
+            Err(_) => return,
+        };
+        if size <= limit {
+            return;
+        }
+
+        // Code inlined from other crates has no lint levels to check against.
+        let source_info = self.body.source_info(location);
+        let lint_root = match &self.body.source_scopes[source_info.scope].local_data {
+            mir::ClearCrossCrate::Set(data) => data.lint_root,
+            mir::ClearCrossCrate::Clear => return,
+        };
+        self.tcx.struct_span_lint_hir(LARGE_ASSIGNMENTS, lint_root, source_info.span, |lint| {
+            let mut err = lint.build(&format!("moving {} bytes", size));
+            err.span_label(source_info.span, "value moved from here");
+            if let Some(local) = operand.place().and_then(|place| place.as_local()) {
+                let decl = &self.body.local_decls[local];
+                if decl.is_user_variable() {
+                    err.span_label(decl.source_info.span, format!("{} bytes stored here", size));
+                }
+            }
+            err.note(&format!("the current limit is {} bytes, set by `-Z move-size-limit`", limit));
+            err.emit();
+        });
+    }
 }
 
 impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
@@ -690,6 +736,11 @@ impl<'a, 'tcx> MirVisitor<'tcx> for MirNeighborCollector<'a, 'tcx> {
         self.super_terminator(terminator, location);
     }
 
+    fn visit_operand(&mut self, operand: &mir::Operand<'tcx>, location: Location) {
+        self.super_operand(operand, location);
+        self.check_move_size(operand, location);
+    }
+
     fn visit_local(
         &mut self,
         _place_local: &Local,
@@ -1189,7 +1240,8 @@ fn collect_miri<'tcx>(
                 output.push(dummy_spanned(MonoItem::Static(def_id)));
             }
         }
-        GlobalAlloc::Memory(alloc) => {
+        // The relocations of a vtable are the functions codegen puts in it.
+        GlobalAlloc::Memory(alloc) | GlobalAlloc::Vtable(alloc, ..) => {
             trace!("collecting {:?} with {:#?}", alloc_id, alloc);
             for &((), inner) in alloc.relocations().values() {
                 rustc_data_structures::stack::ensure_sufficient_stack(|| {
@@ -1214,8 +1266,152 @@ fn collect_neighbours<'tcx>(
 ) {
     debug!("collect_neighbours: {:?}", instance.def_id());
     let body = tcx.instance_mir(instance.def);
+    check_generator_size(tcx, instance, body);
 
     MirNeighborCollector { tcx, body: &body, output, instance }.visit_body(&body);
+
+    if tcx.sess.opts.debugging_opts.print_stack_usage.is_some() {
+        record_call_graph_node(tcx, instance, body);
+    }
+}
+
+/// Records the functions `instance` calls, for `-Z print-stack-usage`. Calls through
+/// function pointers and vtables can't be followed, so they are only flagged.
+fn record_call_graph_node<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: Instance<'tcx>,
+    body: &mir::Body<'tcx>,
+) {
+    let param_env = ty::ParamEnv::reveal_all();
+    let monomorphize =
+        |ty: Ty<'tcx>| tcx.subst_and_normalize_erasing_regions(instance.substs, param_env, &ty);
+
+    let mut callees = Vec::new();
+    let mut calls_indirectly = false;
+    for block in body.basic_blocks() {
+        for statement in &block.statements {
+            if let mir::StatementKind::Assign(box (_, ref rvalue)) = statement.kind {
+                if let mir::Rvalue::NullaryOp(mir::NullOp::Box, _) = rvalue {
+                    let def_id = tcx.require_lang_item(LangItem::ExchangeMalloc, None);
+                    callees.push(Instance::mono(tcx, def_id));
+                }
+            }
+        }
+
+        match block.terminator().kind {
+            mir::TerminatorKind::Call { ref func, .. } => {
+                let callee = match monomorphize(func.ty(body, tcx)).kind {
+                    ty::FnDef(def_id, substs) => {
+                        ty::Instance::resolve(tcx, param_env, def_id, substs).ok().flatten()
+                    }
+                    _ => None,
+                };
+                match callee {
+                    Some(callee) => match callee.def {
+                        ty::InstanceDef::Virtual(..) => calls_indirectly = true,
+                        // The calls LLVM emits for some intrinsics, like `memcpy`, are
+                        // found in the relocations of the object files.
+                        ty::InstanceDef::Intrinsic(_) => {}
+                        _ => callees.push(callee),
+                    },
+                    None => calls_indirectly = true,
+                }
+            }
+            mir::TerminatorKind::Drop { ref place, .. }
+            | mir::TerminatorKind::DropAndReplace { ref place, .. } => {
+                let ty = monomorphize(place.ty(body, tcx).ty);
+                if let ty::Dynamic(..) = ty.kind {
+                    calls_indirectly = true;
+                    continue;
+                }
+                let callee = Instance::resolve_drop_in_place(tcx, ty);
+                if let ty::InstanceDef::DropGlue(_, Some(_)) = callee.def {
+                    callees.push(callee);
+                }
+            }
+            mir::TerminatorKind::Assert { ref msg, .. } => {
+                let lang_item = match *msg {
+                    mir::AssertKind::BoundsCheck { .. } => LangItem::PanicBoundsCheck,
+                    _ => LangItem::Panic,
+                };
+                callees.push(Instance::mono(tcx, tcx.require_lang_item(lang_item, None)));
+            }
+            _ => {}
+        }
+    }
+
+    let mut callees: Vec<_> = callees
+        .into_iter()
+        .map(|callee| (tcx.symbol_name(callee).name.to_string(), callee.to_string()))
+        .collect();
+    callees.sort();
+    callees.dedup();
+
+    tcx.sess.code_stats.record_call_graph_node(CallGraphNode {
+        symbol: tcx.symbol_name(instance).name.to_string(),
+        name: instance.to_string(),
+        callees,
+        calls_indirectly,
+    });
+}
+
+/// Lints generators and `async` state machines larger than `-Z future-size-limit`,
+/// pointing at the largest locals that are saved across suspension points.
+fn check_generator_size<'tcx>(tcx: TyCtxt<'tcx>, instance: Instance<'tcx>, body: &mir::Body<'tcx>) {
+    const MAX_REPORTED_LOCALS: usize = 3;
+
+    let limit = match tcx.sess.opts.debugging_opts.future_size_limit {
+        Some(limit) => limit as u64,
+        None => return,
+    };
+    let (def_id, generator_layout) = match (instance.def, &body.generator_layout) {
+        (ty::InstanceDef::Item(def), Some(generator_layout)) if def.did.is_local() => {
+            (def.did, generator_layout)
+        }
+        _ => return,
+    };
+
+    let param_env = ty::ParamEnv::reveal_all();
+    let layout_size = |ty: Ty<'tcx>| {
+        let ty = tcx.subst_and_normalize_erasing_regions(instance.substs, param_env, &ty);
+        tcx.layout_of(param_env.and(ty)).ok().map(|layout| layout.size.bytes())
+    };
+    let size = match layout_size(tcx.type_of(def_id)) {
+        Some(size) if size > limit => size,
+        _ => return,
+    };
+
+    // Sort the saved locals so the largest ones are shown first. A stable sort
+    // is used so that locals of the same size are shown in source order.
+    let mut saved_locals: Vec<_> = generator_layout
+        .field_tys
+        .iter_enumerated()
+        .filter_map(|(local, &ty)| {
+            let size = layout_size(ty)?;
+            Some((size, generator_layout.field_source_info[local].span))
+        })
+        .filter(|&(size, _)| size > 0)
+        .collect();
+    saved_locals.sort_by(|(size1, _), (size2, _)| size2.cmp(size1));
+
+    // Point at the `async fn` itself rather than at its body.
+    let (descr, span) = match body.generator_kind {
+        Some(hir::GeneratorKind::Async(hir::AsyncGeneratorKind::Fn)) => {
+            ("future", tcx.def_span(tcx.parent(def_id).unwrap()))
+        }
+        Some(hir::GeneratorKind::Async(_)) => ("future", tcx.def_span(def_id)),
+        _ => ("generator", tcx.def_span(def_id)),
+    };
+    let hir_id = tcx.hir().local_def_id_to_hir_id(def_id.expect_local());
+    tcx.struct_span_lint_hir(LARGE_FUTURES, hir_id, span, |lint| {
+        let mut err = lint.build(&format!("this {} takes {} bytes", descr, size));
+        for &(size, span) in saved_locals.iter().take(MAX_REPORTED_LOCALS) {
+            let label = format!("{} byte{} held across a suspension point", size, pluralize!(size));
+            err.span_label(span, label);
+        }
+        err.note(&format!("the current limit is {} bytes, set by `-Z future-size-limit`", limit));
+        err.emit();
+    });
 }
 
 fn def_id_to_string(tcx: TyCtxt<'_>, def_id: LocalDefId) -> String {
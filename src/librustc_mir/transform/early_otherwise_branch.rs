@@ -0,0 +1,430 @@
+use crate::{
+    transform::{MirPass, MirSource},
+    util::patch::MirPatch,
+};
+use rustc_data_structures::fx::FxHashSet;
+use rustc_middle::mir::tcx::PlaceTy;
+use rustc_middle::mir::*;
+use rustc_middle::ty::{Ty, TyCtxt};
+use std::borrow::Cow;
+
+use super::simplify::simplify_cfg;
+
+/// This pass optimizes something like
+/// ```text
+/// let x: Option<()>;
+/// let y: Option<()>;
+/// match (x,y) {
+///     (Some(_), Some(_)) => {0},
+///     _ => {1}
+/// }
+/// ```
+/// into something like
+/// ```text
+/// let x: Option<()>;
+/// let y: Option<()>;
+/// let discriminant_x = // get discriminant of x
+/// let discriminant_y = // get discriminant of y
+/// if discriminant_x != discriminant_y {1} else {0}
+/// ```
+///
+/// More precisely, it looks for a `switchInt` on the discriminant of an enum, where every
+/// target in turn only reads the discriminant of another value of the same enum type and
+/// switches on it with a single value (the one that got us there) and a shared `otherwise`
+/// block. The discriminants are then compared up front, and a mismatch jumps straight to
+/// the shared `otherwise` block.
+pub struct EarlyOtherwiseBranch;
+
+impl<'tcx> MirPass<'tcx> for EarlyOtherwiseBranch {
+    fn run_pass(&self, tcx: TyCtxt<'tcx>, source: MirSource<'tcx>, body: &mut Body<'tcx>) {
+        if tcx.sess.opts.debugging_opts.mir_opt_level < 2 {
+            return;
+        }
+        trace!("running EarlyOtherwiseBranch on {:?}", source);
+
+        // we are only interested in this bb if the terminator is a switchInt
+        let bbs_with_switch =
+            body.basic_blocks().iter_enumerated().filter(|(_, bb)| is_switch(bb.terminator()));
+
+        let opts_to_apply: Vec<OptimizationToApply<'tcx>> = bbs_with_switch
+            .filter_map(|(bb_idx, bb)| {
+                let helper = Helper { body, tcx };
+                let infos = helper.go(bb_idx, bb)?;
+                Some(OptimizationToApply { infos, basic_block_first_switch: bb_idx })
+            })
+            .collect();
+
+        let mut applied_first_switches = FxHashSet::default();
+
+        for opt_to_apply in opts_to_apply {
+            // The infos were computed on the original body. If one of the blocks we want to
+            // jump over has already been rewritten by a previous optimization, they are stale.
+            if opt_to_apply
+                .infos
+                .iter()
+                .any(|info| applied_first_switches.contains(&info.second_switch_info.bb))
+            {
+                trace!("NO: a target was already rewritten, skipping {:?}", &opt_to_apply);
+                continue;
+            }
+            trace!("SUCCESS: found optimization possibility to apply: {:?}", &opt_to_apply);
+            applied_first_switches.insert(opt_to_apply.basic_block_first_switch);
+
+            let first_info = &opt_to_apply.infos[0].first_switch_info;
+            let second_info = &opt_to_apply.infos[0].second_switch_info;
+
+            let statements_before =
+                body.basic_blocks()[opt_to_apply.basic_block_first_switch].statements.len();
+            let end_of_block_location = Location {
+                block: opt_to_apply.basic_block_first_switch,
+                statement_index: statements_before,
+            };
+
+            let mut patch = MirPatch::new(body);
+
+            // create temp to store second discriminant in
+            let discr_type = second_info.discr_ty;
+            let discr_span = second_info.discr_source_info.span;
+            let second_discriminant_temp = patch.new_temp(discr_type, discr_span);
+
+            patch.add_statement(
+                end_of_block_location,
+                StatementKind::StorageLive(second_discriminant_temp),
+            );
+
+            // create assignment of discriminant
+            patch.add_assign(
+                end_of_block_location,
+                Place::from(second_discriminant_temp),
+                Rvalue::Discriminant(second_info.place_of_adt_discr_read),
+            );
+
+            // create temp to store NotEqual comparison between the two discriminants
+            let not_equal_temp = patch.new_temp(tcx.types.bool, discr_span);
+            patch.add_statement(end_of_block_location, StatementKind::StorageLive(not_equal_temp));
+
+            // create NotEqual comparison between the two discriminants
+            let first_discriminant_place = first_info.discr_used_in_switch;
+            let not_equal_rvalue = Rvalue::BinaryOp(
+                BinOp::Ne,
+                Operand::Copy(Place::from(second_discriminant_temp)),
+                Operand::Copy(first_discriminant_place),
+            );
+            patch.add_assign(end_of_block_location, Place::from(not_equal_temp), not_equal_rvalue);
+
+            // generate StorageDead for the second_discriminant_temp not in use anymore
+            patch.add_statement(
+                end_of_block_location,
+                StatementKind::StorageDead(second_discriminant_temp),
+            );
+
+            let (mut targets_to_jump_to, values_to_jump_to): (Vec<_>, Vec<_>) = opt_to_apply
+                .infos
+                .iter()
+                .flat_map(|x| x.second_switch_info.targets_with_values.iter())
+                .cloned()
+                .unzip();
+
+            // When the discriminants are equal but the value is not one of the listed ones, the
+            // original code went to the `otherwise` of the first switch.
+            targets_to_jump_to.push(first_info.otherwise_bb);
+
+            // new block that jumps to the correct discriminant case. This block is switched to
+            // if the discriminants are equal
+            let new_switch_data = BasicBlockData::new(Some(Terminator {
+                source_info: second_info.discr_source_info,
+                kind: TerminatorKind::SwitchInt {
+                    // the first and second discriminants are equal, so just pick one
+                    discr: Operand::Copy(first_discriminant_place),
+                    switch_ty: discr_type,
+                    values: Cow::from(values_to_jump_to),
+                    targets: targets_to_jump_to,
+                },
+            }));
+
+            let new_switch_bb = patch.new_block(new_switch_data);
+
+            // switch on the NotEqual. If true, then jump to the shared `otherwise` case.
+            // If false, then jump to a basic block that then jumps to the correct discriminant
+            // case.
+            let true_case = second_info.otherwise_bb;
+            let false_case = new_switch_bb;
+            patch.patch_terminator(
+                opt_to_apply.basic_block_first_switch,
+                TerminatorKind::if_(
+                    tcx,
+                    Operand::Move(Place::from(not_equal_temp)),
+                    true_case,
+                    false_case,
+                ),
+            );
+
+            // Generate a StorageDead for not_equal_temp in each of the targets, since we moved it
+            // into the switch
+            for bb in [false_case, true_case].iter() {
+                patch.add_statement(
+                    Location { block: *bb, statement_index: 0 },
+                    StatementKind::StorageDead(not_equal_temp),
+                );
+            }
+
+            patch.apply(body);
+        }
+
+        // Since this optimization adds new basic blocks and invalidates others,
+        // clean up the cfg to make it nicer for other passes
+        if !applied_first_switches.is_empty() {
+            simplify_cfg(body);
+        }
+    }
+}
+
+fn is_switch<'tcx>(terminator: &Terminator<'tcx>) -> bool {
+    match terminator.kind {
+        TerminatorKind::SwitchInt { .. } => true,
+        _ => false,
+    }
+}
+
+struct Helper<'a, 'tcx> {
+    body: &'a Body<'tcx>,
+    tcx: TyCtxt<'tcx>,
+}
+
+#[derive(Debug, Clone)]
+struct SwitchDiscriminantInfo<'tcx> {
+    /// The basic block containing the switch
+    bb: BasicBlock,
+    /// Type of the discriminant being switched on
+    discr_ty: Ty<'tcx>,
+    /// The basic block that the otherwise branch points to
+    otherwise_bb: BasicBlock,
+    /// Target along with the value being branched from. Otherwise is not included
+    targets_with_values: Vec<(BasicBlock, u128)>,
+    discr_source_info: SourceInfo,
+    /// The place of the discriminant used in the switch
+    discr_used_in_switch: Place<'tcx>,
+    /// The place of the adt that has its discriminant read
+    place_of_adt_discr_read: Place<'tcx>,
+    /// The type of the adt that has its discriminant read
+    type_adt_matched_on: Ty<'tcx>,
+}
+
+#[derive(Debug)]
+struct OptimizationToApply<'tcx> {
+    infos: Vec<OptimizationInfo<'tcx>>,
+    /// Basic block of the original first switch
+    basic_block_first_switch: BasicBlock,
+}
+
+#[derive(Debug)]
+struct OptimizationInfo<'tcx> {
+    /// Info about the first switch and discriminant
+    first_switch_info: SwitchDiscriminantInfo<'tcx>,
+    /// Info about the second switch and discriminant
+    second_switch_info: SwitchDiscriminantInfo<'tcx>,
+}
+
+impl<'a, 'tcx> Helper<'a, 'tcx> {
+    pub fn go(
+        &self,
+        bb_idx: BasicBlock,
+        bb: &BasicBlockData<'tcx>,
+    ) -> Option<Vec<OptimizationInfo<'tcx>>> {
+        // try to find the statement that defines the discriminant that is used for the switch
+        let discr = self.find_switch_discriminant_info(bb_idx, bb)?;
+
+        // go through each target, finding a discriminant read, and a switch
+        let results = discr
+            .targets_with_values
+            .iter()
+            .map(|&(target, value)| self.find_discriminant_switch_pairing(&discr, target, value))
+            .collect::<Option<Vec<_>>>();
+
+        // if the optimization did not apply for one of the targets, then abort
+        let results = match results {
+            Some(results) if !results.is_empty() => results,
+            _ => {
+                trace!("NO: not all of the targets matched the pattern for optimization");
+                return None;
+            }
+        };
+
+        // All of the second switches must agree on where to go when the discriminants differ,
+        // and must read the discriminant of the same place, since we only read it once.
+        let second = &results[0].second_switch_info;
+        if results.iter().any(|x| {
+            x.second_switch_info.otherwise_bb != second.otherwise_bb
+                || x.second_switch_info.place_of_adt_discr_read != second.place_of_adt_discr_read
+        }) {
+            trace!("NO: the second switches do not share an otherwise target and place");
+            return None;
+        }
+
+        // The first switch must either also go to the shared `otherwise` block, or it must be
+        // exhaustive, in which case its own `otherwise` block is unreachable.
+        if discr.otherwise_bb != second.otherwise_bb && !self.is_unreachable(discr.otherwise_bb) {
+            trace!("NO: otherwise target is not the same");
+            return None;
+        }
+
+        Some(results)
+    }
+
+    /// Returns `true` if `place` can be read whenever its local is initialized, i.e. if it is
+    /// only made of field projections and derefs of references.
+    fn is_always_valid(&self, place: Place<'tcx>) -> bool {
+        let mut place_ty = PlaceTy::from_ty(self.body.local_decls[place.local].ty);
+        for elem in place.projection.iter() {
+            let valid = match elem {
+                ProjectionElem::Field(..) => true,
+                ProjectionElem::Deref => place_ty.ty.is_region_ptr(),
+                _ => false,
+            };
+            if !valid {
+                return false;
+            }
+            place_ty = place_ty.projection_ty(self.tcx, elem);
+        }
+        true
+    }
+
+    fn is_unreachable(&self, bb: BasicBlock) -> bool {
+        match self.body.basic_blocks()[bb].terminator().kind {
+            TerminatorKind::Unreachable => true,
+            _ => false,
+        }
+    }
+
+    fn find_discriminant_switch_pairing(
+        &self,
+        discr_info: &SwitchDiscriminantInfo<'tcx>,
+        target: BasicBlock,
+        value: u128,
+    ) -> Option<OptimizationInfo<'tcx>> {
+        let bb = &self.body.basic_blocks()[target];
+        // find switch
+        if !is_switch(bb.terminator()) {
+            return None;
+        }
+        let this_bb_discr_info = self.find_switch_discriminant_info(target, bb)?;
+
+        // the types of the two adts matched on have to be equal for this optimization to apply
+        if discr_info.type_adt_matched_on != this_bb_discr_info.type_adt_matched_on
+            || discr_info.discr_ty != this_bb_discr_info.discr_ty
+        {
+            trace!(
+                "NO: types do not match. LHS: {:?}, RHS: {:?}",
+                discr_info.type_adt_matched_on,
+                this_bb_discr_info.type_adt_matched_on
+            );
+            return None;
+        }
+
+        // We skip over this block entirely, so it must not do anything but read the
+        // discriminant we are switching on.
+        if bb.statements.iter().rev().skip(1).any(|s| s.kind != StatementKind::Nop) {
+            trace!("NO: the second switch block has other statements");
+            return None;
+        }
+
+        // only allow optimization if the left and right of the tuple being matched are the same
+        // variants. so the following should not optimize
+        //  ```rust
+        // let x: Option<()>;
+        // let y: Option<()>;
+        // match (x,y) {
+        //     (Some(_), None) => {},
+        //     _ => {}
+        // }
+        //  ```
+        // We check this by seeing that the value of the first discriminant is the only other
+        // discriminant value being used as a target in the second switch
+        if !(this_bb_discr_info.targets_with_values.len() == 1
+            && this_bb_discr_info.targets_with_values[0].1 == value)
+        {
+            trace!(
+                "NO: The second switch did not have only 1 target (besides otherwise) that had \
+                 the same value as the value from the first switch that got us here"
+            );
+            return None;
+        }
+
+        // The second discriminant is now read unconditionally at the end of the first block.
+        // That is only fine if the place is valid regardless of the outcome of the first
+        // switch, so only fields and derefs of references are allowed. In particular, nothing
+        // may go through a variant downcast, so this should not be optimized:
+        //
+        // ```rust
+        // enum E<'a> { Empty, Some(&'a E<'a>), }
+        // let Some(Some(_)) = e;
+        // ```
+        //
+        // ```mir
+        // bb0: {
+        //   _2 = discriminant(*_1)
+        //   switchInt(_2) -> [...]
+        // }
+        // bb1: {
+        //   _3 = discriminant(*(((*_1) as Some).0: &E))
+        //   switchInt(_3) -> [...]
+        // }
+        // ```
+        if !self.is_always_valid(this_bb_discr_info.place_of_adt_discr_read) {
+            trace!("NO: the second place is not valid to read in the first block");
+            return None;
+        }
+
+        // if we reach this point, the optimization applies, and we should be able to optimize
+        // this case store the info that is needed to apply the optimization
+        Some(OptimizationInfo {
+            first_switch_info: discr_info.clone(),
+            second_switch_info: this_bb_discr_info,
+        })
+    }
+
+    fn find_switch_discriminant_info(
+        &self,
+        bb_idx: BasicBlock,
+        bb: &BasicBlockData<'tcx>,
+    ) -> Option<SwitchDiscriminantInfo<'tcx>> {
+        match &bb.terminator().kind {
+            TerminatorKind::SwitchInt { discr, targets, values, .. } => {
+                let discr_local = discr.place()?.as_local()?;
+                // the declaration of the discriminant read. Place of this read is being used in
+                // the switch
+                let discr_decl = &self.body.local_decls()[discr_local];
+                let discr_ty = discr_decl.ty;
+                // the otherwise target lies as the last element
+                let otherwise_bb = *targets.last()?;
+                let targets_with_values =
+                    targets.iter().cloned().zip(values.iter().cloned()).collect();
+
+                // find the place of the adt where the discriminant is being read from
+                // assume this is the last statement of the block
+                let place_of_adt_discr_read = match bb.statements.last()?.kind {
+                    StatementKind::Assign(box (lhs, Rvalue::Discriminant(adt_place)))
+                        if lhs.as_local() == Some(discr_local) =>
+                    {
+                        Some(adt_place)
+                    }
+                    _ => None,
+                }?;
+
+                let type_adt_matched_on = place_of_adt_discr_read.ty(self.body, self.tcx).ty;
+
+                Some(SwitchDiscriminantInfo {
+                    bb: bb_idx,
+                    discr_used_in_switch: discr.place()?,
+                    discr_ty,
+                    otherwise_bb,
+                    targets_with_values,
+                    discr_source_info: discr_decl.source_info,
+                    place_of_adt_discr_read,
+                    type_adt_matched_on,
+                })
+            }
+            _ => unreachable!("must only be passed terminator that is a switch"),
+        }
+    }
+}
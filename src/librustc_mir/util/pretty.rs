@@ -638,7 +638,8 @@ pub fn write_allocations<'tcx>(
             Some(GlobalAlloc::Static(did)) => {
                 write!(w, " (extern static: {})", tcx.def_path_str(did))?
             }
-            Some(GlobalAlloc::Memory(alloc)) => {
+            // Vtables are printed like the memory they were created as, to keep MIR dumps stable.
+            Some(GlobalAlloc::Memory(alloc) | GlobalAlloc::Vtable(alloc, ..)) => {
                 write!(w, " (")?;
                 write_allocation_track_relocs(w, alloc)?
             }
@@ -95,7 +95,14 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                         )
                     );
                 }
-                StmtKind::Let { remainder_scope, init_scope, pattern, initializer, lint_level } => {
+                StmtKind::Let {
+                    remainder_scope,
+                    init_scope,
+                    pattern,
+                    initializer,
+                    else_block,
+                    lint_level,
+                } => {
                     let ignores_expr_result =
                         if let PatKind::Wild = *pattern.kind { true } else { false };
                     this.block_context.push(BlockFrame::Statement { ignores_expr_result });
@@ -121,6 +128,17 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                                 |this| {
                                     let scope = (init_scope, source_info);
                                     this.in_scope(scope, lint_level, |this| {
+                                        if let Some(else_block) = else_block {
+                                            return this.ast_let_else(
+                                                block,
+                                                init,
+                                                initializer_span,
+                                                else_block,
+                                                visibility_scope,
+                                                remainder_span,
+                                                &pattern,
+                                            );
+                                        }
                                         this.declare_bindings(
                                             visibility_scope,
                                             remainder_span,
@@ -4,6 +4,9 @@ use crate::build::expr::category::Category;
 use crate::build::ForGuard::{OutsideGuard, RefWithinGuard};
 use crate::build::{BlockAnd, BlockAndExtension, Builder};
 use crate::thir::*;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_middle::hir::place::ProjectionKind as HirProjectionKind;
 use rustc_middle::middle::region;
 use rustc_middle::mir::AssertKind::BoundsCheck;
 use rustc_middle::mir::*;
@@ -18,14 +21,22 @@ use rustc_index::vec::Idx;
 ///
 /// This is used internally when building a place for an expression like `a.b.c`. The fields `b`
 /// and `c` can be progressively pushed onto the place builder that is created when converting `a`.
+///
+/// If `a` is an upvar of a closure that only captures the path `a.b`, the field of the closure
+/// environment already holds `a.b`. In that case `capture_path` starts out as `[b]` and the
+/// projection `b` is consumed instead of being pushed.
 #[derive(Clone)]
 struct PlaceBuilder<'tcx> {
     local: Local,
     projection: Vec<PlaceElem<'tcx>>,
+    capture_path: Vec<HirProjectionKind>,
 }
 
 impl<'tcx> PlaceBuilder<'tcx> {
     fn into_place(self, tcx: TyCtxt<'tcx>) -> Place<'tcx> {
+        if !self.capture_path.is_empty() {
+            bug!("place is a prefix of the captured path {:?}", self.capture_path);
+        }
         Place { local: self.local, projection: tcx.intern_place_elems(&self.projection) }
     }
 
@@ -42,14 +53,25 @@ impl<'tcx> PlaceBuilder<'tcx> {
     }
 
     fn project(mut self, elem: PlaceElem<'tcx>) -> Self {
-        self.projection.push(elem);
+        if self.capture_path.is_empty() {
+            self.projection.push(elem);
+            return self;
+        }
+        match (self.capture_path.remove(0), elem) {
+            (HirProjectionKind::Deref, ProjectionElem::Deref) => {}
+            (HirProjectionKind::Field(index, _), ProjectionElem::Field(field, _))
+                if index as usize == field.index() => {}
+            (expected, _) => {
+                bug!("projection {:?} does not follow the captured path at {:?}", elem, expected)
+            }
+        }
         self
     }
 }
 
 impl<'tcx> From<Local> for PlaceBuilder<'tcx> {
     fn from(local: Local) -> Self {
-        Self { local, projection: Vec::new() }
+        Self { local, projection: Vec::new(), capture_path: Vec::new() }
     }
 }
 
@@ -160,7 +182,9 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 expr_span,
                 source_info,
             ),
-            ExprKind::SelfRef => block.and(PlaceBuilder::from(Local::new(1))),
+            ExprKind::UpvarRef { closure_def_id, var_hir_id } => {
+                block.and(this.upvar_place_builder(closure_def_id, var_hir_id))
+            }
             ExprKind::VarRef { id } => {
                 let place_builder = if this.is_bound_var_in_guard(id) {
                     let index = this.var_local_id(id, RefWithinGuard);
@@ -254,6 +278,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
             | ExprKind::Continue { .. }
             | ExprKind::Return { .. }
             | ExprKind::Literal { .. }
+            | ExprKind::ConstBlock { .. }
             | ExprKind::StaticRef { .. }
             | ExprKind::InlineAsm { .. }
             | ExprKind::LlvmInlineAsm { .. }
@@ -272,6 +297,52 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         }
     }
 
+    /// Builds the place of the upvar `var_hir_id` in the environment of the closure
+    /// `closure_def_id`. If the closure only captures a path below the variable, the
+    /// projections that are pushed onto the returned builder have to follow that path.
+    fn upvar_place_builder(
+        &mut self,
+        closure_def_id: DefId,
+        var_hir_id: hir::HirId,
+    ) -> PlaceBuilder<'tcx> {
+        let typeck_results = self.hir.typeck_results();
+        let upvar_index = typeck_results
+            .closure_captures
+            .get(&closure_def_id)
+            .and_then(|upvars| upvars.get_full(&var_hir_id).map(|(i, _, _)| i))
+            .unwrap_or_else(|| bug!("{:?} is not an upvar of {:?}", var_hir_id, closure_def_id));
+        let upvar_id = ty::UpvarId {
+            var_path: ty::UpvarPath { hir_id: var_hir_id },
+            closure_expr_id: closure_def_id.expect_local(),
+        };
+
+        let closure_env_arg = Local::new(1);
+        let mut place_builder = PlaceBuilder::from(closure_env_arg);
+        let mut closure_ty = self.local_decls[closure_env_arg].ty;
+        if let ty::Ref(_, ty, _) = closure_ty.kind {
+            place_builder = place_builder.deref();
+            closure_ty = ty;
+        }
+        let upvar_ty = match closure_ty.kind {
+            ty::Closure(_, substs) => substs.as_closure().upvar_tys().nth(upvar_index),
+            ty::Generator(_, substs, _) => substs.as_generator().upvar_tys().nth(upvar_index),
+            _ => None,
+        };
+        let upvar_ty = upvar_ty
+            .unwrap_or_else(|| bug!("upvar {:?} with closure env ty {:?}", upvar_id, closure_ty));
+        place_builder = place_builder.field(Field::new(upvar_index), upvar_ty);
+
+        // The upvar might be an `&T` or `&mut T` capture, at which point we
+        // need an implicit deref.
+        if let ty::UpvarCapture::ByRef(_) = typeck_results.upvar_capture(upvar_id) {
+            place_builder = place_builder.deref();
+        }
+
+        place_builder.capture_path =
+            typeck_results.upvar_capture_path(upvar_id).iter().map(|proj| proj.kind).collect();
+        place_builder
+    }
+
     /// Lower an index expression
     ///
     /// This has two complications;
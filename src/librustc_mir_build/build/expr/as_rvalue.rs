@@ -234,6 +234,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
             }
             ExprKind::Yield { .. }
             | ExprKind::Literal { .. }
+            | ExprKind::ConstBlock { .. }
             | ExprKind::StaticRef { .. }
             | ExprKind::Block { .. }
             | ExprKind::Match { .. }
@@ -249,7 +250,7 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
             | ExprKind::Deref { .. }
             | ExprKind::Index { .. }
             | ExprKind::VarRef { .. }
-            | ExprKind::SelfRef
+            | ExprKind::UpvarRef { .. }
             | ExprKind::Break { .. }
             | ExprKind::Continue { .. }
             | ExprKind::Return { .. }
@@ -385,43 +386,31 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
 
         let arg_place = unpack!(block = this.as_place(block, arg));
 
-        let mutability = match arg_place.as_ref() {
-            PlaceRef { local, projection: &[] } => this.local_decls[local].mutability,
-            PlaceRef { local, projection: &[ProjectionElem::Deref] } => {
-                debug_assert!(
-                    this.local_decls[local].is_ref_for_guard(),
-                    "Unexpected capture place",
-                );
-                this.local_decls[local].mutability
-            }
-            PlaceRef {
-                local,
-                projection: &[ref proj_base @ .., ProjectionElem::Field(upvar_index, _)],
-            }
-            | PlaceRef {
-                local,
-                projection:
-                    &[ref proj_base @ .., ProjectionElem::Field(upvar_index, _), ProjectionElem::Deref],
-            } => {
-                let place = PlaceRef { local, projection: proj_base };
-
-                // Not projected from the implicit `self` in a closure.
-                debug_assert!(
-                    match place.local_or_deref_local() {
-                        Some(local) => local == Local::new(1),
-                        None => false,
-                    },
-                    "Unexpected capture place"
-                );
-                // Not in a closure
-                debug_assert!(
-                    this.upvar_mutbls.len() > upvar_index.index(),
-                    "Unexpected capture place"
-                );
-                this.upvar_mutbls[upvar_index.index()]
+        // The captured place may be a path below a variable, see the
+        // `capture_disjoint_fields` feature. Find the variable that it is
+        // based on, then account for any dereferences along the path.
+        let tcx = this.hir.tcx();
+        let (var_place_len, mut mutability) = (0..=arg_place.projection.len())
+            .rev()
+            .find_map(|len| {
+                let var_place =
+                    PlaceRef { local: arg_place.local, projection: &arg_place.projection[..len] };
+                this.captured_var_mutability(var_place).map(|mutability| (len, mutability))
+            })
+            .unwrap_or_else(|| bug!("Unexpected capture place"));
+        for (i, elem) in arg_place.projection.iter().enumerate().skip(var_place_len) {
+            if let ProjectionElem::Deref = elem {
+                let proj_base = &arg_place.projection[..i];
+                let pointer_ty =
+                    Place::ty_from(arg_place.local, proj_base, &this.local_decls, tcx).ty;
+                match pointer_ty.kind {
+                    ty::Ref(_, _, mutbl) | ty::RawPtr(ty::TypeAndMut { mutbl, .. }) => {
+                        mutability = mutbl;
+                    }
+                    _ => {}
+                }
             }
-            _ => bug!("Unexpected capture place"),
-        };
+        }
 
         let borrow_kind = match mutability {
             Mutability::Not => BorrowKind::Unique,
@@ -445,6 +434,45 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         block.and(Operand::Move(Place::from(temp)))
     }
 
+    /// Returns the mutability of `place` if it is a variable that can be
+    /// captured by a closure: a local, the reference to a binding within a
+    /// match guard, or an upvar of the closure being built.
+    fn captured_var_mutability(&self, place: PlaceRef<'tcx>) -> Option<Mutability> {
+        match place {
+            PlaceRef { local, projection: &[] } => Some(self.local_decls[local].mutability),
+            PlaceRef { local, projection: &[ProjectionElem::Deref] }
+                if self.local_decls[local].is_ref_for_guard() =>
+            {
+                Some(self.local_decls[local].mutability)
+            }
+            PlaceRef {
+                local,
+                projection: &[ref proj_base @ .., ProjectionElem::Field(upvar_index, _)],
+            }
+            | PlaceRef {
+                local,
+                projection:
+                    &[
+                        ref proj_base @ ..,
+                        ProjectionElem::Field(upvar_index, _),
+                        ProjectionElem::Deref,
+                    ],
+            } => {
+                let place = PlaceRef { local, projection: proj_base };
+
+                // Only projected from the implicit `self` in a closure.
+                if place.local_or_deref_local() == Some(Local::new(1))
+                    && self.upvar_mutbls.len() > upvar_index.index()
+                {
+                    Some(self.upvar_mutbls[upvar_index.index()])
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
     // Helper to get a `-1` value of the appropriate type
     fn neg_1_literal(&mut self, span: Span, ty: Ty<'tcx>) -> Operand<'tcx> {
         let param_ty = ty::ParamEnv::empty().and(ty);
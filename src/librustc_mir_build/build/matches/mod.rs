@@ -501,6 +501,72 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         .unit()
     }
 
+    /// Lowers a `let PAT = INIT else { ELSE };` statement. The initializer is
+    /// matched against `pattern` like the scrutinee of a two-armed `match`: on
+    /// success the bindings are established for the rest of the block, and
+    /// otherwise the diverging `else_block` is evaluated.
+    ///
+    /// This is called in the scope of the statement, so the temporaries of the
+    /// initializer are dropped at its end, or when `else_block` diverges.
+    crate fn ast_let_else(
+        &mut self,
+        mut block: BasicBlock,
+        init: ExprRef<'tcx>,
+        initializer_span: Span,
+        else_block: ExprRef<'tcx>,
+        visibility_scope: Option<SourceScope>,
+        remainder_span: Span,
+        pattern: &Pat<'tcx>,
+    ) -> BlockAnd<()> {
+        let scrutinee_place = unpack!(block = self.lower_scrutinee(block, init, initializer_span));
+
+        self.declare_bindings(
+            visibility_scope,
+            remainder_span,
+            pattern,
+            ArmHasGuard(false),
+            Some((Some(&scrutinee_place), initializer_span)),
+        );
+
+        let else_block = self.hir.mirror(else_block);
+        let wildcard = Pat { ty: pattern.ty, span: else_block.span, kind: Box::new(PatKind::Wild) };
+        let mut candidate = Candidate::new(scrutinee_place, pattern, false);
+        let mut else_candidate = Candidate::new(scrutinee_place, &wildcard, false);
+
+        let fake_borrow_temps = self.lower_match_tree(
+            block,
+            initializer_span,
+            false,
+            &mut [&mut candidate, &mut else_candidate],
+        );
+
+        let matched_block = self.bind_pattern(
+            self.source_info(pattern.span),
+            candidate,
+            None,
+            &fake_borrow_temps,
+            initializer_span,
+            None,
+        );
+
+        let else_source_info = self.source_info(else_block.span);
+        let else_entry_block = self.bind_pattern(
+            else_source_info,
+            else_candidate,
+            None,
+            &fake_borrow_temps,
+            initializer_span,
+            None,
+        );
+        // Typeck ensures that the `else` block has type `!`, so the block it
+        // ends in is unreachable.
+        let else_place = self.temp(else_block.ty, else_block.span);
+        let else_end_block = unpack!(self.into(else_place, else_entry_block, else_block));
+        self.cfg.terminate(else_end_block, else_source_info, TerminatorKind::Unreachable);
+
+        matched_block.unit()
+    }
+
     /// Declares the bindings of the given patterns and returns the visibility
     /// scope for the bindings in these patterns, if such a scope had to be
     /// created. NOTE: Declaring the bindings should always be done in their
@@ -855,21 +855,18 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
                 .enumerate()
                 .map(|(i, ((&var_id, &upvar_id), ty))| {
                     let capture = hir_typeck_results.upvar_capture(upvar_id);
+                    let mutability = hir_typeck_results.upvar_capture_mutability(upvar_id);
+
+                    // Only emit debuginfo for upvars that are captured as a
+                    // whole, the name would be misleading otherwise.
+                    if !hir_typeck_results.upvar_capture_path(upvar_id).is_empty() {
+                        return mutability;
+                    }
 
-                    let mut mutability = Mutability::Not;
                     let mut name = kw::Invalid;
                     if let Some(Node::Binding(pat)) = tcx_hir.find(var_id) {
                         if let hir::PatKind::Binding(_, _, ident, _) = pat.kind {
                             name = ident.name;
-                            match hir_typeck_results
-                                .extract_binding_mode(tcx.sess, pat.hir_id, pat.span)
-                            {
-                                Some(ty::BindByValue(hir::Mutability::Mut)) => {
-                                    mutability = Mutability::Mut;
-                                }
-                                Some(_) => mutability = Mutability::Not,
-                                _ => {}
-                            }
                         }
                     }
 
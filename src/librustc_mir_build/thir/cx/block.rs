@@ -91,6 +91,7 @@ fn mirror_stmts<'a, 'tcx>(
                         },
                         pattern,
                         initializer: local.init.to_ref(),
+                        else_block: local.els.map(|els| to_expr_ref(cx, els)),
                         lint_level: LintLevel::Explicit(local.hir_id),
                     },
                     opt_destruction_scope: opt_dxn_ext,
@@ -90,6 +90,11 @@ crate enum StmtKind<'tcx> {
         /// let pat: ty = <INIT> ...
         initializer: Option<ExprRef<'tcx>>,
 
+        /// let pat: ty = init else { <ELSE> }
+        ///
+        /// the diverging block evaluated when the pattern doesn't match
+        else_block: Option<ExprRef<'tcx>>,
+
         /// the lint level for this let-statement
         lint_level: LintLevel,
     },
@@ -211,8 +216,14 @@ crate enum ExprKind<'tcx> {
     VarRef {
         id: hir::HirId,
     },
-    /// first argument, used for self in a closure
-    SelfRef,
+    /// Used to represent upvars mentioned in a closure/generator
+    UpvarRef {
+        /// DefId of the closure/generator
+        closure_def_id: DefId,
+
+        /// HirId of the root variable
+        var_hir_id: hir::HirId,
+    },
     Borrow {
         borrow_kind: BorrowKind,
         arg: ExprRef<'tcx>,
@@ -274,6 +285,10 @@ crate enum ExprKind<'tcx> {
         literal: &'tcx Const<'tcx>,
         user_ty: Option<Canonical<'tcx, UserType<'tcx>>>,
     },
+    /// An inline `const` block, e.g. `const { 1 + 1 }`.
+    ConstBlock {
+        value: &'tcx Const<'tcx>,
+    },
     /// A literal containing the address of a `static`.
     ///
     /// This is only distinguished from `Literal` so that we can register some
@@ -69,8 +69,13 @@ impl<'tcx> Visitor<'tcx> for MatchVisitor<'_, 'tcx> {
             hir::LocalSource::ForLoopDesugar => ("`for` loop binding", None),
             hir::LocalSource::AsyncFn => ("async fn binding", None),
             hir::LocalSource::AwaitDesugar => ("`await` future binding", None),
+            hir::LocalSource::AssignDesugar(_) => ("destructuring assignment binding", None),
         };
-        self.check_irrefutable(&loc.pat, msg, sp);
+        if let Some(els) = loc.els {
+            self.check_let_else(loc, els);
+        } else {
+            self.check_irrefutable(&loc.pat, msg, sp);
+        }
         self.check_patterns(false, &loc.pat);
     }
 
@@ -195,6 +200,25 @@ impl<'tcx> MatchVisitor<'_, 'tcx> {
         check_exhaustive(&mut cx, scrut_ty, scrut.span, &matrix, scrut.hir_id, is_empty_match);
     }
 
+    /// The pattern of a `let...else` may be refutable, but lint if the `else` block
+    /// can never be reached.
+    fn check_let_else(&self, loc: &'tcx hir::Local<'tcx>, els: &'tcx hir::Block<'tcx>) {
+        let mut cx = self.new_cx(loc.pat.hir_id);
+
+        let mut have_errors = false;
+        let (pattern, pattern_ty) = self.lower_pattern(&mut cx, &loc.pat, &mut have_errors);
+        if have_errors {
+            return;
+        }
+        let pats: Matrix<'_, '_> = vec![PatStack::from_pattern(pattern)].into_iter().collect();
+
+        if check_not_useful(&mut cx, pattern_ty, &pats, loc.pat.hir_id).is_ok() {
+            cx.tcx.struct_span_lint_hir(IRREFUTABLE_LET_PATTERNS, loc.hir_id, els.span, |lint| {
+                lint.build("irrefutable let...else pattern").emit()
+            });
+        }
+    }
+
     fn check_irrefutable(&self, pat: &'tcx Pat<'tcx>, origin: &str, sp: Option<Span>) {
         let mut cx = self.new_cx(pat.hir_id);
 
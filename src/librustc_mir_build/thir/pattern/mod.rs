@@ -847,13 +847,62 @@ impl<'a, 'tcx> PatCtxt<'a, 'tcx> {
         }
     }
 
-    /// Converts literals, paths and negation of literals to patterns.
+    /// Converts an inline `const { ... }` block to a pattern by evaluating it.
+    fn lower_inline_const(
+        &mut self,
+        anon_const: &'tcx hir::AnonConst,
+        id: hir::HirId,
+        span: Span,
+    ) -> PatKind<'tcx> {
+        let anon_const_def_id = self.tcx.hir().local_def_id(anon_const.hir_id);
+        let value = ty::Const::from_anon_const(self.tcx, anon_const_def_id);
+
+        // Use `Reveal::All` here because patterns are always monomorphic even if their function
+        // isn't.
+        let param_env_reveal_all = self.param_env.with_reveal_all_normalized(self.tcx);
+        let value = match value.val {
+            ty::ConstKind::Param(_) => {
+                self.errors.push(PatternError::ConstParamInPattern(span));
+                return PatKind::Wild;
+            }
+            ty::ConstKind::Unevaluated(def, substs, promoted) => {
+                match self.tcx.const_eval_resolve(
+                    param_env_reveal_all,
+                    def,
+                    substs,
+                    promoted,
+                    Some(span),
+                ) {
+                    Ok(val) => ty::Const::from_value(self.tcx, val, value.ty),
+                    Err(ErrorHandled::TooGeneric) => {
+                        self.tcx
+                            .sess
+                            .span_err(span, "constant pattern depends on a generic parameter");
+                        return PatKind::Wild;
+                    }
+                    Err(_) => {
+                        self.tcx.sess.span_err(span, "could not evaluate constant pattern");
+                        return PatKind::Wild;
+                    }
+                }
+            }
+            _ => value,
+        };
+
+        let mir_structural_match_violation =
+            self.tcx.mir_const_qualif(anon_const_def_id.to_def_id()).custom_eq;
+        *self.const_to_pat(value, id, span, mir_structural_match_violation).kind
+    }
+
+    /// Converts literals, paths, inline `const` blocks and negation of literals to patterns.
     /// The special case for negation exists to allow things like `-128_i8`
     /// which would overflow if we tried to evaluate `128_i8` and then negate
     /// afterwards.
     fn lower_lit(&mut self, expr: &'tcx hir::Expr<'tcx>) -> PatKind<'tcx> {
         if let hir::ExprKind::Path(ref qpath) = expr.kind {
             *self.lower_path(qpath, expr.hir_id, expr.span).kind
+        } else if let hir::ExprKind::ConstBlock(ref anon_const) = expr.kind {
+            self.lower_inline_const(anon_const, expr.hir_id, expr.span)
         } else {
             let (lit, neg) = match expr.kind {
                 hir::ExprKind::Lit(ref lit) => (lit, false),
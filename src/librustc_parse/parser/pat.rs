@@ -313,6 +313,13 @@ impl<'a> Parser<'a> {
             let pat = self.parse_pat_with_range_pat(false, None)?;
             self.sess.gated_spans.gate(sym::box_patterns, lo.to(self.prev_token.span));
             PatKind::Box(pat)
+        } else if self.is_inline_const() {
+            // Parse `const { ... }` as an inline const pattern.
+            let const_block = self.parse_const_block()?;
+            match self.parse_range_end() {
+                Some(form) => self.parse_pat_range_begin_with(const_block, form)?,
+                None => PatKind::Lit(const_block),
+            }
         } else if self.can_be_ident_pat() {
             // Parse `ident @ pat`
             // This can give false positives and parse nullary enums,
@@ -716,6 +723,7 @@ impl<'a> Parser<'a> {
     fn is_pat_range_end_start(&self, dist: usize) -> bool {
         self.look_ahead(dist, |t| {
             t.is_path_start() // e.g. `MY_CONST`;
+                || t.is_keyword(kw::Const) // e.g. `const { N }`;
                 || t.kind == token::Dot // e.g. `.5` for recovery;
                 || t.can_begin_literal_maybe_minus() // e.g. `42`.
                 || t.is_whole_expr()
@@ -723,7 +731,9 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_pat_range_end(&mut self) -> PResult<'a, P<Expr>> {
-        if self.check_path() {
+        if self.is_inline_const() {
+            self.parse_const_block()
+        } else if self.check_path() {
             let lo = self.token.span;
             let (qself, path) = if self.eat_lt() {
                 // Parse a qualified path
@@ -12,7 +12,7 @@ use rustc_ast::token::{self, TokenKind};
 use rustc_ast::util::classify;
 use rustc_ast::{AttrStyle, AttrVec, Attribute, MacCall, MacStmtStyle};
 use rustc_ast::{Block, BlockCheckMode, Expr, ExprKind, Local, Stmt, StmtKind, DUMMY_NODE_ID};
-use rustc_errors::{Applicability, PResult};
+use rustc_errors::{Applicability, DiagnosticBuilder, PResult};
 use rustc_span::source_map::{BytePos, Span};
 use rustc_span::symbol::{kw, sym};
 
@@ -217,8 +217,45 @@ impl<'a> Parser<'a> {
                 return Err(err);
             }
         };
+        let els = match &init {
+            Some(init) if self.eat_keyword(kw::Else) => {
+                self.check_let_else_init(init);
+                let els = self.parse_block()?;
+                self.sess.gated_spans.gate(sym::let_else, lo.to(els.span));
+                Some(els)
+            }
+            _ => None,
+        };
         let hi = if self.token == token::Semi { self.token.span } else { self.prev_token.span };
-        Ok(P(ast::Local { ty, pat, init, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+        Ok(P(ast::Local { ty, pat, init, els, id: DUMMY_NODE_ID, span: lo.to(hi), attrs }))
+    }
+
+    /// Reports initializers of a `let...else` that would read as if the `else` belonged to them.
+    fn check_let_else_init(&self, init: &Expr) {
+        let wrap_in_parens = |mut err: DiagnosticBuilder<'_>| {
+            err.multipart_suggestion(
+                "wrap the expression in parentheses",
+                vec![
+                    (init.span.shrink_to_lo(), "(".to_string()),
+                    (init.span.shrink_to_hi(), ")".to_string()),
+                ],
+                Applicability::MachineApplicable,
+            )
+            .emit();
+        };
+        if let ExprKind::Binary(op, ..) = init.kind {
+            if op.node.lazy() {
+                let msg = format!(
+                    "a `{}` expression cannot be directly assigned in `let...else`",
+                    op.node.to_string()
+                );
+                wrap_in_parens(self.struct_span_err(init.span, &msg));
+            }
+        }
+        if !classify::expr_requires_semi_to_be_stmt(init) {
+            let msg = "right curly brace `}` before `else` in a `let...else` statement not allowed";
+            wrap_in_parens(self.struct_span_err(init.span, msg));
+        }
     }
 
     /// Parses the RHS of a local variable declaration (e.g., '= 14;').
@@ -65,6 +65,8 @@ impl CheckAttrVisitor<'tcx> {
                 self.check_non_exhaustive(attr, span, target)
             } else if self.tcx.sess.check_name(attr, sym::marker) {
                 self.check_marker(attr, span, target)
+            } else if self.tcx.sess.check_name(attr, sym::must_not_suspend) {
+                self.check_must_not_suspend(attr, span, target)
             } else if self.tcx.sess.check_name(attr, sym::target_feature) {
                 self.check_target_feature(attr, span, target)
             } else if self.tcx.sess.check_name(attr, sym::track_caller) {
@@ -201,6 +203,24 @@ impl CheckAttrVisitor<'tcx> {
         }
     }
 
+    /// Checks if the `#[must_not_suspend]` attribute on an `item` is valid. Returns `true` if valid.
+    fn check_must_not_suspend(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
+        match target {
+            Target::Struct | Target::Enum | Target::Union | Target::Trait => true,
+            _ => {
+                self.tcx
+                    .sess
+                    .struct_span_err(
+                        attr.span,
+                        "`must_not_suspend` attribute should be applied to a struct, enum, union, or trait",
+                    )
+                    .span_label(*span, "is not a struct, enum, union, or trait")
+                    .emit();
+                false
+            }
+        }
+    }
+
     /// Checks if the `#[target_feature]` attribute on `item` is valid. Returns `true` if valid.
     fn check_target_feature(&self, attr: &Attribute, span: &Span, target: Target) -> bool {
         match target {
@@ -447,6 +447,10 @@ fn add_from_pat(ir: &mut IrMaps<'_>, pat: &hir::Pat<'_>) {
 
 fn visit_local<'tcx>(ir: &mut IrMaps<'tcx>, local: &'tcx hir::Local<'tcx>) {
     add_from_pat(ir, &local.pat);
+    if local.els.is_some() {
+        // Control flow splits between the pattern and the `else` block.
+        ir.add_live_node_for_node(local.hir_id, ExprNode(local.span));
+    }
     intravisit::walk_local(ir, local);
 }
 
@@ -503,6 +507,7 @@ fn visit_expr<'tcx>(ir: &mut IrMaps<'tcx>, expr: &'tcx Expr<'tcx>) {
         hir::ExprKind::Index(..)
         | hir::ExprKind::Field(..)
         | hir::ExprKind::Array(..)
+        | hir::ExprKind::ConstBlock(..)
         | hir::ExprKind::Call(..)
         | hir::ExprKind::MethodCall(..)
         | hir::ExprKind::Tup(..)
@@ -1016,6 +1021,28 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
                 // initialization, which is mildly more complex than checking
                 // once at the func header but otherwise equivalent.
 
+                if let Some(els) = local.els {
+                    //
+                    //     (init)
+                    //       |
+                    //       v
+                    //    (local)
+                    //     /   \
+                    //    v     v
+                    //  (pat) (els)
+                    //    |
+                    //    v
+                    //  (succ)
+                    //
+                    let ln = self.live_node(local.hir_id, local.span);
+                    self.init_empty(ln, succ);
+                    let pat_succ = self.define_bindings_in_pat(&local.pat, succ);
+                    self.merge_from_succ(ln, pat_succ, true);
+                    let else_succ = self.propagate_through_block(els, succ);
+                    self.merge_from_succ(ln, else_succ, false);
+                    return self.propagate_through_opt_expr(local.init.as_deref(), ln);
+                }
+
                 let succ = self.propagate_through_opt_expr(local.init.as_deref(), succ);
                 self.define_bindings_in_pat(&local.pat, succ)
             }
@@ -1310,6 +1337,7 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
             }
 
             hir::ExprKind::Lit(..)
+            | hir::ExprKind::ConstBlock(..)
             | hir::ExprKind::Err
             | hir::ExprKind::Path(hir::QPath::TypeRelative(..))
             | hir::ExprKind::Path(hir::QPath::LangItem(..)) => succ,
@@ -1547,6 +1575,7 @@ fn check_expr<'tcx>(this: &mut Liveness<'_, 'tcx>, expr: &'tcx Expr<'tcx>) {
         | hir::ExprKind::Index(..)
         | hir::ExprKind::Field(..)
         | hir::ExprKind::Array(..)
+        | hir::ExprKind::ConstBlock(..)
         | hir::ExprKind::Tup(..)
         | hir::ExprKind::Binary(..)
         | hir::ExprKind::Cast(..)
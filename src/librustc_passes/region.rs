@@ -430,8 +430,9 @@ fn resolve_local<'tcx>(
     visitor: &mut RegionResolutionVisitor<'tcx>,
     pat: Option<&'tcx hir::Pat<'tcx>>,
     init: Option<&'tcx hir::Expr<'tcx>>,
+    els: Option<&'tcx hir::Block<'tcx>>,
 ) {
-    debug!("resolve_local(pat={:?}, init={:?})", pat, init);
+    debug!("resolve_local(pat={:?}, init={:?}, els={:?})", pat, init, els);
 
     let blk_scope = visitor.cx.var_parent.map(|(p, _)| p);
 
@@ -508,6 +509,13 @@ fn resolve_local<'tcx>(
     if let Some(pat) = pat {
         visitor.visit_pat(pat);
     }
+    // The `else` block of a `let...else` runs within the statement, so the temporaries
+    // of the initializer are dropped when it diverges, and its own temporaries don't
+    // outlive it.
+    if let Some(els) = els {
+        visitor.terminating_scopes.insert(els.hir_id.local_id);
+        visitor.visit_block(els);
+    }
 
     /// Returns `true` if `pat` match the `P&` non-terminal.
     ///
@@ -770,7 +778,7 @@ impl<'tcx> Visitor<'tcx> for RegionResolutionVisitor<'tcx> {
             // (i.e., `'static`), which means that after `g` returns, it drops,
             // and all the associated destruction scope rules apply.
             self.cx.var_parent = None;
-            resolve_local(self, None, Some(&body.value));
+            resolve_local(self, None, Some(&body.value), None);
         }
 
         if body.generator_kind.is_some() {
@@ -797,7 +805,7 @@ impl<'tcx> Visitor<'tcx> for RegionResolutionVisitor<'tcx> {
         resolve_expr(self, ex);
     }
     fn visit_local(&mut self, l: &'tcx Local<'tcx>) {
-        resolve_local(self, Some(&l.pat), l.init.as_deref());
+        resolve_local(self, Some(&l.pat), l.init.as_deref(), l.els);
     }
 }
 
@@ -862,6 +862,16 @@ pub struct WorkProduct {
     pub cgu_name: String,
     /// Saved file associated with this CGU.
     pub saved_file: Option<String>,
+    /// Saved DWARF object file associated with this CGU, if its debuginfo
+    /// was split out of the object file.
+    pub saved_dwarf_object: Option<String>,
+}
+
+impl WorkProduct {
+    /// Returns the names of all the files saved for this CGU.
+    pub fn saved_files(&self) -> impl Iterator<Item = &String> {
+        self.saved_file.iter().chain(self.saved_dwarf_object.iter())
+    }
 }
 
 #[derive(Clone)]
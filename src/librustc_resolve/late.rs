@@ -305,10 +305,7 @@ impl<'a> PathSource<'a> {
                 | Res::SelfCtor(..) => true,
                 _ => false,
             },
-            PathSource::TupleStruct(_) => match res {
-                Res::Def(DefKind::Ctor(_, CtorKind::Fn), _) | Res::SelfCtor(..) => true,
-                _ => false,
-            },
+            PathSource::TupleStruct(_) => res.expected_in_tuple_struct_pat(),
             PathSource::Struct => match res {
                 Res::Def(
                     DefKind::Struct
@@ -1349,6 +1346,9 @@ impl<'a, 'b, 'ast> LateResolutionVisitor<'a, 'b, 'ast> {
         // Resolve the initializer.
         walk_list!(self, visit_expr, &local.init);
 
+        // Resolve the `else` block, in which the bindings of the pattern aren't in scope.
+        walk_list!(self, visit_block, &local.els);
+
         // Resolve the pattern.
         self.resolve_pattern_top(&local.pat, PatternSource::Let);
     }
@@ -2284,6 +2284,11 @@ impl<'a, 'b, 'ast> LateResolutionVisitor<'a, 'b, 'ast> {
             ExprKind::Async(..) | ExprKind::Closure(..) => {
                 self.with_label_rib(ClosureOrAsyncRibKind, |this| visit::walk_expr(this, expr));
             }
+            // Inline `const` blocks can't refer to locals, but unlike other anonymous
+            // constants they may always use the generic parameters of the enclosing item.
+            ExprKind::ConstBlock(ref anon_const) => {
+                self.with_constant_rib(true, |this| visit::walk_anon_const(this, anon_const));
+            }
             _ => {
                 visit::walk_expr(self, expr);
             }
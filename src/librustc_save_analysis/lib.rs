@@ -5,6 +5,7 @@
 
 mod dump_visitor;
 mod dumper;
+mod lsif;
 #[macro_use]
 mod span_utils;
 mod sig;
@@ -23,7 +24,7 @@ use rustc_middle::middle::cstore::ExternCrate;
 use rustc_middle::middle::privacy::AccessLevels;
 use rustc_middle::ty::{self, DefIdTree, TyCtxt};
 use rustc_middle::{bug, span_bug};
-use rustc_session::config::{CrateType, Input, OutputType};
+use rustc_session::config::{CrateType, Input, OutputType, SaveAnalysisFormat};
 use rustc_session::output::{filename_for_metadata, out_filename};
 use rustc_span::source_map::Spanned;
 use rustc_span::symbol::Ident;
@@ -922,7 +923,7 @@ impl<'a> DumpHandler<'a> {
         DumpHandler { odir, cratename: cratename.to_owned() }
     }
 
-    fn output_file(&self, ctx: &SaveContext<'_>) -> (BufWriter<File>, PathBuf) {
+    fn output_file(&self, ctx: &SaveContext<'_>, extension: &str) -> (BufWriter<File>, PathBuf) {
         let sess = &ctx.tcx.sess;
         let file_name = match ctx.config.output_file {
             Some(ref s) => PathBuf::from(s),
@@ -940,7 +941,7 @@ impl<'a> DumpHandler<'a> {
                 let mut out_name = if executable { String::new() } else { "lib".to_owned() };
                 out_name.push_str(&self.cratename);
                 out_name.push_str(&sess.opts.cg.extra_filename);
-                out_name.push_str(".json");
+                out_name.push_str(extension);
                 root_path.push(&out_name);
 
                 root_path
@@ -960,10 +961,22 @@ impl<'a> DumpHandler<'a> {
 impl SaveHandler for DumpHandler<'_> {
     fn save(&mut self, save_ctxt: &SaveContext<'_>, analysis: &Analysis) {
         let sess = &save_ctxt.tcx.sess;
-        let (output, file_name) = self.output_file(&save_ctxt);
-        if let Err(e) = serde_json::to_writer(output, &analysis) {
-            error!("Can't serialize save-analysis: {:?}", e);
-        }
+        let file_name = match sess.opts.debugging_opts.save_analysis_format {
+            SaveAnalysisFormat::Json => {
+                let (output, file_name) = self.output_file(&save_ctxt, ".json");
+                if let Err(e) = serde_json::to_writer(output, &analysis) {
+                    error!("Can't serialize save-analysis: {:?}", e);
+                }
+                file_name
+            }
+            SaveAnalysisFormat::Lsif => {
+                let (output, file_name) = self.output_file(&save_ctxt, ".lsif");
+                if let Err(e) = lsif::write_lsif(save_ctxt, analysis, output) {
+                    error!("Can't write save-analysis LSIF dump: {:?}", e);
+                }
+                file_name
+            }
+        };
 
         if sess.opts.json_artifact_notifications {
             sess.parse_sess.span_diagnostic.emit_artifact_notification(&file_name, "save-analysis");
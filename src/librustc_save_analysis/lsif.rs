@@ -0,0 +1,352 @@
+//! Conversion of the save-analysis data into the Language Server Index Format (LSIF).
+//!
+//! LSIF describes a graph of vertices (documents, ranges, result sets and the results of
+//! LSP requests) and the edges between them, serialized as one JSON object per line. See
+//! <https://microsoft.github.io/language-server-protocol/specifications/lsif/0.5.0/specification/>.
+//!
+//! We only translate what `DumpVisitor` already collected: definitions, references, hover
+//! text (the signature and docs of a definition) and implementations (trait impls and the
+//! trait methods they implement). Items from other crates are linked through `import`
+//! monikers, which match the `export` monikers of the index of the defining crate.
+
+use crate::SaveContext;
+
+use rls_data::{Analysis, Def, DefKind, RelationKind, SpanData};
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def_id::{CrateNum, DefId, DefIndex, LOCAL_CRATE};
+use serde_json::{json, Value};
+
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// The version of the LSIF specification that we emit.
+const LSIF_VERSION: &str = "0.5.0";
+
+/// The moniker scheme for our symbol identifiers.
+const MONIKER_SCHEME: &str = "rustc";
+
+type Id = u64;
+
+/// An rls id, usable as a map key.
+type DefKey = (u32, u32);
+
+fn def_key(id: rls_data::Id) -> DefKey {
+    (id.krate, id.index)
+}
+
+/// Everything we know about a symbol that is defined in or referenced from this crate.
+#[derive(Default)]
+struct Symbol {
+    /// The range of the definition, if it is in this crate.
+    definition: Option<Id>,
+    /// The ranges referencing this symbol, grouped by document.
+    references: FxHashMap<Id, Vec<Id>>,
+    /// The ranges of the implementations of this symbol, grouped by document.
+    implementations: FxHashMap<Id, Vec<Id>>,
+    /// Markdown hover contents.
+    hover: Vec<Value>,
+    /// Identifier for the moniker, with whether this symbol is exported or imported.
+    moniker: Option<(String, &'static str)>,
+}
+
+struct LsifWriter<'a, 'tcx, W: Write> {
+    scx: &'a SaveContext<'tcx>,
+    out: W,
+    next_id: Id,
+    /// Document vertices by file name, with the ranges they contain.
+    documents: FxHashMap<PathBuf, (Id, Vec<Id>)>,
+    /// Range vertices by file name and byte offsets.
+    ranges: FxHashMap<(PathBuf, u32, u32), Id>,
+    /// The document of each range.
+    range_documents: FxHashMap<Id, Id>,
+    symbols: FxHashMap<DefKey, Symbol>,
+    /// The symbol each range belongs to. A range may only have a single result set.
+    range_symbols: FxHashMap<Id, DefKey>,
+}
+
+/// Writes the LSIF graph of `analysis` to `out`.
+pub fn write_lsif<W: Write>(scx: &SaveContext<'_>, analysis: &Analysis, out: W) -> io::Result<()> {
+    let mut writer = LsifWriter {
+        scx,
+        out,
+        next_id: 0,
+        documents: Default::default(),
+        ranges: Default::default(),
+        range_documents: Default::default(),
+        symbols: Default::default(),
+        range_symbols: Default::default(),
+    };
+    writer.write(analysis)?;
+    writer.out.flush()
+}
+
+impl<'a, 'tcx, W: Write> LsifWriter<'a, 'tcx, W> {
+    fn write(&mut self, analysis: &Analysis) -> io::Result<()> {
+        let scx = self.scx;
+        let project_root = &scx.tcx.sess.working_dir.0;
+        self.vertex(
+            "metaData",
+            json!({
+                "version": LSIF_VERSION,
+                "projectRoot": file_uri(project_root),
+                "toolInfo": { "name": "rustc", "version": option_env!("CFG_VERSION") },
+            }),
+        )?;
+        let project = self.vertex("project", json!({ "kind": "rust" }))?;
+
+        for def in &analysis.defs {
+            let (range, document) = self.range(&def.span)?;
+            let key = def_key(def.id);
+            self.range_symbols.insert(range, key);
+            let moniker = self.moniker(def);
+            let symbol = self.symbols.entry(key).or_default();
+            symbol.definition = Some(range);
+            symbol.hover = hover_contents(def);
+            symbol.moniker = moniker;
+
+            // An implementation of a trait method points back at the trait method.
+            if let Some(decl_id) = def.decl_id {
+                let trait_method = self.symbol(decl_id);
+                trait_method.implementations.entry(document).or_default().push(range);
+            }
+        }
+
+        for reference in &analysis.refs {
+            let (range, document) = self.range(&reference.span)?;
+            self.range_symbols.entry(range).or_insert(def_key(reference.ref_id));
+            let symbol = self.symbol(reference.ref_id);
+            symbol.references.entry(document).or_default().push(range);
+        }
+
+        for relation in &analysis.relations {
+            // Inherent impls are related to a null id, and super-traits are not
+            // implementations.
+            if relation.to.krate == u32::MAX {
+                continue;
+            }
+            if let RelationKind::Impl { .. } = relation.kind {
+                let (range, document) = self.range(&relation.span)?;
+                let symbol = self.symbol(relation.to);
+                symbol.implementations.entry(document).or_default().push(range);
+            }
+        }
+
+        let mut symbols: Vec<_> = std::mem::take(&mut self.symbols).into_iter().collect();
+        symbols.sort_by_key(|&(key, _)| key);
+        let mut result_sets = FxHashMap::default();
+        for (key, symbol) in &symbols {
+            let result_set = self.write_symbol(symbol)?;
+            result_sets.insert(*key, result_set);
+        }
+
+        let mut range_symbols: Vec<_> = self.range_symbols.iter().map(|(&r, &k)| (r, k)).collect();
+        range_symbols.sort();
+        for (range, key) in range_symbols {
+            self.edge("next", json!({ "outV": range, "inV": result_sets[&key] }))?;
+        }
+
+        let mut documents: Vec<_> = self.documents.values().cloned().collect();
+        documents.sort();
+        let document_ids: Vec<_> = documents.iter().map(|&(id, _)| id).collect();
+        self.edge("contains", json!({ "outV": project, "inVs": document_ids }))?;
+        for (document, ranges) in documents {
+            self.edge("contains", json!({ "outV": document, "inVs": ranges }))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the result set of a symbol and the results hanging off it, returning the id of
+    /// the result set.
+    fn write_symbol(&mut self, symbol: &Symbol) -> io::Result<Id> {
+        let result_set = self.vertex("resultSet", json!({}))?;
+
+        if let Some(definition) = symbol.definition {
+            let document = self.range_documents[&definition];
+            let result = self.vertex("definitionResult", json!({}))?;
+            self.edge("textDocument/definition", json!({ "outV": result_set, "inV": result }))?;
+            self.item(result, document, &[definition], None)?;
+        }
+
+        if !symbol.hover.is_empty() {
+            let result = self
+                .vertex("hoverResult", json!({ "result": { "contents": symbol.hover.clone() } }))?;
+            self.edge("textDocument/hover", json!({ "outV": result_set, "inV": result }))?;
+        }
+
+        let result = self.vertex("referenceResult", json!({}))?;
+        self.edge("textDocument/references", json!({ "outV": result_set, "inV": result }))?;
+        if let Some(definition) = symbol.definition {
+            let document = self.range_documents[&definition];
+            self.item(result, document, &[definition], Some("definitions"))?;
+        }
+        for (document, ranges) in sorted(&symbol.references) {
+            self.item(result, document, ranges, Some("references"))?;
+        }
+
+        if !symbol.implementations.is_empty() {
+            let result = self.vertex("implementationResult", json!({}))?;
+            self.edge("textDocument/implementation", json!({ "outV": result_set, "inV": result }))?;
+            for (document, ranges) in sorted(&symbol.implementations) {
+                self.item(result, document, ranges, None)?;
+            }
+        }
+
+        if let Some((identifier, kind)) = &symbol.moniker {
+            let moniker = self.vertex(
+                "moniker",
+                json!({
+                    "scheme": MONIKER_SCHEME,
+                    "identifier": identifier,
+                    "unique": "scheme",
+                    "kind": kind,
+                }),
+            )?;
+            self.edge("moniker", json!({ "outV": result_set, "inV": moniker }))?;
+        }
+
+        Ok(result_set)
+    }
+
+    /// Returns the range vertex for `span` and its document, emitting them if needed.
+    fn range(&mut self, span: &SpanData) -> io::Result<(Id, Id)> {
+        let key = (span.file_name.clone(), span.byte_start, span.byte_end);
+        if let Some(&range) = self.ranges.get(&key) {
+            return Ok((range, self.range_documents[&range]));
+        }
+
+        let document = match self.documents.get(&span.file_name) {
+            Some(&(document, _)) => document,
+            None => {
+                let path = self.scx.tcx.sess.working_dir.0.join(&span.file_name);
+                let document = self
+                    .vertex("document", json!({ "uri": file_uri(&path), "languageId": "rust" }))?;
+                self.documents.insert(span.file_name.clone(), (document, vec![]));
+                document
+            }
+        };
+
+        let range = self.vertex(
+            "range",
+            json!({
+                "start": {
+                    "line": span.line_start.zero_indexed().0,
+                    "character": span.column_start.zero_indexed().0,
+                },
+                "end": {
+                    "line": span.line_end.zero_indexed().0,
+                    "character": span.column_end.zero_indexed().0,
+                },
+            }),
+        )?;
+        self.documents.get_mut(&span.file_name).unwrap().1.push(range);
+        self.ranges.insert(key, range);
+        self.range_documents.insert(range, document);
+        Ok((range, document))
+    }
+
+    /// The moniker of a definition in this crate. Local variables are not visible outside of
+    /// their function, so they don't get one.
+    fn moniker(&self, def: &Def) -> Option<(String, &'static str)> {
+        if def.kind == DefKind::Local {
+            return None;
+        }
+        let def_id = self.def_id(def.id)?;
+        Some((self.moniker_identifier(def_id), "export"))
+    }
+
+    /// Returns the symbol for `id`. If we haven't seen its definition (yet), it is either a
+    /// symbol from another crate, which we only know through its `DefId`, or one of ours that
+    /// will be filled in when we get to its definition.
+    fn symbol(&mut self, id: rls_data::Id) -> &mut Symbol {
+        let key = def_key(id);
+        if !self.symbols.contains_key(&key) {
+            let mut symbol = Symbol::default();
+            if let Some(def_id) = self.def_id(id).filter(|def_id| !def_id.is_local()) {
+                let path = self.scx.tcx.def_path_str(def_id);
+                symbol.hover = vec![json!({ "language": "rust", "value": path })];
+                symbol.moniker = Some((self.moniker_identifier(def_id), "import"));
+            }
+            self.symbols.insert(key, symbol);
+        }
+        self.symbols.get_mut(&key).unwrap()
+    }
+
+    /// A crate-qualified path identifying the item across crates, e.g. `std::vec[0]::Vec[0]`.
+    fn moniker_identifier(&self, def_id: DefId) -> String {
+        let tcx = self.scx.tcx;
+        format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate())
+    }
+
+    /// Returns the `DefId` that `id` was made from. The ids of things without a `DefId`, like
+    /// local variables, are made up from their `HirId` by `id_from_hir_id`, and missing
+    /// definitions get the null id. Neither maps back to a `DefId`.
+    fn def_id(&self, id: rls_data::Id) -> Option<DefId> {
+        if id.krate == u32::MAX {
+            return None;
+        }
+        let krate = CrateNum::from_u32(id.krate);
+        if krate == LOCAL_CRATE
+            && id.index as usize >= self.scx.tcx.hir().definitions().def_index_count()
+        {
+            return None;
+        }
+        Some(DefId { krate, index: DefIndex::from_u32(id.index) })
+    }
+
+    fn item(
+        &mut self,
+        out: Id,
+        document: Id,
+        ranges: &[Id],
+        property: Option<&str>,
+    ) -> io::Result<Id> {
+        let mut fields = json!({ "outV": out, "inVs": ranges, "document": document });
+        if let Some(property) = property {
+            fields["property"] = json!(property);
+        }
+        self.edge("item", fields)
+    }
+
+    fn vertex(&mut self, label: &str, fields: Value) -> io::Result<Id> {
+        self.element("vertex", label, fields)
+    }
+
+    fn edge(&mut self, label: &str, fields: Value) -> io::Result<Id> {
+        self.element("edge", label, fields)
+    }
+
+    fn element(&mut self, ty: &str, label: &str, mut fields: Value) -> io::Result<Id> {
+        self.next_id += 1;
+        let id = self.next_id;
+        fields["id"] = json!(id);
+        fields["type"] = json!(ty);
+        fields["label"] = json!(label);
+        serde_json::to_writer(&mut self.out, &fields)?;
+        self.out.write_all(b"\n")?;
+        Ok(id)
+    }
+}
+
+fn hover_contents(def: &Def) -> Vec<Value> {
+    let signature = if def.value.is_empty() { &def.qualname } else { &def.value };
+    let mut contents = vec![json!({ "language": "rust", "value": signature })];
+    if !def.docs.is_empty() {
+        contents.push(json!(def.docs));
+    }
+    contents
+}
+
+fn sorted(map: &FxHashMap<Id, Vec<Id>>) -> Vec<(Id, &[Id])> {
+    let mut entries: Vec<_> = map.iter().map(|(&k, v)| (k, &v[..])).collect();
+    entries.sort();
+    entries
+}
+
+fn file_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    if path.starts_with('/') {
+        format!("file://{}", path)
+    } else {
+        format!("file:///{}", path)
+    }
+}
@@ -1,5 +1,6 @@
-use rustc_data_structures::fx::FxHashSet;
+use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_data_structures::sync::Lock;
+use rustc_serialize::json::as_json;
 use rustc_target::abi::{Align, Size};
 use std::cmp::{self, Ordering};
 
@@ -26,6 +27,15 @@ pub struct FieldInfo {
     pub align: u64,
 }
 
+/// The largest niche of a type, i.e. the invalid values of one of its scalars that
+/// enclosing enums can use to store their discriminant.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Encodable)]
+pub struct NicheInfo {
+    pub offset: u64,
+    pub size: u64,
+    pub available: u128,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum DataTypeKind {
     Struct,
@@ -42,12 +52,40 @@ pub struct TypeSizeInfo {
     pub overall_size: u64,
     pub packed: bool,
     pub opt_discr_size: Option<u64>,
+    pub niche: Option<NicheInfo>,
     pub variants: Vec<VariantInfo>,
 }
 
+/// The size of one codegened copy of a function.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct MonoItemSizeInfo {
+    /// The generic definition the function is an instance of.
+    pub def_path: String,
+    pub instance: String,
+    /// The codegen unit the copy was placed in.
+    pub cgu: String,
+    pub mir_statements: u64,
+    /// Counted right after codegen, before LLVM optimizes the function.
+    pub llvm_instructions: u64,
+}
+
+/// A codegened function and the functions it may call, as seen by the mono item
+/// collector. Used by `-Z print-stack-usage`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct CallGraphNode {
+    pub symbol: String,
+    pub name: String,
+    /// The `(symbol, name)` of each function that is called directly.
+    pub callees: Vec<(String, String)>,
+    /// Whether the function calls through a function pointer or a vtable.
+    pub calls_indirectly: bool,
+}
+
 #[derive(Default)]
 pub struct CodeStats {
     type_sizes: Lock<FxHashSet<TypeSizeInfo>>,
+    mono_item_sizes: Lock<Vec<MonoItemSizeInfo>>,
+    call_graph: Lock<Vec<CallGraphNode>>,
 }
 
 impl CodeStats {
@@ -59,6 +97,7 @@ impl CodeStats {
         overall_size: Size,
         packed: bool,
         opt_discr_size: Option<Size>,
+        niche: Option<NicheInfo>,
         mut variants: Vec<VariantInfo>,
     ) {
         // Sort variants so the largest ones are shown first. A stable sort is
@@ -72,24 +111,15 @@ impl CodeStats {
             overall_size: overall_size.bytes(),
             packed,
             opt_discr_size: opt_discr_size.map(|s| s.bytes()),
+            niche,
             variants,
         };
         self.type_sizes.borrow_mut().insert(info);
     }
 
-    pub fn print_type_sizes(&self) {
+    pub fn print_type_sizes(&self, filter: Option<&[String]>) {
         let type_sizes = self.type_sizes.borrow();
-        let mut sorted: Vec<_> = type_sizes.iter().collect();
-
-        // Primary sort: large-to-small.
-        // Secondary sort: description (dictionary order)
-        sorted.sort_by(|info1, info2| {
-            // (reversing cmp order to get large-to-small ordering)
-            match info2.overall_size.cmp(&info1.overall_size) {
-                Ordering::Equal => info1.type_description.cmp(&info2.type_description),
-                other => other,
-            }
-        });
+        let sorted = sorted_type_sizes(&type_sizes, filter);
 
         for info in &sorted {
             println!(
@@ -190,4 +220,204 @@ impl CodeStats {
             }
         }
     }
+
+    pub fn record_mono_item_size(&self, info: MonoItemSizeInfo) {
+        self.mono_item_sizes.borrow_mut().push(info);
+    }
+
+    pub fn print_mono_item_sizes(&self) {
+        let mono_item_sizes = self.mono_item_sizes.borrow();
+
+        let mut groups: FxHashMap<&str, Vec<&MonoItemSizeInfo>> = FxHashMap::default();
+        for info in mono_item_sizes.iter() {
+            groups.entry(&info.def_path).or_default().push(info);
+        }
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .map(|(def_path, mut copies)| {
+                copies.sort_by(|info1, info2| {
+                    (&info1.instance, &info1.cgu).cmp(&(&info2.instance, &info2.cgu))
+                });
+                let mir_statements: u64 = copies.iter().map(|info| info.mir_statements).sum();
+                let llvm_instructions: u64 = copies.iter().map(|info| info.llvm_instructions).sum();
+                (def_path, copies, mir_statements, llvm_instructions)
+            })
+            .collect();
+
+        // Primary sort: most unoptimized LLVM instructions first.
+        // Secondary sort: most MIR statements first.
+        // Tertiary sort: definition path (dictionary order)
+        groups.sort_by(|(path1, _, mir1, llvm1), (path2, _, mir2, llvm2)| {
+            (llvm2, mir2, path1).cmp(&(llvm1, mir1, path2))
+        });
+
+        for (def_path, copies, mir_statements, llvm_instructions) in &groups {
+            println!(
+                "print-mono-item-size `{}`: {} copies, {} MIR statements, \
+                 {} unoptimized LLVM instructions",
+                def_path,
+                copies.len(),
+                mir_statements,
+                llvm_instructions
+            );
+            for info in copies {
+                println!(
+                    "print-mono-item-size     `{}` in `{}`: {} MIR statements, \
+                     {} unoptimized LLVM instructions",
+                    info.instance, info.cgu, info.mir_statements, info.llvm_instructions
+                );
+            }
+        }
+    }
+
+    pub fn record_call_graph_node(&self, node: CallGraphNode) {
+        self.call_graph.borrow_mut().push(node);
+    }
+
+    pub fn call_graph(&self) -> Vec<CallGraphNode> {
+        self.call_graph.borrow().clone()
+    }
+
+    /// Prints the recorded layouts as JSON, one object per line, in the same
+    /// order as `print_type_sizes`.
+    pub fn print_type_sizes_json(&self, filter: Option<&[String]>) {
+        let type_sizes = self.type_sizes.borrow();
+        for info in sorted_type_sizes(&type_sizes, filter) {
+            println!("{}", as_json(&JsonTypeSize::new(info)));
+        }
+    }
+}
+
+/// Returns the recorded layouts whose description matches `filter`, if any.
+fn sorted_type_sizes<'a>(
+    type_sizes: &'a FxHashSet<TypeSizeInfo>,
+    filter: Option<&[String]>,
+) -> Vec<&'a TypeSizeInfo> {
+    let mut sorted: Vec<_> = type_sizes
+        .iter()
+        .filter(|info| {
+            filter.map_or(true, |paths| matches_type_path(&info.type_description, paths))
+        })
+        .collect();
+
+    // Primary sort: large-to-small.
+    // Secondary sort: description (dictionary order)
+    sorted.sort_by(|info1, info2| {
+        // (reversing cmp order to get large-to-small ordering)
+        match info2.overall_size.cmp(&info1.overall_size) {
+            Ordering::Equal => info1.type_description.cmp(&info2.type_description),
+            other => other,
+        }
+    });
+    sorted
+}
+
+/// Checks whether `type_description` is one of `paths`, an instantiation of one of
+/// them (`path<...>`), or an item inside one of them (`path::...`).
+fn matches_type_path(type_description: &str, paths: &[String]) -> bool {
+    paths.iter().any(|path| match type_description.strip_prefix(path.as_str()) {
+        Some(rest) => rest.is_empty() || rest.starts_with('<') || rest.starts_with("::"),
+        None => false,
+    })
+}
+
+// The following data types are provided just for serialisation.
+
+#[derive(Encodable)]
+struct JsonTypeSize {
+    /// The type, as printed by `-Z print-type-sizes`.
+    name: String,
+    /// "struct", "union", "enum" or "closure".
+    kind: &'static str,
+    size: u64,
+    align: u64,
+    packed: bool,
+    /// The size of the discriminant, if it is stored in a field of its own.
+    discriminant_size: Option<u64>,
+    niche: Option<NicheInfo>,
+    variants: Vec<JsonVariant>,
+    /// Padding after the end of the largest variant.
+    end_padding: u64,
+}
+
+#[derive(Encodable)]
+struct JsonVariant {
+    name: Option<String>,
+    /// The end of the last field, including the discriminant.
+    size: u64,
+    align: u64,
+    /// Whether the variant is unsized, in which case `size` is its minimum size.
+    is_unsized: bool,
+    /// Sorted by increasing offset.
+    fields: Vec<JsonField>,
+}
+
+#[derive(Encodable)]
+struct JsonField {
+    name: String,
+    offset: u64,
+    size: u64,
+    align: u64,
+    /// Padding between the end of the previous field (or the discriminant)
+    /// and this field.
+    padding_before: u64,
+}
+
+impl JsonTypeSize {
+    fn new(info: &TypeSizeInfo) -> JsonTypeSize {
+        let discr_size = info.opt_discr_size.unwrap_or(0);
+        let mut max_variant_size = discr_size;
+
+        let variants = info
+            .variants
+            .iter()
+            .map(|variant| {
+                max_variant_size = cmp::max(max_variant_size, variant.size);
+
+                // Same field order and padding computation as `print_type_sizes`.
+                let mut fields = variant.fields.clone();
+                fields.sort_by_key(|f| (f.offset, f.size));
+                let mut min_offset = discr_size;
+                let fields = fields
+                    .into_iter()
+                    .map(|field| {
+                        let padding_before = field.offset.saturating_sub(min_offset);
+                        min_offset = field.offset + field.size;
+                        JsonField {
+                            name: field.name,
+                            offset: field.offset,
+                            size: field.size,
+                            align: field.align,
+                            padding_before,
+                        }
+                    })
+                    .collect();
+
+                JsonVariant {
+                    name: variant.name.clone(),
+                    size: variant.size,
+                    align: variant.align,
+                    is_unsized: variant.kind == SizeKind::Min,
+                    fields,
+                }
+            })
+            .collect();
+
+        JsonTypeSize {
+            name: info.type_description.clone(),
+            kind: match info.kind {
+                DataTypeKind::Struct => "struct",
+                DataTypeKind::Union => "union",
+                DataTypeKind::Enum => "enum",
+                DataTypeKind::Closure => "closure",
+            },
+            size: info.overall_size,
+            align: info.align,
+            packed: info.packed,
+            discriminant_size: info.opt_discr_size,
+            niche: info.niche,
+            variants,
+            end_padding: info.overall_size.saturating_sub(max_variant_size),
+        }
+    }
 }
@@ -45,6 +45,7 @@ bitflags! {
         const LEAK    = 1 << 1;
         const MEMORY  = 1 << 2;
         const THREAD  = 1 << 3;
+        const CFI     = 1 << 4;
     }
 }
 
@@ -58,6 +59,7 @@ impl fmt::Display for SanitizerSet {
                 SanitizerSet::LEAK => "leak",
                 SanitizerSet::MEMORY => "memory",
                 SanitizerSet::THREAD => "thread",
+                SanitizerSet::CFI => "cfi",
                 _ => panic!("unrecognized sanitizer {:?}", s),
             };
             if !first {
@@ -75,12 +77,18 @@ impl IntoIterator for SanitizerSet {
     type IntoIter = std::vec::IntoIter<SanitizerSet>;
 
     fn into_iter(self) -> Self::IntoIter {
-        [SanitizerSet::ADDRESS, SanitizerSet::LEAK, SanitizerSet::MEMORY, SanitizerSet::THREAD]
-            .iter()
-            .copied()
-            .filter(|&s| self.contains(s))
-            .collect::<Vec<_>>()
-            .into_iter()
+        [
+            SanitizerSet::ADDRESS,
+            SanitizerSet::LEAK,
+            SanitizerSet::MEMORY,
+            SanitizerSet::THREAD,
+            SanitizerSet::CFI,
+        ]
+        .iter()
+        .copied()
+        .filter(|&s| self.contains(s))
+        .collect::<Vec<_>>()
+        .into_iter()
     }
 }
 
@@ -103,6 +111,61 @@ pub enum Strip {
     Symbols,
 }
 
+/// The different settings that the `-Z save-analysis-format` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum SaveAnalysisFormat {
+    /// The rustc-specific JSON format read by the RLS.
+    Json,
+
+    /// A Language Server Index Format dump, one JSON object per line.
+    Lsif,
+}
+
+/// The different settings that the `-Z print-type-sizes-format` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum TypeSizesFormat {
+    /// The human-readable `print-type-size` lines.
+    Human,
+
+    /// One JSON object per type and line.
+    Json,
+}
+
+/// The different settings that the `-Z split-debuginfo` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum SplitDebuginfo {
+    /// Keep all debuginfo in the object files, and let the linker copy it into
+    /// the final artifact.
+    Off,
+
+    /// Write the debuginfo of each codegen unit into a DWARF object (`.dwo`)
+    /// file next to the object file, which only keeps a skeleton unit pointing
+    /// at it. The linker never reads the `.dwo` files.
+    Unpacked,
+
+    /// Like `Unpacked`, but package the `.dwo` files into a single DWARF
+    /// package (`.dwp`) next to each linked executable or dynamic library.
+    Packed,
+}
+
+/// The different settings that the `-Z stack-protector` flag can have.
+#[derive(Clone, Copy, PartialEq, Hash, Debug)]
+pub enum StackProtector {
+    /// Do not insert stack canaries.
+    None,
+
+    /// Protect functions with character arrays of at least 8 bytes, or with
+    /// `alloca`s, like `-fstack-protector` in Clang and GCC.
+    Basic,
+
+    /// Protect functions with arrays of any type and size, or whose locals
+    /// have their address taken, like `-fstack-protector-strong`.
+    Strong,
+
+    /// Protect all functions, like `-fstack-protector-all`.
+    All,
+}
+
 /// The different settings that the `-C control-flow-guard` flag can have.
 #[derive(Clone, Copy, PartialEq, Hash, Debug)]
 pub enum CFGuard {
@@ -505,6 +568,7 @@ impl_stable_hash_via_hash!(OutputFilenames);
 
 pub const RLINK_EXT: &str = "rlink";
 pub const RUST_CGU_EXT: &str = "rcgu";
+pub const DWARF_OBJECT_EXT: &str = "dwo";
 
 impl OutputFilenames {
     pub fn new(
@@ -565,6 +629,21 @@ impl OutputFilenames {
         path.set_extension(extension);
         path
     }
+
+    /// Returns the path of the DWARF object file the debuginfo of a codegen
+    /// unit is written to, if it is split out of the object file.
+    pub fn split_dwarf_path(
+        &self,
+        split_debuginfo: SplitDebuginfo,
+        codegen_unit_name: Option<&str>,
+    ) -> Option<PathBuf> {
+        match split_debuginfo {
+            SplitDebuginfo::Off => None,
+            SplitDebuginfo::Unpacked | SplitDebuginfo::Packed => {
+                Some(self.temp_path_ext(DWARF_OBJECT_EXT, codegen_unit_name))
+            }
+        }
+    }
 }
 
 pub fn host_triple() -> &'static str {
@@ -2050,8 +2129,8 @@ impl PpMode {
 crate mod dep_tracking {
     use super::{
         CFGuard, CrateType, DebugInfo, ErrorOutputType, LinkerPluginLto, LtoCli, OptLevel,
-        OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SwitchWithOptPath,
-        SymbolManglingVersion,
+        OutputTypes, Passes, SanitizerSet, SourceFileHashAlgorithm, SplitDebuginfo, StackProtector,
+        SwitchWithOptPath, SymbolManglingVersion,
     };
     use crate::lint;
     use crate::utils::NativeLibKind;
@@ -2126,6 +2205,8 @@ crate mod dep_tracking {
     impl_dep_tracking_hash_via_hash!(NativeLibKind);
     impl_dep_tracking_hash_via_hash!(SanitizerSet);
     impl_dep_tracking_hash_via_hash!(CFGuard);
+    impl_dep_tracking_hash_via_hash!(SplitDebuginfo);
+    impl_dep_tracking_hash_via_hash!(StackProtector);
     impl_dep_tracking_hash_via_hash!(TargetTriple);
     impl_dep_tracking_hash_via_hash!(Edition);
     impl_dep_tracking_hash_via_hash!(LinkerPluginLto);
@@ -1,28 +1,84 @@
 pub use self::Level::*;
 use rustc_ast::node_id::{NodeId, NodeMap};
+use rustc_ast::AttrId;
 use rustc_data_structures::stable_hasher::{HashStable, StableHasher, ToStableHashKey};
 use rustc_errors::{pluralize, Applicability, DiagnosticBuilder};
+use rustc_hir::HirId;
 use rustc_span::edition::Edition;
 use rustc_span::{sym, symbol::Ident, MultiSpan, Span, Symbol};
 
 pub mod builtin;
 
+/// Identifies a single lint name in an `#[expect]` attribute.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
+pub enum LintExpectationId {
+    /// An attribute of the AST, as seen by the early lints. Attribute ids are only unique within a
+    /// session, so this id is never hashed: the expectations are checked with the `Stable` id of
+    /// the same attribute, found when building the lint levels of the HIR.
+    Unstable { attr_id: AttrId, lint_index: u16 },
+    /// An attribute of the HIR, identified by its node and its position among the attributes of
+    /// the node.
+    Stable { hir_id: HirId, attr_index: u16, lint_index: u16 },
+}
+
+impl LintExpectationId {
+    /// Returns the id of the lint name at `lint_index` in the attribute's list of lints.
+    pub fn with_lint_index(self, lint_index: u16) -> LintExpectationId {
+        match self {
+            LintExpectationId::Unstable { attr_id, .. } => {
+                LintExpectationId::Unstable { attr_id, lint_index }
+            }
+            LintExpectationId::Stable { hir_id, attr_index, .. } => {
+                LintExpectationId::Stable { hir_id, attr_index, lint_index }
+            }
+        }
+    }
+}
+
+impl<HCX: rustc_hir::HashStableContext> HashStable<HCX> for LintExpectationId {
+    #[inline]
+    fn hash_stable(&self, hcx: &mut HCX, hasher: &mut StableHasher) {
+        match *self {
+            LintExpectationId::Stable { hir_id, attr_index, lint_index } => {
+                hir_id.hash_stable(hcx, hasher);
+                attr_index.hash_stable(hcx, hasher);
+                lint_index.hash_stable(hcx, hasher);
+            }
+            LintExpectationId::Unstable { .. } => {
+                unreachable!("`LintExpectationId::Unstable` is never part of the HIR lint levels")
+            }
+        }
+    }
+}
+
 /// Setting for how to handle a lint.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug, Hash)]
 pub enum Level {
     Allow,
+    /// Like `Allow`, but `unfulfilled_lint_expectations` is reported if the lint is never
+    /// emitted.
+    Expect(LintExpectationId),
     Warn,
     Deny,
     Forbid,
 }
 
-rustc_data_structures::impl_stable_hash_via_hash!(Level);
+impl<HCX: rustc_hir::HashStableContext> HashStable<HCX> for Level {
+    #[inline]
+    fn hash_stable(&self, hcx: &mut HCX, hasher: &mut StableHasher) {
+        ::std::mem::discriminant(self).hash_stable(hcx, hasher);
+        if let Level::Expect(id) = self {
+            id.hash_stable(hcx, hasher);
+        }
+    }
+}
 
 impl Level {
     /// Converts a level to a lower-case string.
     pub fn as_str(self) -> &'static str {
         match self {
             Level::Allow => "allow",
+            Level::Expect(_) => "expect",
             Level::Warn => "warn",
             Level::Deny => "deny",
             Level::Forbid => "forbid",
@@ -30,6 +86,8 @@ impl Level {
     }
 
     /// Converts a lower-case string to a level.
+    ///
+    /// This never returns `Level::Expect`, which can only be set by an attribute.
     pub fn from_str(x: &str) -> Option<Level> {
         match x {
             "allow" => Some(Level::Allow),
@@ -41,6 +99,8 @@ impl Level {
     }
 
     /// Converts a symbol to a level.
+    ///
+    /// `sym::expect` is not handled here, since `Level::Expect` needs the id of the attribute.
     pub fn from_symbol(x: Symbol) -> Option<Level> {
         match x {
             sym::allow => Some(Level::Allow),
@@ -539,6 +539,39 @@ declare_lint! {
     };
 }
 
+declare_lint! {
+    pub UNFULFILLED_LINT_EXPECTATIONS,
+    Warn,
+    "unfulfilled lint expectation",
+    @feature_gate = sym::lint_reasons;
+}
+
+declare_lint! {
+    pub DISJOINT_CAPTURE_DROP_REORDER,
+    Allow,
+    "detects closures whose captured variables would be dropped in a different order \
+     once closures capture disjoint fields"
+}
+
+declare_lint! {
+    pub MUST_NOT_SUSPEND,
+    Warn,
+    "use of a `#[must_not_suspend]` value across a suspend point",
+    @feature_gate = sym::must_not_suspend;
+}
+
+declare_lint! {
+    pub LARGE_FUTURES,
+    Warn,
+    "generators and `async` state machines larger than `-Z future-size-limit`",
+}
+
+declare_lint! {
+    pub LARGE_ASSIGNMENTS,
+    Warn,
+    "values larger than `-Z move-size-limit` that are moved or copied",
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -612,6 +645,11 @@ declare_lint_pass! {
         UNSAFE_OP_IN_UNSAFE_FN,
         INCOMPLETE_INCLUDE,
         CENUM_IMPL_DROP_CAST,
+        UNFULFILLED_LINT_EXPECTATIONS,
+        DISJOINT_CAPTURE_DROP_REORDER,
+        MUST_NOT_SUSPEND,
+        LARGE_FUTURES,
+        LARGE_ASSIGNMENTS,
     ]
 }
 
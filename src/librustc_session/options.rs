@@ -248,11 +248,15 @@ macro_rules! options {
         pub const parse_passes: &str = "a space-separated list of passes, or `all`";
         pub const parse_panic_strategy: &str = "either `unwind` or `abort`";
         pub const parse_relro_level: &str = "one of: `full`, `partial`, or `off`";
-        pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `leak`, `memory` or `thread`";
+        pub const parse_sanitizers: &str = "comma separated list of sanitizers: `address`, `cfi`, `leak`, `memory` or `thread`";
         pub const parse_sanitizer_memory_track_origins: &str = "0, 1, or 2";
         pub const parse_cfguard: &str =
             "either a boolean (`yes`, `no`, `on`, `off`, etc), `checks`, or `nochecks`";
         pub const parse_strip: &str = "either `none`, `debuginfo`, or `symbols`";
+        pub const parse_save_analysis_format: &str = "either `json` or `lsif`";
+        pub const parse_type_sizes_format: &str = "either `human` or `json`";
+        pub const parse_split_debuginfo: &str = "one of: `off`, `unpacked`, or `packed`";
+        pub const parse_stack_protector: &str = "one of: `none`, `basic`, `strong`, or `all`";
         pub const parse_linker_flavor: &str = ::rustc_target::spec::LinkerFlavor::one_of();
         pub const parse_optimization_fuel: &str = "crate=integer";
         pub const parse_unpretty: &str = "`string` or `string=string`";
@@ -464,6 +468,7 @@ macro_rules! options {
                 for s in v.split(',') {
                     *slot |= match s {
                         "address" => SanitizerSet::ADDRESS,
+                        "cfi" => SanitizerSet::CFI,
                         "leak" => SanitizerSet::LEAK,
                         "memory" => SanitizerSet::MEMORY,
                         "thread" => SanitizerSet::THREAD,
@@ -495,6 +500,45 @@ macro_rules! options {
             true
         }
 
+        fn parse_save_analysis_format(slot: &mut SaveAnalysisFormat, v: Option<&str>) -> bool {
+            match v {
+                Some("json") => *slot = SaveAnalysisFormat::Json,
+                Some("lsif") => *slot = SaveAnalysisFormat::Lsif,
+                _ => return false,
+            }
+            true
+        }
+
+        fn parse_type_sizes_format(slot: &mut TypeSizesFormat, v: Option<&str>) -> bool {
+            match v {
+                Some("human") => *slot = TypeSizesFormat::Human,
+                Some("json") => *slot = TypeSizesFormat::Json,
+                _ => return false,
+            }
+            true
+        }
+
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            match v {
+                Some("off") => *slot = SplitDebuginfo::Off,
+                Some("unpacked") => *slot = SplitDebuginfo::Unpacked,
+                Some("packed") => *slot = SplitDebuginfo::Packed,
+                _ => return false,
+            }
+            true
+        }
+
+        fn parse_stack_protector(slot: &mut StackProtector, v: Option<&str>) -> bool {
+            match v {
+                Some("none") => *slot = StackProtector::None,
+                Some("basic") => *slot = StackProtector::Basic,
+                Some("strong") => *slot = StackProtector::Strong,
+                Some("all") => *slot = StackProtector::All,
+                _ => return false,
+            }
+            true
+        }
+
         fn parse_cfguard(slot: &mut CFGuard, v: Option<&str>) -> bool {
             if v.is_some() {
                 let mut bool_arg = None;
@@ -860,6 +904,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "force all crates to be `rustc_private` unstable (default: no)"),
     fuel: Option<(String, u64)> = (None, parse_optimization_fuel, [TRACKED],
         "set the optimization fuel quota for a crate"),
+    future_size_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "warn about `async` blocks, `async fn`s and generators whose state machine is larger \
+        than this many bytes (default: no limit)"),
     hir_stats: bool = (false, parse_bool, [UNTRACKED],
         "print some statistics about AST and HIR (default: no)"),
     human_readable_cgu_names: bool = (false, parse_bool, [TRACKED],
@@ -915,6 +962,9 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         (default: no)"),
     mir_opt_level: usize = (1, parse_uint, [TRACKED],
         "MIR optimization level (0-3; default: 1)"),
+    move_size_limit: Option<usize> = (None, parse_opt_uint, [TRACKED],
+        "warn about values larger than this many bytes that are moved or copied \
+        (default: no limit)"),
     mutable_noalias: bool = (false, parse_bool, [TRACKED],
         "emit noalias metadata for mutable references (default: no)"),
     new_llvm_pass_manager: bool = (false, parse_bool, [TRACKED],
@@ -963,10 +1013,22 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the arguments passed to the linker (default: no)"),
     print_llvm_passes: bool = (false, parse_bool, [UNTRACKED],
         "print the LLVM optimization passes being run (default: no)"),
+    print_mono_item_sizes: bool = (false, parse_bool, [UNTRACKED],
+        "print the number of MIR statements and unoptimized LLVM instructions of each \
+        codegened function, grouped by generic definition (default: no)"),
     print_mono_items: Option<String> = (None, parse_opt_string, [UNTRACKED],
         "print the result of the monomorphization collection pass"),
+    print_stack_usage: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
+        "print the worst-case stack usage of these comma-separated functions, computed from \
+        the call graph and the frame sizes in `.stack_sizes` (implies `-Z emit-stack-sizes`)"),
     print_type_sizes: bool = (false, parse_bool, [UNTRACKED],
         "print layout information for each type encountered (default: no)"),
+    print_type_sizes_filter: Option<Vec<String>> = (None, parse_opt_comma_list, [UNTRACKED],
+        "only print the layouts of these comma-separated type paths, their instantiations \
+        and the types inside them"),
+    print_type_sizes_format: TypeSizesFormat = (TypeSizesFormat::Human,
+        parse_type_sizes_format, [UNTRACKED],
+        "the format of `-Z print-type-sizes`, either `human` or `json` (default: human)"),
     profile: bool = (false, parse_bool, [TRACKED],
         "insert profiling code (default: no)"),
     profile_emit: Option<PathBuf> = (None, parse_opt_pathbuf, [TRACKED],
@@ -997,6 +1059,10 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     save_analysis: bool = (false, parse_bool, [UNTRACKED],
         "write syntax and type analysis (in JSON format) information, in \
         addition to normal output (default: no)"),
+    save_analysis_format: SaveAnalysisFormat = (SaveAnalysisFormat::Json,
+        parse_save_analysis_format, [UNTRACKED],
+        "the format of the `-Z save-analysis` output: `json` (the RLS format) or `lsif` \
+        (a Language Server Index Format dump for code-intelligence tools) (default: `json`)"),
     self_profile: SwitchWithOptPath = (SwitchWithOptPath::Disabled,
         parse_switch_with_opt_path, [UNTRACKED],
         "run the self profiler and output the raw event data"),
@@ -1015,8 +1081,14 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
     // o/w tests have closure@path
     span_free_formats: bool = (false, parse_bool, [UNTRACKED],
         "exclude spans when debug-printing compiler state (default: no)"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo, [TRACKED],
+        "split DWARF debuginfo out of the object files into `.dwo` files, \
+        optionally packaged into a `.dwp` file (`off` (default), `unpacked`, or `packed`)"),
     src_hash_algorithm: Option<SourceFileHashAlgorithm> = (None, parse_src_file_hash, [TRACKED],
         "hash algorithm of source files in debug info (`md5`, or `sha1`)"),
+    stack_protector: StackProtector = (StackProtector::None, parse_stack_protector, [TRACKED],
+        "insert stack canaries to detect stack buffer overflows \
+        (`none` (default), `basic`, `strong`, or `all`)"),
     strip: Strip = (Strip::None, parse_strip, [UNTRACKED],
         "tell the linker which information to strip (`none` (default), `debuginfo` or `symbols`)"),
     symbol_mangling_version: SymbolManglingVersion = (SymbolManglingVersion::Legacy,
@@ -1,7 +1,9 @@
 use crate::cgu_reuse_tracker::CguReuseTracker;
 use crate::code_stats::CodeStats;
-pub use crate::code_stats::{DataTypeKind, FieldInfo, SizeKind, VariantInfo};
-use crate::config::{self, CrateType, OutputType, PrintRequest, SanitizerSet, SwitchWithOptPath};
+pub use crate::code_stats::{CallGraphNode, DataTypeKind, FieldInfo, MonoItemSizeInfo, NicheInfo};
+pub use crate::code_stats::{SizeKind, VariantInfo};
+use crate::config::{self, CrateType, DebugInfo, OutputType, PrintRequest, SanitizerSet};
+use crate::config::{SplitDebuginfo, StackProtector, SwitchWithOptPath};
 use crate::filesearch;
 use crate::lint;
 use crate::parse::ParseSess;
@@ -213,6 +215,10 @@ pub struct Session {
 
     known_attrs: Lock<MarkedAttrs>,
     used_attrs: Lock<MarkedAttrs>,
+
+    /// The `#[expect]` lint attributes whose lints have been emitted, and so which don't
+    /// need to report `unfulfilled_lint_expectations`.
+    fulfilled_lint_expectations: Lock<FxHashSet<lint::LintExpectationId>>,
 }
 
 pub struct PerfStats {
@@ -709,6 +715,16 @@ impl Session {
         self.opts.debugging_opts.tls_model.unwrap_or(self.target.target.options.tls_model)
     }
 
+    /// Returns how debuginfo is split out of the object files, which only
+    /// matters if debuginfo is generated at all.
+    pub fn split_debuginfo(&self) -> SplitDebuginfo {
+        if self.opts.debuginfo == DebugInfo::None {
+            SplitDebuginfo::Off
+        } else {
+            self.opts.debugging_opts.split_debuginfo
+        }
+    }
+
     pub fn must_not_eliminate_frame_pointers(&self) -> bool {
         // "mcount" function relies on stack pointer.
         // See <https://sourceware.org/binutils/docs/gprof/Implementation.html>.
@@ -1037,6 +1053,16 @@ impl Session {
         self.used_attrs.lock().is_marked(attr)
     }
 
+    /// Records that a lint was emitted while its level was set by an `#[expect]` attribute.
+    pub fn fulfill_lint_expectation(&self, id: lint::LintExpectationId) {
+        self.fulfilled_lint_expectations.lock().insert(id);
+    }
+
+    /// Returns the ids of the `#[expect]` attributes whose lints have been emitted so far.
+    pub fn fulfilled_lint_expectations(&self) -> FxHashSet<lint::LintExpectationId> {
+        self.fulfilled_lint_expectations.lock().clone()
+    }
+
     /// Returns `true` if the attribute's path matches the argument. If it matches, then the
     /// attribute is marked as used.
 
@@ -1354,6 +1380,7 @@ pub fn build_session(
         target_features: FxHashSet::default(),
         known_attrs: Lock::new(MarkedAttrs::new()),
         used_attrs: Lock::new(MarkedAttrs::new()),
+        fulfilled_lint_expectations: Lock::new(Default::default()),
     };
 
     validate_commandline_args_with_session_available(&sess);
@@ -1442,6 +1469,28 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         );
     }
 
+    // Split DWARF is an extension of DWARF in ELF object files. It can't be used with
+    // the CodeView debuginfo of MSVC targets or the object formats of macOS and wasm.
+    if sess.opts.debugging_opts.split_debuginfo != SplitDebuginfo::Off
+        && (sess.target.target.options.is_like_osx
+            || sess.target.target.options.is_like_windows
+            || sess.target.target.arch == "wasm32")
+    {
+        sess.err(&format!(
+            "`-Z split-debuginfo` is only supported on ELF targets, not on `{}`",
+            sess.opts.target_triple
+        ));
+    }
+
+    if sess.opts.debugging_opts.stack_protector != StackProtector::None
+        && !sess.target.target.options.supports_stack_protector
+    {
+        sess.err(&format!(
+            "`-Z stack-protector` is not supported on `{}`",
+            sess.opts.target_triple
+        ));
+    }
+
     const ASAN_SUPPORTED_TARGETS: &[&str] = &[
         "aarch64-fuchsia",
         "aarch64-unknown-linux-gnu",
@@ -1450,6 +1499,8 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
         "x86_64-unknown-freebsd",
         "x86_64-unknown-linux-gnu",
     ];
+    const CFI_SUPPORTED_TARGETS: &[&str] =
+        &["aarch64-unknown-linux-gnu", "x86_64-unknown-linux-gnu"];
     const LSAN_SUPPORTED_TARGETS: &[&str] =
         &["aarch64-unknown-linux-gnu", "x86_64-apple-darwin", "x86_64-unknown-linux-gnu"];
     const MSAN_SUPPORTED_TARGETS: &[&str] =
@@ -1465,6 +1516,7 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
     for s in sess.opts.debugging_opts.sanitizer {
         let supported_targets = match s {
             SanitizerSet::ADDRESS => ASAN_SUPPORTED_TARGETS,
+            SanitizerSet::CFI => CFI_SUPPORTED_TARGETS,
             SanitizerSet::LEAK => LSAN_SUPPORTED_TARGETS,
             SanitizerSet::MEMORY => MSAN_SUPPORTED_TARGETS,
             SanitizerSet::THREAD => TSAN_SUPPORTED_TARGETS,
@@ -1477,7 +1529,13 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
                 supported_targets.join(", ")
             ));
         }
-        let conflicting = sess.opts.debugging_opts.sanitizer - s;
+        // CFI only instruments indirect calls and has no runtime, so it can be combined with
+        // any of the other sanitizers.
+        let conflicting = if s == SanitizerSet::CFI {
+            SanitizerSet::empty()
+        } else {
+            sess.opts.debugging_opts.sanitizer - s - SanitizerSet::CFI
+        };
         if !conflicting.is_empty() {
             sess.err(&format!(
                 "`-Zsanitizer={}` is incompatible with `-Zsanitizer={}`",
@@ -1487,6 +1545,15 @@ fn validate_commandline_args_with_session_available(sess: &Session) {
             break;
         }
     }
+
+    // The type tests emitted for CFI are lowered by a pass that only runs during LTO, as it
+    // needs to see every function whose address can be taken.
+    if sess.opts.debugging_opts.sanitizer.contains(SanitizerSet::CFI)
+        && sess.lto() != config::Lto::Fat
+        && !sess.opts.cg.linker_plugin_lto.enabled()
+    {
+        sess.err("`-Zsanitizer=cfi` requires `-Clto` or `-Clinker-plugin-lto`");
+    }
 }
 
 /// Holds data on the current incremental compilation session, if there is one.
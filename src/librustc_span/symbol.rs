@@ -309,10 +309,12 @@ symbols! {
         bridge,
         bswap,
         c_variadic,
+        c_void,
         call,
         call_mut,
         call_once,
         caller_location,
+        capture_disjoint_fields,
         cdylib,
         ceilf32,
         ceilf64,
@@ -321,12 +323,14 @@ symbols! {
         cfg_attr,
         cfg_attr_multi,
         cfg_doctest,
+        cfg_hide,
         cfg_sanitize,
         cfg_target_feature,
         cfg_target_has_atomic,
         cfg_target_thread_local,
         cfg_target_vendor,
         cfg_version,
+        cfi,
         char,
         client,
         clippy,
@@ -417,6 +421,7 @@ symbols! {
         deref,
         deref_mut,
         derive,
+        destructuring_assignment,
         diagnostic,
         direct,
         discriminant_kind,
@@ -427,6 +432,7 @@ symbols! {
         div_assign,
         doc,
         doc_alias,
+        doc_auto_cfg,
         doc_cfg,
         doc_keyword,
         doc_masked,
@@ -471,6 +477,7 @@ symbols! {
         existential_type,
         exp2f32,
         exp2f64,
+        expect,
         expected,
         expf32,
         expf64,
@@ -587,6 +594,7 @@ symbols! {
         infer_static_outlives_requirements,
         inlateout,
         inline,
+        inline_const,
         inout,
         intel,
         into_iter,
@@ -610,6 +618,7 @@ symbols! {
         lazy_normalization_consts,
         le,
         let_chains,
+        let_else,
         lhs,
         lib,
         libc,
@@ -686,6 +695,7 @@ symbols! {
         mul,
         mul_assign,
         mul_with_overflow,
+        must_not_suspend,
         must_use,
         mut_ptr,
         mut_slice_ptr,
@@ -0,0 +1,211 @@
+//! Type identifiers for the control-flow integrity sanitizer (`-Zsanitizer=cfi`).
+//!
+//! LLVM implements CFI with type metadata: functions and vtables are tagged with type identifiers,
+//! and indirect calls first test that the called pointer, or the vtable the called method is loaded
+//! from, is tagged with the identifier the caller expects. Type identifiers of functions are the
+//! Itanium C++ ABI mangling of the function type, which is what Clang uses for
+//! `-fsanitize=cfi-icall`, so indirect calls between Rust and C code compiled by Clang are checked
+//! in both directions. To that end,
+//! integers are encoded as the C integer types of the same size, `()` and `c_void` as `void`, and
+//! `#[repr(C)]` and extern types by their name. All other types have no equivalent in C and are
+//! encoded as vendor extended types holding their v0 mangling.
+
+use rustc_ast::{FloatTy, IntTy, UintTy};
+use rustc_data_structures::base_n;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::sym;
+use rustc_target::abi::call::FnAbi;
+
+use crate::v0;
+
+use std::iter;
+
+/// A part of a type identifier that later occurrences refer back to instead of repeating it, as
+/// defined by the `<substitution>` production of the Itanium C++ ABI.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum Component<'tcx> {
+    /// A pointer, or a type encoded by its name.
+    Ty(Ty<'tcx>),
+    /// The `const`-qualified pointee of a `*const T` or `&T`.
+    Const(Ty<'tcx>),
+    /// The function type a function pointer points to.
+    Fn(Ty<'tcx>),
+}
+
+/// The components encoded so far, numbered in the order they appeared in.
+type Substitutions<'tcx> = FxHashMap<Component<'tcx>, usize>;
+
+/// Returns the type identifier of functions with the ABI `fn_abi`, which calls through function
+/// pointers test.
+pub fn typeid_for_fnabi<'tcx>(tcx: TyCtxt<'tcx>, fn_abi: &FnAbi<'tcx, Ty<'tcx>>) -> String {
+    let mut substitutions = Substitutions::default();
+    let mut typeid = String::from("_ZTSF");
+    if fn_abi.ret.is_ignore() {
+        typeid.push('v');
+    } else {
+        typeid.push_str(&encode_ty(tcx, fn_abi.ret.layout.ty, &mut substitutions));
+    }
+
+    // The variadic arguments of a call are not part of the type of the callee.
+    let args =
+        if fn_abi.c_variadic { &fn_abi.args[..fn_abi.fixed_count] } else { &fn_abi.args[..] };
+
+    // Ignored arguments are zero-sized, which C has no equivalent for. This also leaves out the
+    // closure environment, which the shim that turns a closure into a function pointer takes by
+    // value.
+    let mut args = args.iter().filter(|arg| !arg.is_ignore()).peekable();
+    if args.peek().is_none() && !fn_abi.c_variadic {
+        typeid.push('v');
+    }
+    for arg in args {
+        typeid.push_str(&encode_ty(tcx, arg.layout.ty, &mut substitutions));
+    }
+    if fn_abi.c_variadic {
+        typeid.push('z');
+    }
+    typeid.push('E');
+    typeid
+}
+
+/// Returns the type identifier of the vtables for `trait_ref`, which calls through trait objects
+/// test the vtable they load the method from with. Only the principal trait of the trait object
+/// counts, as a trait object keeps its vtable when auto traits are dropped from its type.
+pub fn typeid_for_vtable<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    trait_ref: Option<ty::PolyExistentialTraitRef<'tcx>>,
+) -> String {
+    let predicates = match trait_ref {
+        Some(trait_ref) => trait_ref.map_bound(|trait_ref| {
+            tcx.mk_existential_predicates(iter::once(ty::ExistentialPredicate::Trait(trait_ref)))
+        }),
+        None => ty::Binder::dummy(tcx.mk_existential_predicates(iter::empty())),
+    };
+    let object_ty = tcx.mk_dynamic(predicates, tcx.lifetimes.re_erased);
+    format!("_ZTS{}", encode_vendor_ty(tcx, object_ty))
+}
+
+fn encode_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    ty: Ty<'tcx>,
+    substitutions: &mut Substitutions<'tcx>,
+) -> String {
+    match ty.kind {
+        ty::Bool => "b".to_string(),
+        ty::Int(_) | ty::Uint(_) => encode_integer(tcx, ty).to_string(),
+        ty::Float(FloatTy::F32) => "f".to_string(),
+        ty::Float(FloatTy::F64) => "d".to_string(),
+        ty::Tuple(_) if ty.is_unit() => "v".to_string(),
+        ty::Adt(adt_def, _) if tcx.is_diagnostic_item(sym::c_void, adt_def.did) => "v".to_string(),
+        ty::Adt(adt_def, substs) if adt_def.repr.c() && substs.is_empty() => {
+            encode_name(tcx, adt_def.did, ty, substitutions)
+        }
+        ty::Foreign(def_id) => encode_name(tcx, def_id, ty, substitutions),
+
+        ty::Ref(_, pointee, mutbl) | ty::RawPtr(ty::TypeAndMut { ty: pointee, mutbl }) => {
+            let mut s = encode_ty(tcx, pointee, substitutions);
+            if mutbl == hir::Mutability::Not {
+                s.insert(0, 'K');
+                compress(substitutions, Component::Const(pointee), &mut s);
+            }
+            s.insert(0, 'P');
+            // References are encoded like raw pointers, so they are the same component.
+            let ptr_ty = tcx.mk_ptr(ty::TypeAndMut { ty: pointee, mutbl });
+            compress(substitutions, Component::Ty(ptr_ty), &mut s);
+            s
+        }
+
+        ty::FnPtr(sig) => {
+            let sig = tcx.erase_late_bound_regions(&sig);
+            let mut s = String::from("F");
+            if sig.output().is_unit() || sig.output().is_never() {
+                s.push('v');
+            } else {
+                s.push_str(&encode_ty(tcx, sig.output(), substitutions));
+            }
+            if sig.inputs().is_empty() && !sig.c_variadic {
+                s.push('v');
+            }
+            for &input in sig.inputs() {
+                s.push_str(&encode_ty(tcx, input, substitutions));
+            }
+            if sig.c_variadic {
+                s.push('z');
+            }
+            s.push('E');
+            compress(substitutions, Component::Fn(ty), &mut s);
+            s.insert(0, 'P');
+            compress(substitutions, Component::Ty(ty), &mut s);
+            s
+        }
+
+        _ => encode_vendor_ty(tcx, ty),
+    }
+}
+
+/// Encodes an integer type as the C integer type of the same size and signedness. Pointer-sized
+/// integers are `long` on 64-bit targets and `int` on 32-bit targets, like `ssize_t` and `size_t`.
+fn encode_integer(tcx: TyCtxt<'_>, ty: Ty<'_>) -> &'static str {
+    let long_is_64 = tcx.data_layout.pointer_size.bits() == 64;
+    match ty.kind {
+        ty::Int(IntTy::I8) => "a",
+        ty::Int(IntTy::I16) => "s",
+        ty::Int(IntTy::I32) => "i",
+        ty::Int(IntTy::I64) | ty::Int(IntTy::Isize) if long_is_64 => "l",
+        ty::Int(IntTy::I64) => "x",
+        ty::Int(IntTy::Isize) => "i",
+        ty::Int(IntTy::I128) => "n",
+        ty::Uint(UintTy::U8) => "h",
+        ty::Uint(UintTy::U16) => "t",
+        ty::Uint(UintTy::U32) => "j",
+        ty::Uint(UintTy::U64) | ty::Uint(UintTy::Usize) if long_is_64 => "m",
+        ty::Uint(UintTy::U64) => "y",
+        ty::Uint(UintTy::Usize) => "j",
+        ty::Uint(UintTy::U128) => "o",
+        _ => bug!("encode_integer: unexpected type `{}`", ty),
+    }
+}
+
+/// Encodes a `#[repr(C)]` type or an extern type by its name, as C code declaring it would.
+fn encode_name<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    def_id: DefId,
+    ty: Ty<'tcx>,
+    substitutions: &mut Substitutions<'tcx>,
+) -> String {
+    let name = tcx.item_name(def_id).as_str();
+    let mut s = format!("{}{}", name.len(), name);
+    compress(substitutions, Component::Ty(ty), &mut s);
+    s
+}
+
+/// Encodes a type that has no equivalent in C as a vendor extended type, `u <source-name>`.
+/// Vendor extended types are builtin types, so they are never substituted.
+fn encode_vendor_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> String {
+    let mangled = v0::mangle_type(tcx, tcx.erase_regions(&ty));
+    format!("u{}{}", mangled.len(), mangled)
+}
+
+/// Replaces the encoding `s` of `component` by a reference to its first occurrence, or makes it
+/// the next substitution candidate if this is the first one.
+fn compress<'tcx>(
+    substitutions: &mut Substitutions<'tcx>,
+    component: Component<'tcx>,
+    s: &mut String,
+) {
+    match substitutions.get(&component) {
+        // The first candidate is `S_`, the next ones are `S0_`, `S1_`, and so on, numbered in
+        // uppercase base 36.
+        Some(&0) => *s = "S_".to_string(),
+        Some(&n) => {
+            let seq_id = base_n::encode(n as u128 - 1, base_n::CASE_INSENSITIVE);
+            *s = format!("S{}_", seq_id.to_uppercase());
+        }
+        None => {
+            let n = substitutions.len();
+            substitutions.insert(component, n);
+        }
+    }
+}
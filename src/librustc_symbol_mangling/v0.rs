@@ -54,6 +54,13 @@ pub(super) fn mangle(
     cx.out
 }
 
+/// Mangles a type on its own, without the compression of repeated paths and types, for the
+/// type identifiers of `typeid.rs`.
+pub(super) fn mangle_type(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> String {
+    let cx = SymbolMangler { tcx, compress: None, binders: vec![], out: String::new() };
+    cx.print_type(ty).unwrap().out
+}
+
 struct CompressionCaches<'tcx> {
     // The length of the prefix in `out` (e.g. 2 for `_R`).
     start_offset: usize,
@@ -42,6 +42,7 @@ pub fn opts() -> TargetOptions {
         //
         // For some more info see the commentary on #47086
         link_env: vec![("ZERO_AR_DATE".to_string(), "1".to_string())],
+        supports_stack_protector: true,
 
         ..Default::default()
     }
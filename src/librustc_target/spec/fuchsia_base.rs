@@ -37,6 +37,7 @@ pub fn opts() -> TargetOptions {
         ]),
         position_independent_executables: true,
         has_elf_tls: true,
+        supports_stack_protector: true,
         ..Default::default()
     }
 }
@@ -25,6 +25,7 @@ pub fn opts() -> TargetOptions {
         eliminate_frame_pointer: false,
         eh_frame_header: false,
         late_link_args,
+        supports_stack_protector: true,
 
         // While we support ELF TLS, rust requires a way to register
         // cleanup handlers (in C, this would be something along the lines of:
@@ -28,6 +28,7 @@ pub fn opts() -> TargetOptions {
         position_independent_executables: true,
         relro_level: RelroLevel::Full,
         has_elf_tls: true,
+        supports_stack_protector: true,
         ..Default::default()
     }
 }
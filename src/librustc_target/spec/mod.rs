@@ -993,6 +993,11 @@ pub struct TargetOptions {
     /// used to locate unwinding information is passed
     /// (only has effect if the linker is `ld`-like).
     pub eh_frame_header: bool,
+
+    /// Whether the backend can insert stack protector canaries, as requested with
+    /// `-Z stack-protector`, and the C library of the target provides the canary and
+    /// `__stack_chk_fail` they need. Defaults to false.
+    pub supports_stack_protector: bool,
 }
 
 impl Default for TargetOptions {
@@ -1085,6 +1090,7 @@ impl Default for TargetOptions {
             llvm_args: vec![],
             use_ctors_section: false,
             eh_frame_header: true,
+            supports_stack_protector: false,
         }
     }
 }
@@ -1478,6 +1484,7 @@ impl Target {
         key!(llvm_args, list);
         key!(use_ctors_section, bool);
         key!(eh_frame_header, bool);
+        key!(supports_stack_protector, bool);
 
         // NB: The old name is deprecated, but support for it is retained for
         // compatibility.
@@ -1716,6 +1723,7 @@ impl ToJson for Target {
         target_option_val!(llvm_args);
         target_option_val!(use_ctors_section);
         target_option_val!(eh_frame_header);
+        target_option_val!(supports_stack_protector);
 
         if default.unsupported_abis != self.options.unsupported_abis {
             d.insert(
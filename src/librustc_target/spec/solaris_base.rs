@@ -9,6 +9,7 @@ pub fn opts() -> TargetOptions {
         is_like_solaris: true,
         limit_rdylib_exports: false, // Linker doesn't support this
         eh_frame_header: false,
+        supports_stack_protector: true,
 
         ..Default::default()
     }
@@ -89,6 +89,7 @@ pub fn opts() -> TargetOptions {
         emit_debug_gdb_scripts: false,
         requires_uwtable: true,
         eh_frame_header: false,
+        supports_stack_protector: true,
 
         ..Default::default()
     }
@@ -281,6 +281,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             }
             ExprKind::DropTemps(ref e) => self.check_expr_with_expectation(e, expected),
             ExprKind::Array(ref args) => self.check_expr_array(args, expected, expr),
+            ExprKind::ConstBlock(ref anon_const) => self.check_expr_const_block(anon_const),
             ExprKind::Repeat(ref element, ref count) => {
                 self.check_expr_repeat(element, count, expected, expr)
             }
@@ -746,9 +747,12 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 DiagnosticId::Error(err_code.into()),
             );
             err.span_label(lhs.span, "cannot assign to this expression");
+            // Plain assignments to tuples, arrays and structs are destructured while lowering,
+            // so only compound assignments can get here.
             if self.is_destructuring_place_expr(lhs) {
-                err.note("destructuring assignments are not currently supported");
-                err.note("for more information, see https://github.com/rust-lang/rfcs/issues/372");
+                err.note(
+                    "destructuring assignments can only use `=`, not compound assignment operators",
+                );
             }
             err.emit();
         }
@@ -1000,6 +1004,22 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         self.tcx.mk_array(element_ty, args.len() as u64)
     }
 
+    fn check_expr_const_block(&self, anon_const: &'tcx hir::AnonConst) -> Ty<'tcx> {
+        let def_id = self.tcx.hir().local_def_id(anon_const.hir_id);
+        let value = ty::Const::from_anon_const(self.tcx, def_id);
+
+        // Inline `const` blocks that may depend on the generics of the enclosing item can
+        // only be evaluated once it is monomorphized, all others are evaluated right away.
+        if !value.has_param_types_or_consts() {
+            self.register_wf_obligation(
+                value.into(),
+                self.tcx.hir().span(anon_const.hir_id),
+                ObligationCauseCode::MiscObligation,
+            );
+        }
+        value.ty
+    }
+
     fn check_expr_repeat(
         &self,
         element: &'tcx hir::Expr<'tcx>,
@@ -1037,7 +1037,17 @@ fn typeck_with_fallback<'tcx>(
                     hir::TyKind::Infer => Some(AstConv::ast_ty_to_ty(&fcx, ty)),
                     _ => None,
                 })
-                .unwrap_or_else(fallback);
+                .unwrap_or_else(|| match tcx.hir().get(tcx.hir().get_parent_node(id)) {
+                    // Inline `const` blocks have no declared type, so their type is
+                    // inferred from their body alone.
+                    Node::Expr(&hir::Expr {
+                        kind: ExprKind::ConstBlock(ref anon_const), ..
+                    }) if anon_const.hir_id == id => fcx.next_ty_var(TypeVariableOrigin {
+                        kind: TypeVariableOriginKind::TypeInference,
+                        span,
+                    }),
+                    _ => fallback(),
+                });
             let expected_type = fcx.normalize_associated_types_in(body.value.span, &expected_type);
             fcx.require_type_is_sized(expected_type, body.value.span, traits::ConstSized);
 
@@ -4643,6 +4653,37 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         self.check_pat_top(&local.pat, ty, ty_span, origin_expr);
         let pat_ty = self.node_ty(local.pat.hir_id);
         self.overwrite_local_ty_if_err(local, ty, pat_ty);
+
+        if let Some(els) = local.els {
+            self.check_let_else(els);
+        }
+    }
+
+    /// Checks the `else` block of a `let...else`. It doesn't produce a value for the
+    /// binding, so it's checked without an expectation and has to be of type `!`.
+    fn check_let_else(&self, els: &'tcx hir::Block<'tcx>) {
+        // The statements after the `let...else` are only reached when the pattern
+        // matched, so the divergence of the `else` block doesn't carry over to them.
+        let previous_diverges = self.diverges.get();
+        let else_ty = self.check_block_with_expected(els, NoExpectation);
+        let cause = self.misc(els.span);
+        match self.at(&cause, self.param_env).eq(self.tcx.types.never, else_ty) {
+            Ok(ok) => self.register_infer_ok_obligations(ok),
+            Err(_) => {
+                let else_ty = self.resolve_vars_if_possible(&else_ty);
+                struct_span_err!(
+                    self.tcx.sess,
+                    els.span,
+                    E0308,
+                    "`else` clause of `let...else` does not diverge"
+                )
+                .span_label(els.span, format!("expected `!`, found `{}`", else_ty))
+                .help("try adding a diverging expression, such as `return` or `panic!(..)`")
+                .help("...or use `match` instead of `let...else`")
+                .emit();
+            }
+        }
+        self.diverges.set(previous_diverges);
     }
 
     fn overwrite_local_ty_if_err(
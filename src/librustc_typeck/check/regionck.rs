@@ -82,7 +82,7 @@ use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::PatKind;
 use rustc_infer::infer::outlives::env::OutlivesEnvironment;
 use rustc_infer::infer::{self, RegionObligation, RegionckMode};
-use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId};
+use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId, ProjectionKind};
 use rustc_middle::ty::adjustment;
 use rustc_middle::ty::{self, Ty};
 use rustc_span::Span;
@@ -644,7 +644,23 @@ impl<'a, 'tcx> RegionCtxt<'a, 'tcx> {
         let origin = infer::DataBorrowed(borrow_place.place.ty(), span);
         self.type_must_outlive(origin, borrow_place.place.ty(), borrow_region);
 
-        for pointer_ty in borrow_place.place.deref_tys() {
+        // Dereferences along the path captured by a closure are performed by
+        // the closure's parent when the upvar is captured, not by the borrow.
+        let capture_path_len = match borrow_place.place.base {
+            PlaceBase::Upvar(upvar_id) => {
+                self.typeck_results.borrow().upvar_capture_path(upvar_id).len()
+            }
+            _ => 0,
+        };
+        let projections = &borrow_place.place.projections;
+        let pointer_tys = borrow_place.place.deref_tys().take(
+            projections[capture_path_len.min(projections.len())..]
+                .iter()
+                .filter(|proj| proj.kind == ProjectionKind::Deref)
+                .count(),
+        );
+
+        for pointer_ty in pointer_tys {
             debug!(
                 "link_region(borrow_region={:?}, borrow_kind={:?}, pointer_ty={:?})",
                 borrow_region, borrow_kind, borrow_place
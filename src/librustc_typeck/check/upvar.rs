@@ -29,20 +29,33 @@
 //! `ty::InferBorrow(upvar_id)` or something like that, but this would
 //! then mean that all later passes would have to check for these figments
 //! and report an error, and it just seems like more mess in the end.)
+//!
+//! ### Capturing disjoint fields
+//!
+//! With `#![feature(capture_disjoint_fields)]`, a closure does not
+//! necessarily capture each upvar as a whole. Instead, it captures the
+//! longest path below the root variable (field accesses and dereferences of
+//! references and boxes) that is shared by every use of the variable in the
+//! closure body. For example, a closure that only uses `self.a` captures
+//! `self.a`, which leaves `self.b` free to be borrowed next to the closure.
+//! The borrow kind is then inferred relative to the captured path, see
+//! `compute_capture_paths()`.
 
 use super::FnCtxt;
 
 use crate::expr_use_visitor as euv;
-use rustc_data_structures::fx::FxIndexMap;
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::def_id::LocalDefId;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::UpvarRegion;
-use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId};
-use rustc_middle::ty::{self, Ty, TyCtxt, UpvarSubsts};
+use rustc_middle::hir::place::{PlaceBase, PlaceWithHirId, Projection, ProjectionKind};
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeFoldable, UpvarSubsts};
+use rustc_session::lint;
 use rustc_span::{Span, Symbol};
 use std::collections::hash_map::Entry;
+use std::mem;
 
 impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     pub fn closure_analyze(&self, body: &'tcx hir::Body<'tcx>) {
@@ -156,6 +169,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             current_closure_kind: ty::ClosureKind::LATTICE_BOTTOM,
             current_origin: None,
             adjust_upvar_captures: ty::UpvarCaptureMap::default(),
+            accesses: Vec::new(),
         };
         euv::ExprUseVisitor::new(
             &mut delegate,
@@ -166,6 +180,26 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         )
         .consume_body(body);
 
+        // Now that we have seen every use of the upvars, decide which place
+        // below each root variable is captured and infer how it is captured,
+        // looking only at the part of each use below the captured place.
+        let capture_paths = self.compute_capture_paths(
+            closure_hir_id,
+            closure_def_id,
+            span,
+            capture_clause,
+            &delegate.accesses,
+        );
+        for (place_with_id, access) in mem::take(&mut delegate.accesses) {
+            let capture_path_len = match place_with_id.place.base {
+                PlaceBase::Upvar(upvar_id) => capture_paths.get(&upvar_id).map_or(0, Vec::len),
+                _ => 0,
+            };
+            let place_with_id = place_below_capture_path(place_with_id, capture_path_len);
+            delegate.adjust_upvar_for_access(&place_with_id, access);
+        }
+        self.typeck_results.borrow_mut().upvar_capture_paths.extend(capture_paths);
+
         if let Some(closure_substs) = infer_kind {
             // Unify the (as yet unbound) type variable in the closure
             // substs with the kind we inferred.
@@ -214,6 +248,98 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
+    /// Decides which place below each root variable the closure captures: the
+    /// longest path that is shared by every use of the variable in the closure
+    /// body and that can be captured on its own (see `capturable_path_len`).
+    /// Upvars that are captured as a whole are not included in the result.
+    ///
+    /// Unless `capture_disjoint_fields` is enabled, every upvar is captured as
+    /// a whole. In that case we lint on the closures whose captures would be
+    /// dropped in a different order once the feature is enabled.
+    fn compute_capture_paths(
+        &self,
+        closure_hir_id: hir::HirId,
+        closure_def_id: DefId,
+        span: Span,
+        capture_clause: hir::CaptureBy,
+        accesses: &[(PlaceWithHirId<'tcx>, UpvarAccess)],
+    ) -> ty::UpvarCapturePathMap<'tcx> {
+        // For each upvar, the common prefix of all of its uses, and whether
+        // one of them moves out of it.
+        let mut uses: FxHashMap<ty::UpvarId, (&[Projection<'tcx>], bool)> = Default::default();
+        for (place_with_id, access) in accesses {
+            let upvar_id = match place_with_id.place.base {
+                PlaceBase::Upvar(upvar_id) => upvar_id,
+                _ => continue,
+            };
+            let projections = &place_with_id.place.projections[..];
+            let (path, is_moved) = uses.entry(upvar_id).or_insert((projections, false));
+            let common_len =
+                path.iter().zip(projections).take_while(|(a, b)| a.kind == b.kind).count();
+            let common_path: &[Projection<'tcx>] = *path;
+            *path = &common_path[..common_len];
+            *is_moved |= matches!(access, UpvarAccess::Consume(euv::Move));
+        }
+
+        let mut capture_paths = ty::UpvarCapturePathMap::default();
+        let upvars = match self.tcx.upvars_mentioned(closure_def_id) {
+            Some(upvars) => upvars,
+            None => return capture_paths,
+        };
+        for &var_hir_id in upvars.keys() {
+            let upvar_id = ty::UpvarId {
+                var_path: ty::UpvarPath { hir_id: var_hir_id },
+                closure_expr_id: closure_def_id.expect_local(),
+            };
+            let (path, by_value) = match uses.get(&upvar_id) {
+                Some(&(path, is_moved)) => {
+                    (path, is_moved || capture_clause == hir::CaptureBy::Value)
+                }
+                None => continue,
+            };
+            let var_ty = self.resolve_vars_if_possible(&self.node_ty(var_hir_id));
+            let path = &path[..capturable_path_len(self.tcx, var_ty, path, by_value)];
+            debug!("compute_capture_paths: upvar_id={:?} path={:?}", upvar_id, path);
+            if path.is_empty() {
+                continue;
+            }
+
+            if self.tcx.features().capture_disjoint_fields {
+                capture_paths.insert(upvar_id, path.to_vec());
+            } else if by_value
+                && !var_ty.has_infer_types_or_consts()
+                && uncaptured_parts_need_drop(self.tcx, self.param_env, var_ty, path)
+            {
+                // The parts of the variable that are not captured would be
+                // dropped at the end of the enclosing scope instead of
+                // together with the closure.
+                let name = var_name(self.tcx, var_hir_id);
+                self.tcx.struct_span_lint_hir(
+                    lint::builtin::DISJOINT_CAPTURE_DROP_REORDER,
+                    closure_hir_id,
+                    span,
+                    |lint| {
+                        lint.build(&format!(
+                            "drop order of `{}` will change when closures capture disjoint fields",
+                            name
+                        ))
+                        .note(&format!(
+                            "only part of `{}` would be moved into the closure, the rest of it \
+                             would be dropped at the end of the enclosing scope",
+                            name
+                        ))
+                        .help(&format!(
+                            "add `let _ = &{};` to the closure body to keep capturing `{}` as a whole",
+                            name, name
+                        ))
+                        .emit()
+                    },
+                );
+            }
+        }
+        capture_paths
+    }
+
     // Returns a list of `Ty`s for each upvar.
     fn final_upvar_tys(&self, closure_id: hir::HirId) -> Vec<Ty<'tcx>> {
         // Presently an unboxed closure type cannot "escape" out of a
@@ -228,12 +354,18 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             .iter()
             .flat_map(|upvars| {
                 upvars.iter().map(|(&var_hir_id, _)| {
-                    let upvar_ty = self.node_ty(var_hir_id);
                     let upvar_id = ty::UpvarId {
                         var_path: ty::UpvarPath { hir_id: var_hir_id },
                         closure_expr_id: closure_def_id,
                     };
-                    let capture = self.typeck_results.borrow().upvar_capture(upvar_id);
+                    let typeck_results = self.typeck_results.borrow();
+                    let capture = typeck_results.upvar_capture(upvar_id);
+                    // The type of the captured place, which may be below the
+                    // root variable.
+                    let upvar_ty = match typeck_results.upvar_capture_path(upvar_id).last() {
+                        Some(projection) => projection.ty,
+                        None => self.node_ty(var_hir_id),
+                    };
 
                     debug!("var_id={:?} upvar_ty={:?} capture={:?}", var_hir_id, upvar_ty, capture);
 
@@ -271,9 +403,48 @@ struct InferBorrowKind<'a, 'tcx> {
     // For each upvar that we access, we track the minimal kind of
     // access we need (ref, ref mut, move, etc).
     adjust_upvar_captures: ty::UpvarCaptureMap<'tcx>,
+
+    // Every use of a place based on an upvar, in the order in which they
+    // were encountered. They are only processed once the captured place of
+    // each upvar is known.
+    accesses: Vec<(PlaceWithHirId<'tcx>, UpvarAccess)>,
+}
+
+/// A use of a place based on an upvar, as reported by the `ExprUseVisitor`.
+#[derive(Clone, Copy, Debug)]
+enum UpvarAccess {
+    Consume(euv::ConsumeMode),
+    Borrow(ty::BorrowKind),
+    Mutate,
+    FakeRead,
 }
 
 impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
+    fn record_access(&mut self, place_with_id: &PlaceWithHirId<'tcx>, access: UpvarAccess) {
+        if let PlaceBase::Upvar(_) = place_with_id.place.base {
+            self.accesses.push((place_with_id.clone(), access));
+        }
+    }
+
+    fn adjust_upvar_for_access(
+        &mut self,
+        place_with_id: &PlaceWithHirId<'tcx>,
+        access: UpvarAccess,
+    ) {
+        match access {
+            UpvarAccess::Consume(mode) => {
+                self.adjust_upvar_borrow_kind_for_consume(place_with_id, mode);
+            }
+            UpvarAccess::Borrow(ty::ImmBorrow) | UpvarAccess::FakeRead => {}
+            UpvarAccess::Borrow(ty::UniqueImmBorrow) => {
+                self.adjust_upvar_borrow_kind_for_unique(place_with_id);
+            }
+            UpvarAccess::Borrow(ty::MutBorrow) | UpvarAccess::Mutate => {
+                self.adjust_upvar_borrow_kind_for_mut(place_with_id);
+            }
+        }
+    }
+
     fn adjust_upvar_borrow_kind_for_consume(
         &mut self,
         place_with_id: &PlaceWithHirId<'tcx>,
@@ -500,28 +671,101 @@ impl<'a, 'tcx> InferBorrowKind<'a, 'tcx> {
 impl<'a, 'tcx> euv::Delegate<'tcx> for InferBorrowKind<'a, 'tcx> {
     fn consume(&mut self, place_with_id: &PlaceWithHirId<'tcx>, mode: euv::ConsumeMode) {
         debug!("consume(place_with_id={:?},mode={:?})", place_with_id, mode);
-        self.adjust_upvar_borrow_kind_for_consume(place_with_id, mode);
+        self.record_access(place_with_id, UpvarAccess::Consume(mode));
     }
 
     fn borrow(&mut self, place_with_id: &PlaceWithHirId<'tcx>, bk: ty::BorrowKind) {
         debug!("borrow(place_with_id={:?}, bk={:?})", place_with_id, bk);
-
-        match bk {
-            ty::ImmBorrow => {}
-            ty::UniqueImmBorrow => {
-                self.adjust_upvar_borrow_kind_for_unique(place_with_id);
-            }
-            ty::MutBorrow => {
-                self.adjust_upvar_borrow_kind_for_mut(place_with_id);
-            }
-        }
+        self.record_access(place_with_id, UpvarAccess::Borrow(bk));
     }
 
     fn mutate(&mut self, assignee_place: &PlaceWithHirId<'tcx>) {
         debug!("mutate(assignee_place={:?})", assignee_place);
+        self.record_access(assignee_place, UpvarAccess::Mutate);
+    }
+
+    fn fake_read(&mut self, place_with_id: &PlaceWithHirId<'tcx>) {
+        debug!("fake_read(place_with_id={:?})", place_with_id);
+        self.record_access(place_with_id, UpvarAccess::FakeRead);
+    }
+}
 
-        self.adjust_upvar_borrow_kind_for_mut(assignee_place);
+/// Returns how many projections of `path`, starting from a root variable of
+/// type `base_ty`, can be captured by a closure. We stop before indexing,
+/// before dereferencing anything but references and boxes, and before
+/// accessing fields of enums, unions and packed structs. Moving a place into
+/// the closure additionally stops before any dereference and before fields of
+/// types that implement `Drop`, which cannot be moved out of.
+fn capturable_path_len<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    base_ty: Ty<'tcx>,
+    path: &[Projection<'tcx>],
+    by_value: bool,
+) -> usize {
+    let mut ty = base_ty;
+    for (index, projection) in path.iter().enumerate() {
+        let capturable = match projection.kind {
+            ProjectionKind::Deref => !by_value && (ty.is_region_ptr() || ty.is_box()),
+            ProjectionKind::Field(..) => match ty.kind {
+                ty::Tuple(_) => true,
+                ty::Adt(def, _) => {
+                    def.is_struct() && !def.repr.packed() && !(by_value && def.has_dtor(tcx))
+                }
+                _ => false,
+            },
+            ProjectionKind::Index | ProjectionKind::Subslice => false,
+        };
+        if !capturable {
+            return index;
+        }
+        ty = projection.ty;
     }
+    path.len()
+}
+
+/// Whether the parts of a variable of type `base_ty` that are not below `path`,
+/// which the closure would no longer capture, need to be dropped: that is, the
+/// fields next to the ones `path` goes through.
+fn uncaptured_parts_need_drop<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    base_ty: Ty<'tcx>,
+    path: &[Projection<'tcx>],
+) -> bool {
+    let mut ty = base_ty;
+    for projection in path {
+        if let ProjectionKind::Field(captured, _) = projection.kind {
+            let is_dropped_sibling = |(index, field_ty): (usize, Ty<'tcx>)| {
+                index != captured as usize && field_ty.needs_drop(tcx, param_env)
+            };
+            let siblings_need_drop = match ty.kind {
+                ty::Tuple(substs) => substs.types().enumerate().any(is_dropped_sibling),
+                ty::Adt(def, substs) => {
+                    let fields = def.non_enum_variant().fields.iter();
+                    fields.map(|field| field.ty(tcx, substs)).enumerate().any(is_dropped_sibling)
+                }
+                _ => false,
+            };
+            if siblings_need_drop {
+                return true;
+            }
+        }
+        ty = projection.ty;
+    }
+    false
+}
+
+/// Rebases a place on an upvar onto the place that the closure captures for
+/// that upvar, dropping the first `capture_path_len` projections.
+fn place_below_capture_path<'tcx>(
+    place_with_id: PlaceWithHirId<'tcx>,
+    capture_path_len: usize,
+) -> PlaceWithHirId<'tcx> {
+    let PlaceWithHirId { hir_id, place } = place_with_id;
+    let mut projections = place.projections;
+    let base_ty = projections[..capture_path_len].last().map_or(place.base_ty, |proj| proj.ty);
+    projections.drain(..capture_path_len);
+    PlaceWithHirId::new(hir_id, base_ty, place.base, projections)
 }
 
 fn var_name(tcx: TyCtxt<'_>, var_hir_id: hir::HirId) -> Symbol {
@@ -9,6 +9,7 @@ use rustc_hir as hir;
 use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::error_reporting::TypeAnnotationNeeded::E0282;
 use rustc_infer::infer::InferCtxt;
+use rustc_middle::hir::place::Projection;
 use rustc_middle::ty::adjustment::{Adjust, Adjustment, PointerCast};
 use rustc_middle::ty::fold::{TypeFoldable, TypeFolder};
 use rustc_middle::ty::{self, Ty, TyCtxt};
@@ -342,6 +343,19 @@ impl<'cx, 'tcx> WritebackCx<'cx, 'tcx> {
             debug!("Upvar capture for {:?} resolved to {:?}", upvar_id, new_upvar_capture);
             self.typeck_results.upvar_capture_map.insert(*upvar_id, new_upvar_capture);
         }
+
+        for (upvar_id, path) in self.fcx.typeck_results.borrow().upvar_capture_paths.iter() {
+            let var_hir_id = upvar_id.var_path.hir_id;
+            let path = path
+                .iter()
+                .map(|projection| Projection {
+                    ty: self.resolve(&projection.ty, &var_hir_id),
+                    kind: projection.kind,
+                })
+                .collect();
+            debug!("Upvar capture path for {:?} resolved to {:?}", upvar_id, path);
+            self.typeck_results.upvar_capture_paths.insert(*upvar_id, path);
+        }
     }
 
     fn visit_closures(&mut self) {
@@ -35,6 +35,11 @@ pub trait Delegate<'tcx> {
 
     // The path at `place_with_id` is being assigned to.
     fn mutate(&mut self, assignee_place: &PlaceWithHirId<'tcx>);
+
+    // The place at `place_with_id` is evaluated without its value being
+    // used as a whole, e.g. the initializer of a `let` or the base of a
+    // functional record update. Any use of its value is reported separately.
+    fn fake_read(&mut self, _place_with_id: &PlaceWithHirId<'tcx>) {}
 }
 
 #[derive(Copy, Clone, PartialEq, Debug)]
@@ -258,7 +263,10 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
                 self.consume_exprs(&ia.inputs_exprs);
             }
 
-            hir::ExprKind::Continue(..) | hir::ExprKind::Lit(..) | hir::ExprKind::Err => {}
+            hir::ExprKind::Continue(..)
+            | hir::ExprKind::Lit(..)
+            | hir::ExprKind::ConstBlock(..)
+            | hir::ExprKind::Err => {}
 
             hir::ExprKind::Loop(ref blk, _, _) => {
                 self.walk_block(blk);
@@ -348,7 +356,11 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
             // `walk_pat`:
             self.walk_expr(&expr);
             let init_place = return_if_err!(self.mc.cat_expr(&expr));
+            self.delegate.fake_read(&init_place);
             self.walk_irrefutable_pat(&init_place, &local.pat);
+            if let Some(els) = local.els {
+                self.walk_block(els);
+            }
         }
     }
 
@@ -384,6 +396,7 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
         };
 
         let with_place = return_if_err!(self.mc.cat_expr(&with_expr));
+        self.delegate.fake_read(&with_place);
 
         // Select just those fields of the `with`
         // expression that will actually be used
@@ -553,11 +566,16 @@ impl<'a, 'tcx> ExprUseVisitor<'a, 'tcx> {
                     closure_expr_id: closure_def_id,
                 };
                 let upvar_capture = self.mc.typeck_results.upvar_capture(upvar_id);
-                let captured_place = return_if_err!(self.cat_captured_var(
+                let mut captured_place = return_if_err!(self.cat_captured_var(
                     closure_expr.hir_id,
                     fn_decl_span,
                     var_id,
                 ));
+                // The closure may only capture a path below the variable.
+                captured_place
+                    .place
+                    .projections
+                    .extend_from_slice(self.mc.typeck_results.upvar_capture_path(upvar_id));
                 match upvar_capture {
                     ty::UpvarCapture::ByValue(_) => {
                         let mode = copy_or_move(&self.mc, &captured_place);
@@ -8,6 +8,7 @@ use std::mem;
 use std::ops;
 
 use rustc_ast::{LitKind, MetaItem, MetaItemKind, NestedMetaItem};
+use rustc_data_structures::fx::FxHashSet;
 use rustc_feature::Features;
 use rustc_session::parse::ParseSess;
 use rustc_span::symbol::{sym, Symbol};
@@ -117,6 +118,61 @@ impl Cfg {
         }
     }
 
+    /// Removes the configuration options in `hidden` from this configuration, like the ones hidden
+    /// with `#![doc(cfg_hide(...))]`.
+    ///
+    /// Returns `None` if nothing is left.
+    pub(crate) fn strip_hidden(&self, hidden: &FxHashSet<Cfg>) -> Option<Cfg> {
+        match *self {
+            Cfg::False | Cfg::True => Some(self.clone()),
+            Cfg::Cfg(..) => {
+                if hidden.contains(self) {
+                    None
+                } else {
+                    Some(self.clone())
+                }
+            }
+            Cfg::Not(ref child) => child.strip_hidden(hidden).map(|child| !child),
+            Cfg::Any(ref sub_cfgs) => {
+                let mut sub_cfgs =
+                    sub_cfgs.iter().filter_map(|c| c.strip_hidden(hidden)).peekable();
+                sub_cfgs.peek()?;
+                Some(sub_cfgs.fold(Cfg::False, |x, y| x | y))
+            }
+            Cfg::All(ref sub_cfgs) => {
+                let mut sub_cfgs =
+                    sub_cfgs.iter().filter_map(|c| c.strip_hidden(hidden)).peekable();
+                sub_cfgs.peek()?;
+                Some(sub_cfgs.fold(Cfg::True, |x, y| x & y))
+            }
+        }
+    }
+
+    /// Simplifies the configuration by absorption: `all(a, any(a, b))` becomes `a`, and
+    /// `any(a, all(a, b))` becomes `a` as well.
+    pub(crate) fn simplify(self) -> Cfg {
+        match self {
+            Cfg::Not(child) => !child.simplify(),
+            Cfg::All(sub_cfgs) => {
+                let sub_cfgs = sub_cfgs.into_iter().map(Cfg::simplify).collect::<Vec<_>>();
+                let absorbed = |c: &Cfg| match *c {
+                    Cfg::Any(ref any) => any.iter().any(|a| sub_cfgs.contains(a)),
+                    _ => false,
+                };
+                sub_cfgs.iter().filter(|c| !absorbed(c)).cloned().fold(Cfg::True, |x, y| x & y)
+            }
+            Cfg::Any(sub_cfgs) => {
+                let sub_cfgs = sub_cfgs.into_iter().map(Cfg::simplify).collect::<Vec<_>>();
+                let absorbed = |c: &Cfg| match *c {
+                    Cfg::All(ref all) => all.iter().any(|a| sub_cfgs.contains(a)),
+                    _ => false,
+                };
+                sub_cfgs.iter().filter(|c| !absorbed(c)).cloned().fold(Cfg::False, |x, y| x | y)
+            }
+            cfg => cfg,
+        }
+    }
+
     /// Whether the configuration consists of just `Cfg` or `Not`.
     fn is_simple(&self) -> bool {
         match *self {
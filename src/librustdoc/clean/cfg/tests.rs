@@ -236,6 +236,54 @@ fn test_cfg_or() {
     })
 }
 
+#[test]
+fn test_strip_hidden() {
+    with_default_session_globals(|| {
+        let mut hidden = FxHashSet::default();
+        hidden.insert(word_cfg("doc"));
+        hidden.insert(name_value_cfg("feature", "internal"));
+
+        assert_eq!(word_cfg("doc").strip_hidden(&hidden), None);
+        assert_eq!(word_cfg("unix").strip_hidden(&hidden), Some(word_cfg("unix")));
+        assert_eq!(
+            name_value_cfg("feature", "sync").strip_hidden(&hidden),
+            Some(name_value_cfg("feature", "sync"))
+        );
+        assert_eq!((!word_cfg("doc")).strip_hidden(&hidden), None);
+        assert_eq!(
+            (word_cfg("doc") | word_cfg("unix")).strip_hidden(&hidden),
+            Some(word_cfg("unix"))
+        );
+        assert_eq!(
+            (word_cfg("unix") & name_value_cfg("feature", "internal") & word_cfg("b"))
+                .strip_hidden(&hidden),
+            Some(Cfg::All(vec![word_cfg("unix"), word_cfg("b")]))
+        );
+        assert_eq!(
+            (word_cfg("doc") & name_value_cfg("feature", "internal")).strip_hidden(&hidden),
+            None
+        );
+    })
+}
+
+#[test]
+fn test_simplify() {
+    with_default_session_globals(|| {
+        assert_eq!(word_cfg("a").simplify(), word_cfg("a"));
+        assert_eq!((word_cfg("a") & (word_cfg("a") | word_cfg("b"))).simplify(), word_cfg("a"));
+        assert_eq!((word_cfg("a") | (word_cfg("a") & word_cfg("b"))).simplify(), word_cfg("a"));
+        assert_eq!(
+            (word_cfg("a") & word_cfg("c") & (word_cfg("b") | word_cfg("c"))).simplify(),
+            Cfg::All(vec![word_cfg("a"), word_cfg("c")])
+        );
+        assert_eq!(
+            (word_cfg("a") & (word_cfg("b") | word_cfg("c"))).simplify(),
+            Cfg::All(vec![word_cfg("a"), Cfg::Any(vec![word_cfg("b"), word_cfg("c")])])
+        );
+        assert_eq!((!(word_cfg("a") & (word_cfg("a") | word_cfg("b")))).simplify(), !word_cfg("a"));
+    })
+}
+
 #[test]
 fn test_parse_ok() {
     with_default_session_globals(|| {
@@ -0,0 +1,622 @@
+//! Rustdoc's Markdown backend.
+//!
+//! This writes the documentation as a tree of CommonMark files laid out like the HTML output: a
+//! `index.md` for each module and a `<type>.<name>.md` file for each other item. The intra-doc
+//! links of the documentation become relative links between these files.
+
+use std::fs;
+use std::path::PathBuf;
+
+use rustc_hir as hir;
+use rustc_span::edition::Edition;
+use rustc_span::hygiene::MacroKind;
+
+use crate::clean;
+use crate::config::{RenderInfo, RenderOptions};
+use crate::docfs::PathError;
+use crate::doctree::StructType;
+use crate::error::Error;
+use crate::formats::cache::Cache;
+use crate::formats::item_type::ItemType;
+use crate::formats::{FormatRenderer, Impl};
+use crate::html::format::{
+    print_abi_with_space, print_generic_bounds, Buffer, PrintWithSpace, WhereClause,
+};
+use crate::html::markdown::rewrite_for_commonmark;
+use crate::html::render::{item_ty_to_strs, plain_summary_line, CURRENT_DEPTH};
+
+/// The order of the sections of a module page.
+const ITEM_TYPE_ORDER: &[ItemType] = &[
+    ItemType::Import,
+    ItemType::Primitive,
+    ItemType::Module,
+    ItemType::Macro,
+    ItemType::Struct,
+    ItemType::Enum,
+    ItemType::Constant,
+    ItemType::Static,
+    ItemType::Trait,
+    ItemType::Function,
+    ItemType::Typedef,
+    ItemType::Union,
+    ItemType::ForeignType,
+    ItemType::Keyword,
+    ItemType::OpaqueTy,
+    ItemType::ProcAttribute,
+    ItemType::ProcDerive,
+    ItemType::TraitAlias,
+];
+
+#[derive(Clone)]
+crate struct MarkdownRenderer {
+    /// Current hierarchy of components leading down to what's currently being rendered.
+    current: Vec<String>,
+    /// The directory of the module currently being rendered.
+    dst: PathBuf,
+    /// Whether the current module is stripped. Its items stay undocumented, like the ones that
+    /// only get redirect pages in the HTML output.
+    stripped: bool,
+}
+
+impl FormatRenderer for MarkdownRenderer {
+    fn init(
+        krate: clean::Crate,
+        options: RenderOptions,
+        _render_info: RenderInfo,
+        _edition: Edition,
+        _cache: &mut Cache,
+    ) -> Result<(Self, clean::Crate), Error> {
+        try_err!(fs::create_dir_all(&options.output), &options.output);
+        Ok((MarkdownRenderer { current: Vec::new(), dst: options.output, stripped: false }, krate))
+    }
+
+    fn item(&mut self, item: clean::Item, cache: &Cache) -> Result<(), Error> {
+        if self.stripped || item.is_stripped() {
+            return Ok(());
+        }
+        let item_type = item.type_();
+        if item_type == ItemType::ExternCrate || item_type == ItemType::Import {
+            return Ok(());
+        }
+
+        let mut buf = Buffer::new();
+        self.render_item(&mut buf, &item, cache);
+        let path = self.dst.join(format!("{}.{}.md", item_type, item.name.as_ref().unwrap()));
+        try_err!(fs::create_dir_all(&self.dst), &self.dst);
+        try_err!(fs::write(&path, buf.into_inner()), &path);
+        Ok(())
+    }
+
+    fn mod_item_in(
+        &mut self,
+        item: &clean::Item,
+        item_name: &str,
+        _cache: &Cache,
+    ) -> Result<(), Error> {
+        self.stripped |= item.is_stripped();
+        self.dst.push(item_name);
+        self.current.push(item_name.to_owned());
+        if self.stripped {
+            return Ok(());
+        }
+
+        let module = match item.inner {
+            clean::ModuleItem(ref m) => m,
+            _ => unreachable!(),
+        };
+        let mut buf = Buffer::new();
+        self.render_module(&mut buf, item, module);
+        let path = self.dst.join("index.md");
+        try_err!(fs::create_dir_all(&self.dst), &self.dst);
+        try_err!(fs::write(&path, buf.into_inner()), &path);
+        Ok(())
+    }
+
+    fn mod_item_out(&mut self, _item_name: &str) -> Result<(), Error> {
+        self.dst.pop();
+        self.current.pop();
+        Ok(())
+    }
+
+    fn after_krate(&mut self, _krate: &clean::Crate, _cache: &Cache) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn after_run(&mut self, _diag: &rustc_errors::Handler) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl MarkdownRenderer {
+    fn render_module(&self, w: &mut Buffer, item: &clean::Item, module: &clean::Module) {
+        // The links of a page are relative to its directory.
+        CURRENT_DEPTH.with(|slot| slot.set(self.current.len()));
+
+        let kind = if module.is_crate { "Crate" } else { "Module" };
+        write!(w, "# {} `{}`\n\n", kind, self.current.join("::"));
+        document(w, item, 1);
+
+        for &item_type in ITEM_TYPE_ORDER {
+            let mut items = module
+                .items
+                .iter()
+                .filter(|i| !i.is_stripped() && i.type_() == item_type)
+                .collect::<Vec<_>>();
+            if items.is_empty() {
+                continue;
+            }
+            items.sort_by(|a, b| a.name.cmp(&b.name));
+
+            write!(w, "## {}\n\n", item_ty_to_strs(&item_type).1);
+            for i in items {
+                match i.inner {
+                    clean::ImportItem(ref import) => {
+                        writeln!(
+                            w,
+                            "- `{:#}{:#}`",
+                            i.visibility.print_with_space(),
+                            import.print()
+                        );
+                    }
+                    _ => {
+                        let name = i.name.as_ref().unwrap();
+                        let href = if item_type == ItemType::Module {
+                            format!("{}/index.md", name)
+                        } else {
+                            format!("{}.{}.md", item_type, name)
+                        };
+                        write!(w, "- [`{}`]({})", name, href);
+                        let summary = plain_summary_line(i.doc_value());
+                        if !summary.is_empty() {
+                            write!(w, ": {}", summary);
+                        }
+                        w.write_str("\n");
+                    }
+                }
+            }
+            w.write_str("\n");
+        }
+    }
+
+    fn render_item(&self, w: &mut Buffer, item: &clean::Item, cache: &Cache) {
+        CURRENT_DEPTH.with(|slot| slot.set(self.current.len()));
+
+        let name = item.name.as_ref().unwrap();
+        write!(w, "# {} `{}::{}`\n\n", item_kind(item), self.current.join("::"), name);
+        if let Some(ref deprecation) = item.deprecation {
+            let since = deprecation.since.as_ref().map(|s| format!(" since {}", s));
+            write!(w, "> **Deprecated{}**", since.unwrap_or_default());
+            if let Some(ref note) = deprecation.note {
+                write!(w, ": {}", note);
+            }
+            w.write_str("\n\n");
+        }
+
+        w.write_str("```rust\n");
+        declaration(w, item, name);
+        w.write_str("```\n\n");
+        document(w, item, 1);
+
+        match item.inner {
+            clean::StructItem(ref s) => render_fields(w, &s.fields),
+            clean::UnionItem(ref u) => render_fields(w, &u.fields),
+            clean::EnumItem(ref e) => {
+                let variants = e.variants.iter().filter(|v| !v.is_stripped()).collect::<Vec<_>>();
+                if !variants.is_empty() {
+                    w.write_str("## Variants\n\n");
+                }
+                for variant in variants {
+                    let variant_name = variant.name.as_ref().unwrap();
+                    write!(w, "<a id=\"variant.{}\"></a>\n\n", variant_name);
+                    if let clean::VariantItem(ref v) = variant.inner {
+                        write!(w, "### `{}`\n\n", variant_signature(variant_name, v));
+                    }
+                    document(w, variant, 3);
+                }
+            }
+            clean::TraitItem(ref t) => render_trait_items(w, item, t, cache),
+            _ => {}
+        }
+
+        match item.inner {
+            clean::StructItem(..)
+            | clean::UnionItem(..)
+            | clean::EnumItem(..)
+            | clean::TypedefItem(..)
+            | clean::ForeignTypeItem
+            | clean::PrimitiveItem(..) => {
+                if let Some(impls) = cache.impls.get(&item.def_id) {
+                    render_impls(w, impls);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes the documentation of `item`, with its headings moved `heading_offset` levels down.
+fn document(w: &mut Buffer, item: &clean::Item, heading_offset: u32) {
+    let doc = match item.collapsed_doc_value() {
+        Some(doc) => doc,
+        None => return,
+    };
+    let links =
+        item.links().into_iter().map(|(s, href)| (s, markdown_href(&href))).collect::<Vec<_>>();
+    let doc = rewrite_for_commonmark(&doc, &links, heading_offset);
+    let doc = doc.trim();
+    if !doc.is_empty() {
+        write!(w, "{}\n\n", doc);
+    }
+}
+
+fn render_fields(w: &mut Buffer, fields: &[clean::Item]) {
+    let fields = fields
+        .iter()
+        .filter_map(|f| match (&f.name, &f.inner) {
+            (Some(name), clean::StructFieldItem(ty)) => Some((f, name, ty)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return;
+    }
+    w.write_str("## Fields\n\n");
+    for (field, name, ty) in fields {
+        write!(w, "<a id=\"structfield.{}\"></a>\n\n", name);
+        write!(w, "### `{}: {:#}`\n\n", name, ty.print());
+        document(w, field, 3);
+    }
+}
+
+fn render_trait_items(w: &mut Buffer, item: &clean::Item, t: &clean::Trait, cache: &Cache) {
+    let sections: &[(&str, fn(&clean::Item) -> bool)] = &[
+        ("Associated Types", |i| i.type_() == ItemType::AssocType),
+        ("Associated Constants", |i| i.type_() == ItemType::AssocConst),
+        ("Required Methods", |i| i.type_() == ItemType::TyMethod),
+        ("Provided Methods", |i| i.type_() == ItemType::Method),
+    ];
+    for &(title, filter) in sections {
+        let items = t.items.iter().filter(|i| filter(i)).collect::<Vec<_>>();
+        if items.is_empty() {
+            continue;
+        }
+        write!(w, "## {}\n\n", title);
+        for i in items {
+            render_assoc_item(w, i, "###", 3);
+        }
+    }
+
+    if let Some(implementors) = cache.implementors.get(&item.def_id) {
+        w.write_str("## Implementors\n\n");
+        for implementor in implementors {
+            writeln!(w, "- `{:#}`", implementor.inner_impl().print());
+        }
+        w.write_str("\n");
+    }
+}
+
+/// Turns a link to a page of the HTML output into a link to the matching Markdown file.
+fn markdown_href(href: &str) -> String {
+    if href.contains("://") {
+        return href.to_string();
+    }
+    let (path, fragment) = href.split_at(href.find('#').unwrap_or_else(|| href.len()));
+    match path.strip_suffix(".html") {
+        Some(path) => format!("{}.md{}", path, fragment),
+        None => href.to_string(),
+    }
+}
+
+fn render_impls(w: &mut Buffer, impls: &[Impl]) {
+    let (inherent, traits): (Vec<&Impl>, Vec<&Impl>) =
+        impls.iter().partition(|i| i.inner_impl().trait_.is_none());
+
+    if !inherent.is_empty() {
+        w.write_str("## Implementations\n\n");
+        for i in inherent {
+            write!(w, "### `{:#}`\n\n", i.inner_impl().print());
+            document(w, &i.impl_item, 3);
+            for assoc in &i.inner_impl().items {
+                render_assoc_item(w, assoc, "####", 4);
+            }
+        }
+    }
+
+    let sections: &[(&str, fn(&clean::Impl) -> bool)] = &[
+        ("Trait Implementations", |i| !i.synthetic && i.blanket_impl.is_none()),
+        ("Auto Trait Implementations", |i| i.synthetic),
+        ("Blanket Implementations", |i| i.blanket_impl.is_some()),
+    ];
+    for &(title, filter) in sections {
+        let impls = traits.iter().filter(|i| filter(i.inner_impl())).collect::<Vec<_>>();
+        if impls.is_empty() {
+            continue;
+        }
+        write!(w, "## {}\n\n", title);
+        for i in impls {
+            writeln!(w, "- `{:#}`", i.inner_impl().print());
+        }
+        w.write_str("\n");
+    }
+}
+
+/// Writes an associated item under a heading of level `heading`, with an anchor matching the one
+/// of the HTML output.
+fn render_assoc_item(w: &mut Buffer, item: &clean::Item, heading: &str, heading_offset: u32) {
+    let name = match item.name {
+        Some(ref name) => name,
+        None => return,
+    };
+    let signature = match item.inner {
+        clean::MethodItem(ref m) => format!(
+            "{:#}{}",
+            item.visibility.print_with_space(),
+            function_signature(name, &m.header, &m.generics, &m.decl)
+        ),
+        clean::TyMethodItem(ref m) => function_signature(name, &m.header, &m.generics, &m.decl),
+        clean::AssocConstItem(ref ty, _) => format!("const {}: {:#}", name, ty.print()),
+        clean::AssocTypeItem(ref bounds, _) => format!(
+            "type {}{}{:#}",
+            name,
+            if bounds.is_empty() { "" } else { ": " },
+            print_generic_bounds(bounds)
+        ),
+        clean::TypedefItem(ref t, true) => format!("type {} = {:#}", name, t.type_.print()),
+        _ => return,
+    };
+    write!(w, "<a id=\"{}.{}\"></a>\n\n", item.type_(), name);
+    write!(w, "{} `{}`\n\n", heading, signature);
+    document(w, item, heading_offset);
+}
+
+/// Returns the word the title of the page of `item` starts with.
+fn item_kind(item: &clean::Item) -> &'static str {
+    match item.inner {
+        clean::FunctionItem(..) | clean::ForeignFunctionItem(..) => "Function",
+        clean::TraitItem(..) => "Trait",
+        clean::StructItem(..) => "Struct",
+        clean::UnionItem(..) => "Union",
+        clean::EnumItem(..) => "Enum",
+        clean::TypedefItem(..) => "Type Definition",
+        clean::MacroItem(..) => "Macro",
+        clean::ProcMacroItem(ref mac) => match mac.kind {
+            MacroKind::Bang => "Macro",
+            MacroKind::Attr => "Attribute Macro",
+            MacroKind::Derive => "Derive Macro",
+        },
+        clean::PrimitiveItem(..) => "Primitive Type",
+        clean::StaticItem(..) | clean::ForeignStaticItem(..) => "Static",
+        clean::ConstantItem(..) => "Constant",
+        clean::ForeignTypeItem => "Foreign Type",
+        clean::KeywordItem(..) => "Keyword",
+        clean::OpaqueTyItem(..) => "Opaque Type",
+        clean::TraitAliasItem(..) => "Trait Alias",
+        _ => "Item",
+    }
+}
+
+/// Writes the declaration of `item` as plain Rust code.
+fn declaration(w: &mut Buffer, item: &clean::Item, name: &str) {
+    let vis = item.visibility.print_with_space();
+    match item.inner {
+        clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+            writeln!(w, "{:#}{}", vis, function_signature(name, &f.header, &f.generics, &f.decl));
+        }
+        clean::StructItem(ref s) => {
+            write!(w, "{:#}struct {}{:#}", vis, name, s.generics.print());
+            fields_declaration(w, s.struct_type, &s.generics, &s.fields, s.fields_stripped);
+        }
+        clean::UnionItem(ref u) => {
+            write!(w, "{:#}union {}{:#}", vis, name, u.generics.print());
+            fields_declaration(w, u.struct_type, &u.generics, &u.fields, u.fields_stripped);
+        }
+        clean::EnumItem(ref e) => {
+            writeln!(
+                w,
+                "{:#}enum {}{:#}{:#} {{",
+                vis,
+                name,
+                e.generics.print(),
+                WhereClause { gens: &e.generics, indent: 0, end_newline: false }
+            );
+            for variant in e.variants.iter().filter(|v| !v.is_stripped()) {
+                if let (Some(name), clean::VariantItem(ref v)) = (&variant.name, &variant.inner) {
+                    writeln!(w, "    {},", variant_signature(name, v));
+                }
+            }
+            if e.variants_stripped {
+                w.write_str("    // some variants omitted\n");
+            }
+            w.write_str("}\n");
+        }
+        clean::TypedefItem(ref t, _) => writeln!(
+            w,
+            "{:#}type {}{:#}{:#} = {:#};",
+            vis,
+            name,
+            t.generics.print(),
+            WhereClause { gens: &t.generics, indent: 0, end_newline: false },
+            t.type_.print()
+        ),
+        clean::OpaqueTyItem(ref t, _) => writeln!(
+            w,
+            "{:#}type {}{:#} = impl {:#};",
+            vis,
+            name,
+            t.generics.print(),
+            print_generic_bounds(&t.bounds)
+        ),
+        clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => writeln!(
+            w,
+            "{:#}static {}{}: {:#};",
+            vis,
+            s.mutability.print_with_space(),
+            name,
+            s.type_.print()
+        ),
+        clean::ConstantItem(ref c) => {
+            writeln!(w, "{:#}const {}: {:#} = {};", vis, name, c.type_.print(), c.expr)
+        }
+        clean::TraitItem(ref t) => {
+            writeln!(
+                w,
+                "{:#}{}{}trait {}{:#}{}{:#}{:#} {{",
+                vis,
+                t.unsafety.print_with_space(),
+                if t.is_auto { "auto " } else { "" },
+                name,
+                t.generics.print(),
+                if t.bounds.is_empty() { "" } else { ": " },
+                print_generic_bounds(&t.bounds),
+                WhereClause { gens: &t.generics, indent: 0, end_newline: false }
+            );
+            for i in &t.items {
+                let name = match i.name {
+                    Some(ref name) => name,
+                    None => continue,
+                };
+                match i.inner {
+                    clean::TyMethodItem(ref m) => writeln!(
+                        w,
+                        "    {};",
+                        function_signature(name, &m.header, &m.generics, &m.decl)
+                    ),
+                    clean::MethodItem(ref m) => writeln!(
+                        w,
+                        "    {} {{ ... }}",
+                        function_signature(name, &m.header, &m.generics, &m.decl)
+                    ),
+                    clean::AssocConstItem(ref ty, ref default) => writeln!(
+                        w,
+                        "    const {}: {:#}{};",
+                        name,
+                        ty.print(),
+                        if default.is_some() { " = ..." } else { "" }
+                    ),
+                    clean::AssocTypeItem(ref bounds, ref default) => writeln!(
+                        w,
+                        "    type {}{}{:#}{};",
+                        name,
+                        if bounds.is_empty() { "" } else { ": " },
+                        print_generic_bounds(bounds),
+                        if default.is_some() { " = ..." } else { "" }
+                    ),
+                    _ => {}
+                }
+            }
+            w.write_str("}\n");
+        }
+        clean::TraitAliasItem(ref t) => writeln!(
+            w,
+            "trait {}{:#} = {:#};",
+            name,
+            t.generics.print(),
+            print_generic_bounds(&t.bounds)
+        ),
+        clean::MacroItem(ref m) => writeln!(w, "{}", m.source.trim_end()),
+        clean::ProcMacroItem(ref m) => match m.kind {
+            MacroKind::Bang => writeln!(w, "{}!() {{ /* proc-macro */ }}", name),
+            MacroKind::Attr => writeln!(w, "#[{}]", name),
+            MacroKind::Derive => {
+                writeln!(w, "#[derive({})]", name);
+                if !m.helpers.is_empty() {
+                    writeln!(w, "// Helper attributes: {}", m.helpers.join(", "));
+                }
+            }
+        },
+        clean::ForeignTypeItem => writeln!(w, "{:#}type {};", vis, name),
+        clean::PrimitiveItem(..) => writeln!(w, "{}", name),
+        clean::KeywordItem(..) => writeln!(w, "{}", name),
+        _ => {}
+    }
+}
+
+/// Writes the fields of a struct or union, after its name and generics.
+fn fields_declaration(
+    w: &mut Buffer,
+    struct_type: StructType,
+    generics: &clean::Generics,
+    fields: &[clean::Item],
+    fields_stripped: bool,
+) {
+    let where_clause = WhereClause { gens: generics, indent: 0, end_newline: false };
+    match struct_type {
+        StructType::Plain => {
+            writeln!(w, "{:#} {{", where_clause);
+            for field in fields {
+                if let (Some(name), clean::StructFieldItem(ty)) = (&field.name, &field.inner) {
+                    writeln!(
+                        w,
+                        "    {:#}{}: {:#},",
+                        field.visibility.print_with_space(),
+                        name,
+                        ty.print()
+                    );
+                }
+            }
+            if fields_stripped {
+                w.write_str("    // some fields omitted\n");
+            }
+            w.write_str("}\n");
+        }
+        StructType::Tuple => {
+            let fields = fields
+                .iter()
+                .map(|field| match field.inner {
+                    clean::StructFieldItem(ref ty) => {
+                        format!("{:#}{:#}", field.visibility.print_with_space(), ty.print())
+                    }
+                    _ => "_".to_string(),
+                })
+                .collect::<Vec<_>>();
+            writeln!(w, "({}){:#};", fields.join(", "), where_clause);
+        }
+        StructType::Unit => writeln!(w, "{:#};", where_clause),
+    }
+}
+
+fn function_signature(
+    name: &str,
+    header: &hir::FnHeader,
+    generics: &clean::Generics,
+    decl: &clean::FnDecl,
+) -> String {
+    format!(
+        "{}{}{}{}fn {}{:#}{:#}{:#}",
+        header.constness.print_with_space(),
+        header.asyncness.print_with_space(),
+        header.unsafety.print_with_space(),
+        print_abi_with_space(header.abi),
+        name,
+        generics.print(),
+        decl.print(),
+        WhereClause { gens: generics, indent: 0, end_newline: false }
+    )
+}
+
+fn variant_signature(name: &str, variant: &clean::Variant) -> String {
+    match variant.kind {
+        clean::VariantKind::CLike => name.to_string(),
+        clean::VariantKind::Tuple(ref tys) => format!(
+            "{}({})",
+            name,
+            tys.iter().map(|ty| format!("{:#}", ty.print())).collect::<Vec<_>>().join(", ")
+        ),
+        clean::VariantKind::Struct(ref s) => {
+            let mut fields = s
+                .fields
+                .iter()
+                .filter_map(|field| match (&field.name, &field.inner) {
+                    (Some(name), clean::StructFieldItem(ty)) => {
+                        Some(format!("{}: {:#}", name, ty.print()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if s.fields_stripped {
+                fields.push("..".to_string());
+            }
+            format!("{} {{ {} }}", name, fields.join(", "))
+        }
+    }
+}
@@ -26,13 +26,16 @@ use crate::html::markdown::IdMap;
 use crate::html::render::StylePath;
 use crate::html::static_files;
 use crate::opts;
-use crate::passes::{self, Condition, DefaultPassOption};
+use crate::passes::{self, Condition, CoverageOptions, DefaultPassOption};
+use crate::scrape_examples::{self, AllCallLocations, CallSnippet, ScrapeExamplesOptions};
+use crate::semver::SemverOptions;
 use crate::theme;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum OutputFormat {
     Json,
     Html,
+    Markdown,
 }
 
 impl OutputFormat {
@@ -51,6 +54,7 @@ impl TryFrom<&str> for OutputFormat {
         match value {
             "json" => Ok(OutputFormat::Json),
             "html" => Ok(OutputFormat::Html),
+            "markdown" => Ok(OutputFormat::Markdown),
             _ => Err(format!("unknown output format `{}`", value)),
         }
     }
@@ -117,6 +121,13 @@ pub struct Options {
     /// For example, using ignore-foo to ignore running the doctest on any target that
     /// contains "foo" as a substring
     pub enable_per_target_ignores: bool,
+    /// Whether to compile the doctests that allow it into a single binary, instead of one
+    /// binary per doctest.
+    pub merge_doctests: bool,
+    /// Item paths to run the doctests of. If empty, the doctests of all items are run.
+    pub doctest_filters: Vec<String>,
+    /// Optional path to write the JSON events of the doctests to.
+    pub doctest_events: Option<PathBuf>,
 
     /// The path to a rustc-like binary to build tests with. If not set, we
     /// default to loading from $sysroot/bin/rustc.
@@ -137,6 +148,8 @@ pub struct Options {
     /// Whether to run the `calculate-doc-coverage` pass, which counts the number of public items
     /// with and without documentation.
     pub show_coverage: bool,
+    /// The options of `--show-coverage`.
+    crate coverage_options: CoverageOptions,
 
     // Options that alter generated documentation pages
     /// Crate version to note on the sidebar of generated docs.
@@ -145,6 +158,12 @@ pub struct Options {
     pub render_options: RenderOptions,
     /// Output format rendering (used only for "show-coverage" option for the moment)
     pub output_format: Option<OutputFormat>,
+    /// If set, the crate is only type-checked to find the calls it makes to the functions of other
+    /// crates, for their scraped examples.
+    crate scrape_examples_options: Option<ScrapeExamplesOptions>,
+    /// If set, the public API of the crate is saved or compared with the one of a previous
+    /// version, instead of documenting it.
+    crate semver_options: Option<SemverOptions>,
 }
 
 impl fmt::Debug for Options {
@@ -180,11 +199,17 @@ impl fmt::Debug for Options {
             .field("manual_passes", &self.manual_passes)
             .field("display_warnings", &self.display_warnings)
             .field("show_coverage", &self.show_coverage)
+            .field("coverage_options", &self.coverage_options)
             .field("crate_version", &self.crate_version)
             .field("render_options", &self.render_options)
             .field("runtool", &self.runtool)
             .field("runtool_args", &self.runtool_args)
             .field("enable-per-target-ignores", &self.enable_per_target_ignores)
+            .field("merge_doctests", &self.merge_doctests)
+            .field("doctest_filters", &self.doctest_filters)
+            .field("doctest_events", &self.doctest_events)
+            .field("scrape_examples_options", &self.scrape_examples_options)
+            .field("semver_options", &self.semver_options)
             .finish()
     }
 }
@@ -250,6 +275,13 @@ pub struct RenderOptions {
     pub document_private: bool,
     /// Document items that have `doc(hidden)`.
     pub document_hidden: bool,
+    /// If true, identifiers in the rendered source files link to their definitions.
+    pub generate_link_to_definition: bool,
+    /// If true, the search index is split into shards by crate and item name, which are loaded
+    /// on demand.
+    pub shard_search_index: bool,
+    /// The scraped examples given with `--with-examples`.
+    crate call_locations: AllCallLocations,
 }
 
 /// Temporary storage for data obtained during `RustdocVisitor::clean()`.
@@ -264,6 +296,12 @@ pub struct RenderInfo {
     pub deref_mut_trait_did: Option<DefId>,
     pub owned_box_did: Option<DefId>,
     pub output_format: Option<OutputFormat>,
+    /// The options of `--show-coverage`.
+    crate coverage_options: CoverageOptions,
+    /// The jump-to-definition links of the local source files, if they're generated.
+    crate span_map: crate::html::render::span_map::SpanMap,
+    /// The scraped examples calling the local functions.
+    crate call_locations: FxHashMap<DefId, Vec<CallSnippet>>,
 }
 
 impl Options {
@@ -496,6 +534,7 @@ impl Options {
             passes::DefaultPassOption::Default
         };
         let manual_passes = matches.opt_strs("passes");
+        let coverage_options = CoverageOptions::new(&matches, &diag, show_coverage)?;
 
         let crate_types = match parse_crate_types_from_list(matches.opt_strs("crate-type")) {
             Ok(types) => types,
@@ -512,12 +551,19 @@ impl Options {
                         diag.struct_err("json output format isn't supported for doc generation")
                             .emit();
                         return Err(1);
-                    } else if !o.is_json() && show_coverage {
+                    } else if o == OutputFormat::Markdown && !nightly_options::is_nightly_build() {
                         diag.struct_err(
-                            "html output format isn't supported for the --show-coverage option",
+                            "markdown output format is only available on the nightly channel",
                         )
                         .emit();
                         return Err(1);
+                    } else if !o.is_json() && show_coverage {
+                        diag.struct_err(&format!(
+                            "{} output format isn't supported for the --show-coverage option",
+                            s
+                        ))
+                        .emit();
+                        return Err(1);
                     }
                     Some(o)
                 }
@@ -552,8 +598,17 @@ impl Options {
         let runtool = matches.opt_str("runtool");
         let runtool_args = matches.opt_strs("runtool-arg");
         let enable_per_target_ignores = matches.opt_present("enable-per-target-ignores");
+        let merge_doctests = matches.opt_present("merge-doctests");
+        let doctest_filters = matches.opt_strs("doctest-filter");
+        let doctest_events = matches.opt_str("doctest-events").map(PathBuf::from);
         let document_private = matches.opt_present("document-private-items");
         let document_hidden = matches.opt_present("document-hidden-items");
+        let generate_link_to_definition = matches.opt_present("generate-link-to-definition");
+        let shard_search_index = matches.opt_present("shard-search-index");
+        let scrape_examples_options = ScrapeExamplesOptions::new(&matches, &diag)?;
+        let call_locations =
+            scrape_examples::load_call_locations(matches.opt_strs("with-examples"), &diag)?;
+        let semver_options = SemverOptions::new(&matches, &diag)?;
 
         let (lint_opts, describe_lints, lint_cap) = get_cmd_lint_options(matches, error_format);
 
@@ -583,11 +638,15 @@ impl Options {
             manual_passes,
             display_warnings,
             show_coverage,
+            coverage_options,
             crate_version,
             persist_doctests,
             runtool,
             runtool_args,
             enable_per_target_ignores,
+            merge_doctests,
+            doctest_filters,
+            doctest_events,
             test_builder,
             render_options: RenderOptions {
                 output,
@@ -609,8 +668,13 @@ impl Options {
                 generate_search_filter,
                 document_private,
                 document_hidden,
+                generate_link_to_definition,
+                shard_search_index,
+                call_locations,
             },
             output_format,
+            scrape_examples_options,
+            semver_options,
         })
     }
 
@@ -618,6 +682,12 @@ impl Options {
     pub fn markdown_input(&self) -> bool {
         self.input.extension().map_or(false, |e| e == "md" || e == "markdown")
     }
+
+    /// Returns `true` if the file given as `self.input` is an API model saved with
+    /// `--save-api-model`.
+    crate fn api_model_input(&self) -> bool {
+        self.input.extension().map_or(false, |e| e == "json")
+    }
 }
 
 /// Prints deprecation warnings for deprecated options
@@ -34,7 +34,9 @@ use crate::clean;
 use crate::clean::{AttributesExt, MAX_DEF_ID};
 use crate::config::{Options as RustdocOptions, RenderOptions};
 use crate::config::{OutputFormat, RenderInfo};
-use crate::passes::{self, Condition::*, ConditionalPass};
+use crate::html::render::span_map;
+use crate::passes::{self, Condition::*, ConditionalPass, CoverageOptions};
+use crate::scrape_examples::{self, ScrapeExamplesOptions};
 
 pub use rustc_session::config::{CodegenOptions, DebuggingOptions, Input, Options};
 pub use rustc_session::search_paths::SearchPath;
@@ -306,6 +308,8 @@ pub fn run_core(
         display_warnings,
         render_options,
         output_format,
+        coverage_options,
+        scrape_examples_options,
         ..
     } = options;
 
@@ -372,6 +376,8 @@ pub fn run_core(
         error_format,
         edition,
         describe_lints,
+        // Scraping the tests of a crate needs the `#[test]` functions to be kept.
+        test: scrape_examples_options.as_ref().map_or(false, |options| options.scrape_tests),
         ..Options::default()
     };
 
@@ -472,6 +478,8 @@ pub fn run_core(
                         manual_passes,
                         render_options,
                         output_format,
+                        coverage_options,
+                        scrape_examples_options,
                     )
                 })
             });
@@ -487,6 +495,8 @@ fn run_global_ctxt(
     mut manual_passes: Vec<String>,
     render_options: RenderOptions,
     output_format: Option<OutputFormat>,
+    coverage_options: CoverageOptions,
+    scrape_examples_options: Option<ScrapeExamplesOptions>,
 ) -> (clean::Crate, RenderInfo, RenderOptions) {
     // Certain queries assume that some checks were run elsewhere
     // (see https://github.com/rust-lang/rust/pull/73566#issuecomment-656954425),
@@ -530,6 +540,11 @@ fn run_global_ctxt(
     let mut renderinfo = RenderInfo::default();
     renderinfo.access_levels = access_levels;
     renderinfo.output_format = output_format;
+    renderinfo.coverage_options = coverage_options;
+    if !render_options.call_locations.is_empty() {
+        renderinfo.call_locations =
+            scrape_examples::resolve_call_locations(tcx, &render_options.call_locations);
+    }
 
     let mut ctxt = DocContext {
         tcx,
@@ -657,6 +672,15 @@ fn run_global_ctxt(
 
     ctxt.sess().abort_if_errors();
 
+    if let Some(options) = &scrape_examples_options {
+        tcx.sess.time("scrape_examples", || scrape_examples::run(tcx, options));
+    }
+
+    if ctxt.render_options.generate_link_to_definition {
+        let span_map = tcx.sess.time("collect_spans", || span_map::collect_spans(&ctxt));
+        ctxt.renderinfo.borrow_mut().span_map = span_map;
+    }
+
     (krate, ctxt.renderinfo.into_inner(), ctxt.render_options)
 }
 
@@ -15,15 +15,19 @@ use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::sym;
 use rustc_span::{BytePos, FileName, Pos, Span, DUMMY_SP};
 use rustc_target::spec::TargetTriple;
+use serde::{Deserialize, Serialize};
 use tempfile::Builder as TempFileBuilder;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
+use std::fs::File;
 use std::io::{self, Write};
 use std::panic;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{self, Command, Stdio};
 use std::str;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::clean::Attributes;
 use crate::config::Options;
@@ -99,6 +103,7 @@ pub fn run(options: Options) -> Result<(), ErrorReported> {
     };
 
     let mut test_args = options.test_args.clone();
+    test_args.insert(0, "rustdoctest".to_string());
     let display_warnings = options.display_warnings;
 
     let tests = interface::run_compiler(config, |compiler| {
@@ -144,6 +149,7 @@ pub fn run(options: Options) -> Result<(), ErrorReported> {
             });
             compiler.session().abort_if_errors();
 
+            collector.run_merged_doctests(&test_args);
             let ret: Result<_, ErrorReported> = Ok(collector.tests);
             ret
         })
@@ -153,8 +159,6 @@ pub fn run(options: Options) -> Result<(), ErrorReported> {
         Err(ErrorReported) => return Err(ErrorReported),
     };
 
-    test_args.insert(0, "rustdoctest".to_string());
-
     testing::test_main(
         &test_args,
         tests,
@@ -214,6 +218,8 @@ enum TestFailure {
     ExecutionFailure(process::Output),
     /// The test is marked `should_panic` but the test binary executed successfully.
     UnexpectedRunPass,
+    /// The test failed in the merged doctest binary, with the output libtest captured for it.
+    MergedFailure(String),
 }
 
 enum DirState {
@@ -230,6 +236,323 @@ impl DirState {
     }
 }
 
+/// A doctest of the merged crate.
+struct MergedDoctest {
+    /// The name of the doctest given to libtest.
+    name: String,
+    /// The doctest, as it was written in the documentation.
+    test: String,
+    /// The file the doctest comes from, and the line its code block starts at.
+    path: PathBuf,
+    line: usize,
+    should_panic: bool,
+}
+
+/// The result of a doctest run by the merged binary.
+#[derive(Clone)]
+enum MergedOutcome {
+    Ok,
+    /// The doctest failed, with the output libtest captured while it ran.
+    Failed(String),
+}
+
+/// Doctests of one edition that are compiled together into a single binary with
+/// `--merge-doctests`.
+///
+/// Every doctest becomes a `#[test]` function in its own module of the merged crate, which is
+/// compiled with `--test` and run once before libtest runs the doctests, so that all of them run
+/// in a single process.
+/// Each module is written to its own file, padded so that the doctest starts at the same line as
+/// in the documentation, and the file is remapped to the path of the documentation, so that panic
+/// locations point at the documentation.
+struct MergedDoctests {
+    cratename: String,
+    options: Options,
+    opts: TestOptions,
+    edition: Edition,
+    outdir: DirState,
+    tests: Mutex<Vec<MergedDoctest>>,
+    /// The outcome of each doctest and how long it ran, filled in before libtest runs the
+    /// doctests. A doctest without an outcome, because the merged binary failed to compile or
+    /// didn't get to run it, is compiled on its own instead.
+    outcomes: Mutex<HashMap<usize, (MergedOutcome, Option<Duration>)>>,
+}
+
+impl MergedDoctests {
+    fn new(
+        cratename: String,
+        options: Options,
+        opts: TestOptions,
+        edition: Edition,
+    ) -> MergedDoctests {
+        let outdir = if let Some(mut path) = options.persist_doctests.clone() {
+            path.push(format!("merged_doctests_{}", edition));
+            std::fs::create_dir_all(&path)
+                .expect("Couldn't create directory for doctest executables");
+            DirState::Perm(path)
+        } else {
+            DirState::Temp(
+                TempFileBuilder::new()
+                    .prefix("rustdoctest")
+                    .tempdir()
+                    .expect("rustdoc needs a tempdir"),
+            )
+        };
+        MergedDoctests {
+            cratename,
+            options,
+            opts,
+            edition,
+            outdir,
+            tests: Mutex::new(Vec::new()),
+            outcomes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether `test` can be compiled as a module of the merged crate. Tests that need to be
+    /// their own crate (because they have crate attributes, `extern crate` items or their own
+    /// `fn main`) or that aren't simply run are compiled standalone.
+    fn can_merge(&self, test: &str, config: &LangString) -> bool {
+        if config.compile_fail || config.no_run || config.test_harness || config.standalone {
+            return false;
+        }
+        // Before 2018, paths in `use` items are relative to the crate root, which is no longer the
+        // root of the doctest once it's moved into a module.
+        if self.edition < Edition::Edition2018 {
+            return false;
+        }
+        // The doctest-wide attributes are only applied to the module of each test. That's fine
+        // for lints, but not for anything that really needs to be a crate attribute.
+        let is_lint = |attr: &String| {
+            ["allow(", "warn(", "deny(", "forbid("].iter().any(|lint| attr.starts_with(lint))
+        };
+        if !self.opts.attrs.iter().all(is_lint) {
+            return false;
+        }
+        // The module of a doctest lives in the output directory, so relative paths given to
+        // `include!` and friends wouldn't be relative to the documentation anymore.
+        if ["include!", "include_str!", "include_bytes!"].iter().any(|mac| test.contains(mac)) {
+            return false;
+        }
+        let (crate_attrs, everything_else, crates) = partition_source(test);
+        if crate_attrs.lines().any(|line| line.trim().starts_with("#![")) || !crates.is_empty() {
+            return false;
+        }
+        let everything_else = everything_else.trim();
+        match check_for_main_and_extern_crate(test, crates, everything_else, None, self.edition) {
+            Ok((has_main, _)) => !has_main,
+            Err(ErrorReported) => false,
+        }
+    }
+
+    /// Adds a doctest to the merged crate, returning its index.
+    fn add(
+        &self,
+        name: String,
+        test: String,
+        path: PathBuf,
+        line: usize,
+        should_panic: bool,
+    ) -> usize {
+        let mut tests = self.tests.lock().unwrap();
+        tests.push(MergedDoctest { name, test, path, line, should_panic });
+        tests.len() - 1
+    }
+
+    /// Compiles the merged binary and runs the doctests whose names are `selected`, recording
+    /// their outcomes. Nothing is compiled if none of the doctests are selected.
+    fn compile_and_run(&self, selected: &HashSet<String>) {
+        let skipped: Vec<usize> = {
+            let tests = self.tests.lock().unwrap();
+            if !tests.iter().any(|test| selected.contains(&test.name)) {
+                return;
+            }
+            (0..tests.len()).filter(|&index| !selected.contains(&tests[index].name)).collect()
+        };
+        if let Some(binary) = self.compile() {
+            *self.outcomes.lock().unwrap() = self.run(&binary, &skipped);
+        }
+    }
+
+    /// Returns the outcome the merged binary recorded for the doctest at `index`, and how long the
+    /// doctest ran.
+    fn outcome(&self, index: usize) -> Option<(MergedOutcome, Option<Duration>)> {
+        self.outcomes.lock().unwrap().get(&index).cloned()
+    }
+
+    fn compile(&self) -> Option<PathBuf> {
+        let (root, remap_args) = match self.write_sources() {
+            Ok(paths) => paths,
+            Err(err) => {
+                debug!("couldn't write the merged doctests: {}", err);
+                return None;
+            }
+        };
+        let output_file = self.outdir.path().join("rust_out");
+
+        let mut compiler =
+            rustc_command(&self.options, self.edition, self.options.target.clone(), &output_file);
+        compiler.arg("--test");
+        compiler.arg(format!("@{}", remap_args.display()));
+        compiler.arg(&root);
+
+        let output = compiler.output().expect("Failed to spawn rustc process");
+        if output.status.success() {
+            Some(output_file)
+        } else {
+            // The errors are reported again when the doctests are compiled on their own, with
+            // the right doctest attached to them.
+            debug!(
+                "merged doctests failed to compile:\n{}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            None
+        }
+    }
+
+    /// Writes the merged crate to the output directory, returning the path of its root and of the
+    /// file with the `--remap-path-prefix` arguments of the doctests.
+    fn write_sources(&self) -> io::Result<(PathBuf, PathBuf)> {
+        let tests = self.tests.lock().unwrap();
+        let outdir = self.outdir.path();
+        let mut root = String::from("#![allow(unused)]\n");
+        if !self.opts.no_crate_inject
+            && self.cratename != "std"
+            && tests.iter().any(|test| test.test.contains(&self.cratename[..]))
+        {
+            root.push_str(&format!("extern crate {};\n", self.cratename));
+        }
+        let mut remap_args = String::new();
+
+        // The crate is injected above, so that the tests can refer to it from their module.
+        let module_opts = TestOptions { no_crate_inject: true, ..self.opts.clone() };
+        for (index, test) in tests.iter().enumerate() {
+            let (code, line_offset) =
+                make_test(&test.test, None, false, &module_opts, self.edition);
+
+            // The doctest starts after the line of the code block, like when the line offset is
+            // given to `rustc` through `UNSTABLE_RUSTDOC_TEST_LINE`.
+            let mut module = "\n".repeat(test.line.saturating_sub(line_offset));
+            module.push_str(&code);
+            module.push_str("\n#[test]\n");
+            if test.should_panic {
+                module.push_str("#[should_panic]\n");
+            }
+            module.push_str("fn __rustdoc_test() {\n    main()\n}\n");
+
+            // Each module gets a directory of its own, which is remapped to the directory of the
+            // documentation.
+            let dir = outdir.join(index.to_string());
+            let file_name = test.path.file_name().unwrap_or("doctest.rs".as_ref());
+            std::fs::create_dir_all(&dir)?;
+            std::fs::write(dir.join(file_name), module)?;
+            let module_path = Path::new(&index.to_string()).join(file_name);
+            root.push_str(&format!(
+                "#[path = {:?}]\nmod __doctest_{};\n",
+                module_path.to_string_lossy(),
+                index,
+            ));
+            let doc_dir = test.path.parent().unwrap_or(Path::new(""));
+            remap_args.push_str(&format!(
+                "--remap-path-prefix={}={}\n",
+                dir.display(),
+                doc_dir.display(),
+            ));
+        }
+
+        debug!("merged doctests:\n{}", root);
+
+        let root_path = outdir.join("merged_doctests.rs");
+        std::fs::write(&root_path, root)?;
+        let remap_args_path = outdir.join("remap-path-prefix.args");
+        std::fs::write(&remap_args_path, remap_args)?;
+        Ok((root_path, remap_args_path))
+    }
+
+    /// Runs the merged binary, except for the doctests at the indices in `skipped`, returning the
+    /// outcome of each doctest that libtest reported.
+    fn run(
+        &self,
+        binary: &Path,
+        skipped: &[usize],
+    ) -> HashMap<usize, (MergedOutcome, Option<Duration>)> {
+        let mut cmd;
+        if let Some(tool) = &self.options.runtool {
+            cmd = Command::new(tool);
+            cmd.args(&self.options.runtool_args);
+            cmd.arg(binary);
+        } else {
+            cmd = Command::new(binary);
+        }
+        // The JSON output of libtest and the time it takes for each test are unstable.
+        cmd.args(&["--format", "json", "--report-time", "-Z", "unstable-options", "--exact"]);
+        cmd.env("RUSTC_BOOTSTRAP", "1");
+        for index in skipped {
+            cmd.arg("--skip").arg(format!("__doctest_{}::__rustdoc_test", index));
+        }
+
+        match cmd.output() {
+            Ok(out) => parse_merged_output(&String::from_utf8_lossy(&out.stdout)),
+            Err(err) => {
+                debug!("couldn't run the merged doctests: {}", err);
+                HashMap::new()
+            }
+        }
+    }
+}
+
+/// Returns the index of the doctest a libtest test name of the merged binary refers to.
+fn merged_test_index(name: &str) -> Option<usize> {
+    name.strip_prefix("__doctest_")?.split("::").next()?.parse().ok()
+}
+
+/// An event of libtest's JSON output, as printed by the merged binary.
+#[derive(Deserialize)]
+struct MergedTestEvent {
+    #[serde(rename = "type")]
+    ty: String,
+    event: String,
+    #[serde(default)]
+    name: String,
+    /// The output libtest captured while a failed test ran.
+    stdout: Option<String>,
+    /// Why a test failed, if it didn't fail by panicking.
+    message: Option<String>,
+    /// How long a test ran, in seconds followed by `s`, with `--report-time`.
+    exec_time: Option<String>,
+}
+
+/// Parses the JSON output of libtest for the merged binary, one event per line. The outcome of
+/// each test and how long it ran are read from the event that ends it.
+fn parse_merged_output(stdout: &str) -> HashMap<usize, (MergedOutcome, Option<Duration>)> {
+    let mut outcomes = HashMap::new();
+    for line in stdout.lines() {
+        let event: MergedTestEvent = match serde_json::from_str(line) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+        let index = match merged_test_index(&event.name) {
+            Some(index) if event.ty == "test" => index,
+            _ => continue,
+        };
+        let outcome = match &event.event[..] {
+            "ok" => MergedOutcome::Ok,
+            "failed" => {
+                let output = event.stdout.iter().chain(&event.message);
+                let output = output.map(|output| output.trim()).collect::<Vec<_>>().join("\n");
+                MergedOutcome::Failed(output)
+            }
+            _ => continue,
+        };
+        let exec_time = event
+            .exec_time
+            .as_ref()
+            .and_then(|time| time.strip_suffix('s')?.parse().ok().map(Duration::from_secs_f64));
+        outcomes.insert(index, (outcome, exec_time));
+    }
+    outcomes
+}
+
 fn run_test(
     test: &str,
     cratename: &str,
@@ -252,48 +575,15 @@ fn run_test(
 
     let output_file = outdir.path().join("rust_out");
 
-    let rustc_binary = options
-        .test_builder
-        .as_deref()
-        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
-    let mut compiler = Command::new(&rustc_binary);
-    compiler.arg("--crate-type").arg("bin");
-    for cfg in &options.cfgs {
-        compiler.arg("--cfg").arg(&cfg);
-    }
-    if let Some(sysroot) = options.maybe_sysroot {
-        compiler.arg("--sysroot").arg(sysroot);
-    }
-    compiler.arg("--edition").arg(&edition.to_string());
+    let mut compiler = rustc_command(&options, edition, target, &output_file);
     compiler.env("UNSTABLE_RUSTDOC_TEST_PATH", path);
     compiler.env("UNSTABLE_RUSTDOC_TEST_LINE", format!("{}", line as isize - line_offset as isize));
-    compiler.arg("-o").arg(&output_file);
     if as_test_harness {
         compiler.arg("--test");
     }
-    for lib_str in &options.lib_strs {
-        compiler.arg("-L").arg(&lib_str);
-    }
-    for extern_str in &options.extern_strs {
-        compiler.arg("--extern").arg(&extern_str);
-    }
-    compiler.arg("-Ccodegen-units=1");
-    for codegen_options_str in &options.codegen_options_strs {
-        compiler.arg("-C").arg(&codegen_options_str);
-    }
-    for debugging_option_str in &options.debugging_opts_strs {
-        compiler.arg("-Z").arg(&debugging_option_str);
-    }
     if no_run && !compile_fail {
         compiler.arg("--emit=metadata");
     }
-    compiler.arg("--target").arg(match target {
-        TargetTriple::TargetTriple(s) => s,
-        TargetTriple::TargetPath(path) => {
-            path.to_str().expect("target path must be valid unicode").to_string()
-        }
-    });
-
     compiler.arg("-");
     compiler.stdin(Stdio::piped());
     compiler.stderr(Stdio::piped());
@@ -361,6 +651,50 @@ fn run_test(
     Ok(())
 }
 
+/// Creates the `rustc` invocation shared by standalone and merged doctests, which compiles a
+/// binary into `output_file`. The input is left to the caller.
+fn rustc_command(
+    options: &Options,
+    edition: Edition,
+    target: TargetTriple,
+    output_file: &Path,
+) -> Command {
+    let rustc_binary = options
+        .test_builder
+        .as_deref()
+        .unwrap_or_else(|| rustc_interface::util::rustc_path().expect("found rustc"));
+    let mut compiler = Command::new(&rustc_binary);
+    compiler.arg("--crate-type").arg("bin");
+    for cfg in &options.cfgs {
+        compiler.arg("--cfg").arg(&cfg);
+    }
+    if let Some(sysroot) = &options.maybe_sysroot {
+        compiler.arg("--sysroot").arg(sysroot);
+    }
+    compiler.arg("--edition").arg(&edition.to_string());
+    compiler.arg("-o").arg(output_file);
+    for lib_str in &options.lib_strs {
+        compiler.arg("-L").arg(&lib_str);
+    }
+    for extern_str in &options.extern_strs {
+        compiler.arg("--extern").arg(&extern_str);
+    }
+    compiler.arg("-Ccodegen-units=1");
+    for codegen_options_str in &options.codegen_options_strs {
+        compiler.arg("-C").arg(&codegen_options_str);
+    }
+    for debugging_option_str in &options.debugging_opts_strs {
+        compiler.arg("-Z").arg(&debugging_option_str);
+    }
+    compiler.arg("--target").arg(match target {
+        TargetTriple::TargetTriple(s) => s,
+        TargetTriple::TargetPath(path) => {
+            path.to_str().expect("target path must be valid unicode").to_string()
+        }
+    });
+    compiler
+}
+
 /// Transforms a test into code that can be compiled into a Rust binary, and returns the number of
 /// lines before the test code begins.
 pub fn make_test(
@@ -395,8 +729,60 @@ pub fn make_test(
     prog.push_str(&crate_attrs);
     prog.push_str(&crates);
 
-    // Uses librustc_ast to parse the doctest and find if there's a main fn and the extern
-    // crate already is included.
+    let (already_has_main, already_has_extern_crate) =
+        match check_for_main_and_extern_crate(s, crates, everything_else, cratename, edition) {
+            Ok(result) => result,
+            Err(ErrorReported) => {
+                // If the parser panicked due to a fatal error, pass the test code through
+                // unchanged. The error will be reported during compilation.
+                return (s.to_owned(), 0);
+            }
+        };
+
+    // Don't inject `extern crate std` because it's already injected by the
+    // compiler.
+    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
+        if let Some(cratename) = cratename {
+            // Make sure its actually used if not included.
+            if s.contains(cratename) {
+                prog.push_str(&format!("extern crate {};\n", cratename));
+                line_offset += 1;
+            }
+        }
+    }
+
+    // FIXME: This code cannot yet handle no_std test cases yet
+    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
+        prog.push_str(everything_else);
+    } else {
+        let returns_result = everything_else.trim_end().ends_with("(())");
+        let (main_pre, main_post) = if returns_result {
+            (
+                "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {",
+                "}\n_inner().unwrap() }",
+            )
+        } else {
+            ("fn main() {\n", "\n}")
+        };
+        prog.extend([main_pre, everything_else, main_post].iter().cloned());
+        line_offset += 1;
+    }
+
+    debug!("final doctest:\n{}", prog);
+
+    (prog, line_offset)
+}
+
+/// Uses librustc_ast to parse the doctest and find if there's a main fn and the extern crate
+/// already is included. `s` is the whole doctest, and `crates` and `everything_else` are the parts
+/// of it returned by `partition_source`.
+fn check_for_main_and_extern_crate(
+    s: &str,
+    crates: String,
+    everything_else: &str,
+    cratename: Option<&str>,
+    edition: Edition,
+) -> Result<(bool, bool), ErrorReported> {
     let result = rustc_driver::catch_fatal_errors(|| {
         rustc_span::with_session_globals(edition, || {
             use rustc_errors::emitter::EmitterWriter;
@@ -477,14 +863,7 @@ pub fn make_test(
             (found_main, found_extern_crate, found_macro)
         })
     });
-    let (already_has_main, already_has_extern_crate, found_macro) = match result {
-        Ok(result) => result,
-        Err(ErrorReported) => {
-            // If the parser panicked due to a fatal error, pass the test code through unchanged.
-            // The error will be reported during compilation.
-            return (s.to_owned(), 0);
-        }
-    };
+    let (already_has_main, already_has_extern_crate, found_macro) = result?;
 
     // If a doctest's `fn main` is being masked by a wrapper macro, the parsing loop above won't
     // see it. In that case, run the old text-based scan to see if they at least have a main
@@ -501,38 +880,7 @@ pub fn make_test(
         already_has_main
     };
 
-    // Don't inject `extern crate std` because it's already injected by the
-    // compiler.
-    if !already_has_extern_crate && !opts.no_crate_inject && cratename != Some("std") {
-        if let Some(cratename) = cratename {
-            // Make sure its actually used if not included.
-            if s.contains(cratename) {
-                prog.push_str(&format!("extern crate {};\n", cratename));
-                line_offset += 1;
-            }
-        }
-    }
-
-    // FIXME: This code cannot yet handle no_std test cases yet
-    if dont_insert_main || already_has_main || prog.contains("![no_std]") {
-        prog.push_str(everything_else);
-    } else {
-        let returns_result = everything_else.trim_end().ends_with("(())");
-        let (main_pre, main_post) = if returns_result {
-            (
-                "fn main() { fn _inner() -> Result<(), impl core::fmt::Debug> {",
-                "}\n_inner().unwrap() }",
-            )
-        } else {
-            ("fn main() {\n", "\n}")
-        };
-        prog.extend([main_pre, everything_else, main_post].iter().cloned());
-        line_offset += 1;
-    }
-
-    debug!("final doctest:\n{}", prog);
-
-    (prog, line_offset)
+    Ok((already_has_main, already_has_extern_crate))
 }
 
 // FIXME(aburka): use a real parser to deal with multiline attributes
@@ -605,8 +953,64 @@ fn partition_source(s: &str) -> (String, String, String) {
     (before, after, crates)
 }
 
+/// Whether a doctest of the item at `path` is selected by the `--doctest-filter` `filter`, i.e.
+/// whether the item is the one named by `filter` or one of its children.
+///
+/// The filter may start with the name of the crate, which the paths of the items don't include.
+fn item_path_matches(cratename: &str, path: &[String], filter: &str) -> bool {
+    let mut filter: Vec<&str> = filter.split("::").filter(|s| !s.is_empty()).collect();
+    if filter.first() == Some(&cratename) && path.first().map(|s| &**s) != Some(cratename) {
+        filter.remove(0);
+    }
+    filter.len() <= path.len() && filter.iter().zip(path).all(|(a, b)| *a == *b)
+}
+
+/// Where a doctest comes from, as reported in its `--doctest-events`.
+#[derive(Serialize)]
+struct DoctestInfo {
+    /// The name of the test given to libtest.
+    name: String,
+    /// The path of the item the doctest is written on, or the headers above it in a Markdown file.
+    item: String,
+    file: String,
+    /// The lines of the code block, including its fences.
+    line_start: usize,
+    line_end: usize,
+}
+
+/// An event of `--doctest-events`, modelled after the test events of libtest's JSON output.
+#[derive(Serialize)]
+struct DoctestEvent<'a> {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    event: &'a str,
+    #[serde(flatten)]
+    info: &'a DoctestInfo,
+    /// How long the doctest took to compile and run, in seconds. For a merged doctest, this is how
+    /// long it ran in the merged binary.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exec_time: Option<f64>,
+}
+
+/// The file the events of `--doctest-events` are written to, one JSON object per line.
+struct DoctestEvents(Mutex<File>);
+
+impl DoctestEvents {
+    fn write(&self, info: &DoctestInfo, event: &str, exec_time: Option<Duration>) {
+        let event = DoctestEvent {
+            ty: "test",
+            event,
+            info,
+            exec_time: exec_time.map(|time| time.as_secs_f64()),
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        // Failing to report an event shouldn't fail the doctest itself.
+        let _ = writeln!(self.0.lock().unwrap(), "{}", json);
+    }
+}
+
 pub trait Tester {
-    fn add_test(&mut self, test: String, config: LangString, line: usize);
+    fn add_test(&mut self, test: String, config: LangString, line: usize, end_line: usize);
     fn get_line(&self) -> usize {
         0
     }
@@ -647,6 +1051,10 @@ pub struct Collector {
     source_map: Option<Lrc<SourceMap>>,
     filename: Option<PathBuf>,
     visited_tests: HashMap<(String, usize), usize>,
+    /// With `--merge-doctests`, the merged binary of each edition.
+    merged_doctests: HashMap<Edition, Arc<MergedDoctests>>,
+    /// With `--doctest-events`, where the events of the doctests are written.
+    events: Option<Arc<DoctestEvents>>,
 }
 
 impl Collector {
@@ -659,6 +1067,12 @@ impl Collector {
         filename: Option<PathBuf>,
         enable_per_target_ignores: bool,
     ) -> Collector {
+        let events = options.doctest_events.as_ref().map(|path| {
+            let file = File::create(path).unwrap_or_else(|e| {
+                panic!("Couldn't create doctest events file {}: {}", path.display(), e)
+            });
+            Arc::new(DoctestEvents(Mutex::new(file)))
+        });
         Collector {
             tests: Vec::new(),
             names: Vec::new(),
@@ -671,6 +1085,8 @@ impl Collector {
             source_map,
             filename,
             visited_tests: HashMap::new(),
+            merged_doctests: HashMap::new(),
+            events,
         }
     }
 
@@ -682,10 +1098,50 @@ impl Collector {
         format!("{} - {}(line {})", filename, item_path, line)
     }
 
+    /// Whether the doctests of the current item are selected by `--doctest-filter`.
+    fn is_selected(&self) -> bool {
+        self.options.doctest_filters.is_empty()
+            || self
+                .options
+                .doctest_filters
+                .iter()
+                .any(|filter| item_path_matches(&self.cratename, &self.names, filter))
+    }
+
     pub fn set_position(&mut self, position: Span) {
         self.position = position;
     }
 
+    /// With `--merge-doctests`, compiles and runs the merged binaries before libtest runs the
+    /// doctests, which then only look up their outcome. Only the doctests that libtest selects
+    /// with `test_args`, the arguments it is given, are run.
+    pub fn run_merged_doctests(&self, test_args: &[String]) {
+        if self.merged_doctests.is_empty() {
+            return;
+        }
+        let opts = match testing::test::parse_opts(test_args) {
+            Some(Ok(opts)) if !opts.list => opts,
+            // Listing the doctests doesn't run them, and libtest reports invalid arguments itself.
+            _ => return,
+        };
+        let tests = self
+            .tests
+            .iter()
+            .map(|test| testing::TestDescAndFn {
+                desc: test.desc.clone(),
+                testfn: testing::DynTestFn(box || {}),
+            })
+            .collect();
+        let selected: HashSet<String> = testing::test::filter_tests(&opts, tests)
+            .into_iter()
+            .filter(|test| !test.desc.ignore)
+            .map(|test| test.desc.name.as_slice().to_string())
+            .collect();
+        for merged in self.merged_doctests.values() {
+            merged.compile_and_run(&selected);
+        }
+    }
+
     fn get_filename(&self) -> FileName {
         if let Some(ref source_map) = self.source_map {
             let filename = source_map.span_to_filename(self.position);
@@ -706,9 +1162,22 @@ impl Collector {
 }
 
 impl Tester for Collector {
-    fn add_test(&mut self, test: String, config: LangString, line: usize) {
+    fn add_test(&mut self, test: String, config: LangString, line: usize, end_line: usize) {
+        if !self.is_selected() {
+            return;
+        }
         let filename = self.get_filename();
         let name = self.generate_name(line, &filename);
+        let events = self.events.clone().map(|events| {
+            let info = DoctestInfo {
+                name: name.clone(),
+                item: self.names.join("::"),
+                file: filename.to_string(),
+                line_start: line,
+                line_end: end_line,
+            };
+            (events, info)
+        });
         let cratename = self.cratename.to_string();
         let opts = self.opts.clone();
         let edition = config.edition.unwrap_or(self.options.edition);
@@ -725,7 +1194,7 @@ impl Tester for Collector {
             _ => PathBuf::from(r"doctest.rs"),
         };
 
-        let outdir = if let Some(mut path) = options.persist_doctests.clone() {
+        let persist_path = if let Some(mut path) = options.persist_doctests.clone() {
             // For example `module/file.rs` would become `module_file_rs`
             let folder_name = filename
                 .to_string()
@@ -746,53 +1215,100 @@ impl Tester for Collector {
                 },
                 line = line,
             ));
+            Some(path)
+        } else {
+            None
+        };
 
-            std::fs::create_dir_all(&path)
-                .expect("Couldn't create directory for doctest executables");
+        let ignore = match config.ignore {
+            Ignore::All => true,
+            Ignore::None => false,
+            Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
+        };
 
-            DirState::Perm(path)
+        let merged = if self.options.merge_doctests && !ignore {
+            let merged = self.merged_doctests.entry(edition).or_insert_with(|| {
+                Arc::new(MergedDoctests::new(
+                    cratename.clone(),
+                    options.clone(),
+                    opts.clone(),
+                    edition,
+                ))
+            });
+            if merged.can_merge(&test, &config) {
+                let index =
+                    merged.add(name.clone(), test.clone(), path.clone(), line, config.should_panic);
+                Some((merged.clone(), index))
+            } else {
+                None
+            }
         } else {
-            DirState::Temp(
-                TempFileBuilder::new()
-                    .prefix("rustdoctest")
-                    .tempdir()
-                    .expect("rustdoc needs a tempdir"),
-            )
+            None
         };
 
         debug!("creating test {}: {}", name, test);
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
                 name: testing::DynTestName(name),
-                ignore: match config.ignore {
-                    Ignore::All => true,
-                    Ignore::None => false,
-                    Ignore::Some(ref ignores) => ignores.iter().any(|s| target_str.contains(s)),
-                },
+                ignore,
                 // compiler failures are test failures
                 should_panic: testing::ShouldPanic::No,
                 allow_fail: config.allow_fail,
                 test_type: testing::TestType::DocTest,
             },
             testfn: testing::DynTestFn(box move || {
-                let res = run_test(
-                    &test,
-                    &cratename,
-                    line,
-                    options,
-                    config.should_panic,
-                    config.no_run,
-                    config.test_harness,
-                    runtool,
-                    runtool_args,
-                    target,
-                    config.compile_fail,
-                    config.error_codes,
-                    &opts,
-                    edition,
-                    outdir,
-                    path,
-                );
+                if let Some((events, info)) = &events {
+                    events.write(info, "started", None);
+                }
+                let start = Instant::now();
+                let outcome = merged.and_then(|(merged, index)| merged.outcome(index));
+                // A merged doctest ran before libtest got to it, so its time is the one the merged
+                // binary reported.
+                let (res, exec_time) = match outcome {
+                    Some((MergedOutcome::Ok, exec_time)) => (Ok(()), exec_time),
+                    Some((MergedOutcome::Failed(output), exec_time)) => {
+                        (Err(TestFailure::MergedFailure(output)), exec_time)
+                    }
+                    None => {
+                        // The output directory is only created for the doctests that are
+                        // compiled on their own.
+                        let outdir = if let Some(path) = persist_path {
+                            std::fs::create_dir_all(&path)
+                                .expect("Couldn't create directory for doctest executables");
+                            DirState::Perm(path)
+                        } else {
+                            DirState::Temp(
+                                TempFileBuilder::new()
+                                    .prefix("rustdoctest")
+                                    .tempdir()
+                                    .expect("rustdoc needs a tempdir"),
+                            )
+                        };
+                        let res = run_test(
+                            &test,
+                            &cratename,
+                            line,
+                            options,
+                            config.should_panic,
+                            config.no_run,
+                            config.test_harness,
+                            runtool,
+                            runtool_args,
+                            target,
+                            config.compile_fail,
+                            config.error_codes,
+                            &opts,
+                            edition,
+                            outdir,
+                            path,
+                        );
+                        (res, Some(start.elapsed()))
+                    }
+                };
+                if let Some((events, info)) = &events {
+                    let event = if res.is_ok() { "ok" } else { "failed" };
+                    events.write(info, event, exec_time);
+                }
 
                 if let Err(err) = res {
                     match err {
@@ -847,6 +1363,14 @@ impl Tester for Collector {
                                 }
                             }
                         }
+                        TestFailure::MergedFailure(output) => {
+                            eprintln!("Test failed in the merged doctest binary.");
+
+                            if !output.is_empty() {
+                                eprintln!();
+                                eprintln!("output:\n{}", output);
+                            }
+                        }
                     }
 
                     panic::resume_unwind(box ());
@@ -1,4 +1,4 @@
-use super::{make_test, TestOptions};
+use super::{item_path_matches, make_test, parse_merged_output, MergedOutcome, TestOptions};
 use rustc_span::edition::DEFAULT_EDITION;
 
 #[test]
@@ -277,3 +277,33 @@ test_wrapper! {
     let output = make_test(input, Some("my_crate"), false, &opts, DEFAULT_EDITION);
     assert_eq!(output, (expected, 1));
 }
+
+#[test]
+fn doctest_filter_item_path() {
+    let path: Vec<String> = vec!["foo".into(), "Bar".into(), "baz".into()];
+    assert!(item_path_matches("krate", &path, "foo"));
+    assert!(item_path_matches("krate", &path, "foo::Bar"));
+    assert!(item_path_matches("krate", &path, "foo::Bar::baz"));
+    assert!(item_path_matches("krate", &path, "krate::foo::Bar"));
+    assert!(item_path_matches("krate", &path, "krate"));
+    assert!(!item_path_matches("krate", &path, "foo::Ba"));
+    assert!(!item_path_matches("krate", &path, "Bar"));
+    assert!(!item_path_matches("krate", &path, "foo::Bar::baz::qux"));
+    assert!(!item_path_matches("krate", &[], "foo"));
+}
+
+#[test]
+fn merged_doctests_json_output() {
+    let stdout = r#"{ "type": "suite", "event": "started", "test_count": 3 }
+{ "type": "test", "event": "started", "name": "__doctest_0::__rustdoc_test" }
+{ "type": "test", "name": "__doctest_0::__rustdoc_test", "event": "ok", "exec_time": "0.250s" }
+{ "type": "test", "name": "__doctest_2::__rustdoc_test", "event": "failed", "stdout": "boom\n" }
+{ "type": "test", "name": "__doctest_3::__rustdoc_test", "event": "failed", "message": "no panic" }
+{ "type": "suite", "event": "failed", "passed": 1, "failed": 2, "filtered_out": 1 }
+"#;
+    let outcomes = parse_merged_output(stdout);
+    assert_eq!(outcomes.len(), 3);
+    assert!(matches!(outcomes[&0], (MergedOutcome::Ok, Some(time)) if time.as_millis() == 250));
+    assert!(matches!(&outcomes[&2], (MergedOutcome::Failed(output), None) if output == "boom"));
+    assert!(matches!(&outcomes[&3], (MergedOutcome::Failed(output), None) if output == "no panic"));
+}
@@ -17,6 +17,7 @@ use crate::formats::Impl;
 use crate::html::render::cache::{extern_location, get_index_search_type, ExternalLocation};
 use crate::html::render::IndexItem;
 use crate::html::render::{plain_summary_line, shorten};
+use crate::scrape_examples::CallSnippet;
 
 thread_local!(crate static CACHE_KEY: RefCell<Arc<Cache>> = Default::default());
 
@@ -122,6 +123,9 @@ pub struct Cache {
     /// Aliases added through `#[doc(alias = "...")]`. Since a few items can have the same alias,
     /// we need the alias element to have an array of items.
     pub aliases: BTreeMap<String, Vec<usize>>,
+
+    /// The snippets of the scraped examples that call each local function.
+    crate call_locations: FxHashMap<DefId, Vec<CallSnippet>>,
 }
 
 impl Cache {
@@ -141,6 +145,7 @@ impl Cache {
             deref_trait_did,
             deref_mut_trait_did,
             owned_box_did,
+            call_locations,
             ..
         } = render_info;
 
@@ -160,6 +165,7 @@ impl Cache {
             deref_mut_trait_did,
             owned_box_did,
             masked_crates: mem::take(&mut krate.masked_crates),
+            call_locations,
             ..Cache::default()
         };
 
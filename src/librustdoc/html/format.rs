@@ -15,7 +15,7 @@ use rustc_span::def_id::DefId;
 use rustc_target::spec::abi::Abi;
 
 use crate::clean::{self, PrimitiveType};
-use crate::formats::cache::cache;
+use crate::formats::cache::{cache, Cache};
 use crate::formats::item_type::ItemType;
 use crate::html::escape::Escape;
 use crate::html::render::cache::ExternalLocation;
@@ -481,14 +481,23 @@ impl clean::Path {
 }
 
 pub fn href(did: DefId) -> Option<(String, ItemType, Vec<String>)> {
-    let cache = cache();
+    let depth = CURRENT_DEPTH.with(|l| l.get());
+    href_with_root_path(did, &cache(), &"../".repeat(depth))
+}
+
+/// Like `href`, but for a page whose path to the documentation root is `root_path`, instead of a
+/// page of the module hierarchy.
+crate fn href_with_root_path(
+    did: DefId,
+    cache: &Cache,
+    root_path: &str,
+) -> Option<(String, ItemType, Vec<String>)> {
     if !did.is_local() && !cache.access_levels.is_public(did) && !cache.document_private {
         return None;
     }
 
-    let depth = CURRENT_DEPTH.with(|l| l.get());
     let (fqp, shortty, mut url) = match cache.paths.get(&did) {
-        Some(&(ref fqp, shortty)) => (fqp, shortty, "../".repeat(depth)),
+        Some(&(ref fqp, shortty)) => (fqp, shortty, root_path.to_string()),
         None => {
             let &(ref fqp, shortty) = cache.external_paths.get(&did)?;
             (
@@ -496,7 +505,7 @@ pub fn href(did: DefId) -> Option<(String, ItemType, Vec<String>)> {
                 shortty,
                 match cache.extern_locations[&did.krate] {
                     (.., ExternalLocation::Remote(ref s)) => s.to_string(),
-                    (.., ExternalLocation::Local) => "../".repeat(depth),
+                    (.., ExternalLocation::Local) => root_path.to_string(),
                     (.., ExternalLocation::Unknown) => return None,
                 },
             )
@@ -12,6 +12,7 @@ use std::io;
 use std::io::prelude::*;
 
 use rustc_ast::token::{self, Token};
+use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::Lrc;
 use rustc_parse::lexer;
 use rustc_session::parse::ParseSess;
@@ -20,12 +21,17 @@ use rustc_span::source_map::SourceMap;
 use rustc_span::symbol::{kw, sym};
 use rustc_span::{BytePos, FileName, SourceFile, Span};
 
-/// Highlights `src`, returning the HTML output.
+/// The URLs that the tokens of some source code link to, keyed by the byte range of the token in
+/// the source.
+crate type SourceLinks = FxHashMap<(u32, u32), String>;
+
+/// Highlights `src`, returning the HTML output. The tokens found in `links` are turned into links.
 pub fn render_with_highlighting(
     src: String,
     class: Option<&str>,
     playground_button: Option<&str>,
     tooltip: Option<(&str, &str)>,
+    links: Option<&SourceLinks>,
 ) -> String {
     debug!("highlighting: ================\n{}\n==============", src);
     let mut out = Vec::new();
@@ -46,7 +52,7 @@ pub fn render_with_highlighting(
 
     let classifier_source_file = Lrc::clone(&source_file);
     let highlight_result = rustc_driver::catch_fatal_errors(|| {
-        let mut classifier = Classifier::new(&sess, classifier_source_file);
+        let mut classifier = Classifier::new(&sess, classifier_source_file, links);
 
         let mut highlighted_source = vec![];
         if classifier.write_source(&mut highlighted_source).is_err() {
@@ -84,10 +90,14 @@ pub fn render_with_highlighting(
 /// Processes a program (nested in the internal `lexer`), classifying strings of
 /// text by highlighting category (`Class`). Calls out to a `Writer` to write
 /// each span of text in sequence.
-struct Classifier<'sess> {
-    lexer: lexer::StringReader<'sess>,
+struct Classifier<'a> {
+    lexer: lexer::StringReader<'a>,
     peek_token: Option<Token>,
-    source_map: &'sess SourceMap,
+    source_map: &'a SourceMap,
+    /// The start of the highlighted source in `source_map`, which the byte ranges of `links` are
+    /// relative to.
+    start_pos: BytePos,
+    links: Option<&'a SourceLinks>,
 
     // State of the classifier.
     in_attribute: bool,
@@ -144,6 +154,12 @@ trait Writer {
     /// The latter can be thought of as a shorthand for the former, which is
     /// more flexible.
     fn string<T: Display>(&mut self, text: T, klass: Class) -> io::Result<()>;
+
+    /// Called before the text of a token that links to `href`.
+    fn enter_link(&mut self, href: &str) -> io::Result<()>;
+
+    /// Called after the text of a token that links somewhere.
+    fn exit_link(&mut self) -> io::Result<()>;
 }
 
 // Implement `Writer` for anything that can be written to, this just implements
@@ -163,6 +179,14 @@ impl<U: Write> Writer for U {
     fn exit_span(&mut self) -> io::Result<()> {
         write!(self, "</span>")
     }
+
+    fn enter_link(&mut self, href: &str) -> io::Result<()> {
+        write!(self, "<a href=\"{}\">", Escape(href))
+    }
+
+    fn exit_link(&mut self) -> io::Result<()> {
+        write!(self, "</a>")
+    }
 }
 
 #[derive(Debug)]
@@ -177,14 +201,21 @@ impl From<io::Error> for HighlightError {
     }
 }
 
-impl<'sess> Classifier<'sess> {
-    fn new(sess: &ParseSess, source_file: Lrc<SourceFile>) -> Classifier<'_> {
+impl<'a> Classifier<'a> {
+    fn new(
+        sess: &'a ParseSess,
+        source_file: Lrc<SourceFile>,
+        links: Option<&'a SourceLinks>,
+    ) -> Classifier<'a> {
+        let start_pos = source_file.start_pos;
         let lexer = lexer::StringReader::new(sess, source_file, None);
 
         Classifier {
             lexer,
             peek_token: None,
             source_map: sess.source_map(),
+            start_pos,
+            links,
             in_attribute: false,
             in_macro: false,
             in_macro_nonterminal: false,
@@ -405,7 +436,18 @@ impl<'sess> Classifier<'sess> {
 
         // Anything that didn't return above is the simple case where we the
         // class just spans a single token, so we can use the `string` method.
+        let link = self.links.and_then(|links| {
+            let lo = (token.span.lo() - self.start_pos).0;
+            let hi = (token.span.hi() - self.start_pos).0;
+            links.get(&(lo, hi))
+        });
+        if let Some(href) = link {
+            out.enter_link(href)?;
+        }
         out.string(Escape(&self.snip(token.span)), klass)?;
+        if link.is_some() {
+            out.exit_link()?;
+        }
 
         Ok(())
     }
@@ -15,7 +15,7 @@ fn highlight(src: &str) -> String {
             src.to_owned(),
         );
 
-        let mut classifier = Classifier::new(&sess, source_file);
+        let mut classifier = Classifier::new(&sess, source_file, None);
         classifier.write_source(&mut out).unwrap();
     });
 
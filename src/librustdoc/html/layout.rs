@@ -111,6 +111,7 @@ pub fn render<T: Print, S: Print>(
     <script>\
         window.rootPath = \"{root_path}\";\
         window.currentCrate = \"{krate}\";\
+        window.resourceSuffix = \"{suffix}\";\
     </script>\
     <script src=\"{static_root_path}main{suffix}.js\"></script>\
     {static_extra_scripts}\
@@ -38,7 +38,7 @@ use crate::doctest;
 use crate::html::highlight;
 use crate::html::toc::TocBuilder;
 
-use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag};
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, LinkType, Options, Parser, Tag};
 
 #[cfg(test)]
 mod tests;
@@ -309,6 +309,7 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'_, 'a, I> {
                 )),
                 playground_button.as_deref(),
                 Some((s1.as_str(), s2)),
+                None,
             ));
             Some(Event::Html(s.into()))
         } else {
@@ -330,6 +331,7 @@ impl<'a, I: Iterator<Item = Event<'a>>> Iterator for CodeBlocks<'_, 'a, I> {
                 )),
                 playground_button.as_deref(),
                 None,
+                None,
             ));
             Some(Event::Html(s.into()))
         }
@@ -614,7 +616,8 @@ pub fn find_testable_code<T: doctest::Tester>(
 
                 nb_lines += doc[prev_offset..offset.start].lines().count();
                 let line = tests.get_line() + nb_lines + 1;
-                tests.add_test(text, block_info, line);
+                let end_line = line + doc[offset.clone()].lines().count().saturating_sub(1);
+                tests.add_test(text, block_info, line, end_line);
                 prev_offset = offset.start;
             }
             Event::Start(Tag::Heading(level)) => {
@@ -689,6 +692,8 @@ pub struct LangString {
     pub error_codes: Vec<String>,
     pub allow_fail: bool,
     pub edition: Option<Edition>,
+    /// Whether this test must be compiled on its own, even with `--merge-doctests`.
+    pub standalone: bool,
 }
 
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -711,6 +716,7 @@ impl LangString {
             error_codes: Vec::new(),
             allow_fail: false,
             edition: None,
+            standalone: false,
         }
     }
 
@@ -766,6 +772,10 @@ impl LangString {
                     data.rust = true;
                     seen_rust_tags = true;
                 }
+                "standalone" => {
+                    data.standalone = true;
+                    seen_rust_tags = !seen_other_tags;
+                }
                 "test_harness" => {
                     data.test_harness = true;
                     seen_rust_tags = !seen_other_tags || seen_rust_tags;
@@ -1140,6 +1150,121 @@ crate fn rust_code_blocks(md: &str, extra_info: &ExtraInfo<'_, '_>) -> Vec<RustC
     code_blocks
 }
 
+/// Rewrites the documentation `md` as plain CommonMark, for the Markdown output format.
+///
+/// The intra-doc links are turned into regular links to the URLs in `links`, the Rust code blocks
+/// lose their rustdoc-specific attributes and hidden lines, and the ATX headings are moved
+/// `heading_offset` levels down so that they fit below the heading of the documented item.
+crate fn rewrite_for_commonmark(
+    md: &str,
+    links: &[(String, String)],
+    heading_offset: u32,
+) -> String {
+    if md.is_empty() {
+        return String::new();
+    }
+
+    let replacer = |_: &str, s: &str| {
+        if let Some(&(_, ref replace)) = links.iter().find(|link| &*link.0 == s) {
+            Some((replace.clone(), s.to_owned()))
+        } else {
+            None
+        }
+    };
+    let mut p =
+        Parser::new_with_broken_link_callback(md, opts(), Some(&replacer)).into_offset_iter();
+
+    // The replacements to make, as the ranges they replace in `md`.
+    let mut edits: Vec<(Range<usize>, String)> = vec![];
+    while let Some((event, range)) = p.next() {
+        match event {
+            Event::Start(Tag::Heading(level)) if md[range.clone()].starts_with('#') => {
+                let offset = heading_offset.min(6u32.saturating_sub(level));
+                edits.push((range.start..range.start, "#".repeat(offset as usize)));
+            }
+            Event::Start(Tag::Link(kind, dest, _)) => {
+                let href = match kind {
+                    // The broken link callback already resolved these.
+                    LinkType::ShortcutUnknown
+                    | LinkType::CollapsedUnknown
+                    | LinkType::ReferenceUnknown => dest.to_string(),
+                    _ => match links.iter().find(|link| *link.0 == *dest) {
+                        Some(&(_, ref href)) => href.clone(),
+                        None => continue,
+                    },
+                };
+                let source = &md[range.clone()];
+                let text_end = match kind {
+                    LinkType::Inline => source.rfind("]("),
+                    LinkType::Reference
+                    | LinkType::ReferenceUnknown
+                    | LinkType::Collapsed
+                    | LinkType::CollapsedUnknown => source.rfind("]["),
+                    LinkType::Shortcut | LinkType::ShortcutUnknown if source.ends_with(']') => {
+                        Some(source.len() - 1)
+                    }
+                    _ => None,
+                };
+                if let Some(text_end) = text_end {
+                    edits.push((range, format!("{}]({})", &source[..text_end], href)));
+                }
+            }
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(syntax))) => {
+                let lang_string = if syntax.is_empty() {
+                    LangString::all_false()
+                } else {
+                    LangString::parse(&syntax, ErrorCodes::No, false, None)
+                };
+                let mut code = String::new();
+                while let Some((Event::Text(text), _)) = p.next() {
+                    code.push_str(&text);
+                }
+                if !lang_string.rust {
+                    continue;
+                }
+
+                // The lines after the opening fence have to stay in the same container, like a
+                // list item or a block quote, so they get the same indentation and markers.
+                let line_start = md[..range.start].rfind('\n').map_or(0, |i| i + 1);
+                let prefix: String = md[line_start..range.start]
+                    .chars()
+                    .map(|c| if c == '>' { '>' } else { ' ' })
+                    .collect();
+                let source = &md[range.clone()];
+                let fence_char = if source.starts_with('~') { '~' } else { '`' };
+                let fence: String = source.chars().take_while(|&c| c == fence_char).collect();
+
+                let mut block = format!("{}rust\n", fence);
+                for line in code.lines().filter_map(|l| map_line(l).for_html()) {
+                    block.push_str(&prefix);
+                    block.push_str(&line);
+                    block.push('\n');
+                }
+                block.push_str(&prefix);
+                block.push_str(&fence);
+                if source.ends_with('\n') {
+                    block.push('\n');
+                }
+                edits.push((range, block));
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = String::with_capacity(md.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        if range.start < pos {
+            continue;
+        }
+        out.push_str(&md[pos..range.start]);
+        out.push_str(&replacement);
+        pos = range.end;
+    }
+    out.push_str(&md[pos..]);
+    out
+}
+
 #[derive(Clone, Default, Debug)]
 pub struct IdMap {
     map: FxHashMap<String, usize>,
@@ -1,4 +1,5 @@
 use super::plain_summary_line;
+use super::rewrite_for_commonmark;
 use super::{ErrorCodes, IdMap, Ignore, LangString, Markdown, MarkdownHtml};
 use rustc_span::edition::{Edition, DEFAULT_EDITION};
 use std::cell::RefCell;
@@ -76,6 +77,7 @@ fn test_lang_string_parse() {
                 original: s.to_owned(),
                 allow_fail,
                 edition,
+                standalone: false,
             }
         )
     }
@@ -129,6 +131,20 @@ fn test_lang_string_parse() {
     );
 }
 
+#[test]
+fn test_lang_string_standalone() {
+    fn t(s: &str, standalone: bool, rust: bool) {
+        let lang = LangString::parse(s, ErrorCodes::Yes, true, None);
+        assert_eq!((lang.standalone, lang.rust), (standalone, rust), "{}", s);
+    }
+
+    t("", false, true);
+    t("standalone", true, true);
+    t("should_panic,standalone", true, true);
+    t("{.standalone .rust}", true, true);
+    t("text,standalone", true, false);
+}
+
 #[test]
 fn test_header() {
     fn t(input: &str, expect: &str) {
@@ -237,3 +253,27 @@ fn test_markdown_html_escape() {
     t("Struct<'a, T>", "<p>Struct&lt;'a, T&gt;</p>\n");
     t("Struct<br>", "<p>Struct&lt;br&gt;</p>\n");
 }
+
+#[test]
+fn test_rewrite_for_commonmark() {
+    fn t(input: &str, expect: &str) {
+        let links = vec![
+            ("Foo".to_string(), "struct.Foo.md".to_string()),
+            ("`Bar`".to_string(), "../bar/index.md".to_string()),
+        ];
+        let output = rewrite_for_commonmark(input, &links, 1);
+        assert_eq!(output, expect, "original: {}", input);
+    }
+
+    t("See [Foo].", "See [Foo](struct.Foo.md).");
+    t(
+        "See [`Bar`] and [the foo](Foo).",
+        "See [`Bar`](../bar/index.md) and [the foo](struct.Foo.md).",
+    );
+    t("See [this][Foo] or [Baz].", "See [this](struct.Foo.md) or [Baz].");
+    t("# Examples\n\nText", "## Examples\n\nText");
+    t("```\n# fn main() {\nlet x = 1;\n# }\n```\n", "```rust\nlet x = 1;\n```\n");
+    t("```should_panic\npanic!();\n```", "```rust\npanic!();\n```");
+    t("```text\n# not hidden\n```", "```text\n# not hidden\n```");
+    t("> ```\n> # use x;\n> y();\n> ```", "> ```rust\n> y();\n> ```");
+}
@@ -61,10 +61,70 @@ pub fn extern_location(
         .unwrap_or(Unknown) // Well, at least we tried.
 }
 
+/// The search index of a crate.
+crate struct SearchIndex {
+    /// The entry of the crate in `search-index.js`.
+    crate entry: String,
+    /// With `--shard-search-index`, the contents of the files of the shards of the index, by key.
+    crate shards: Vec<(String, String)>,
+}
+
 /// Builds the search index from the collected metadata
-pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
-    let mut defid_to_pathid = FxHashMap::default();
+crate fn build_index(krate: &clean::Crate, cache: &mut Cache, sharded: bool) -> SearchIndex {
+    let crate_paths = attach_parents(cache);
+
+    let crate_doc = krate
+        .module
+        .as_ref()
+        .map(|module| shorten(plain_summary_line(module.doc_value())))
+        .unwrap_or(String::new());
+
+    if sharded {
+        return build_sharded_index(krate, cache, crate_doc, crate_paths);
+    }
+
     let mut crate_items = Vec::with_capacity(cache.search_index.len());
+    let Cache { ref mut search_index, ref aliases, .. } = *cache;
+
+    // Omit the parent path if it is same to that of the prior item.
+    let mut lastpath = String::new();
+    for item in search_index {
+        if lastpath == item.path {
+            item.path.clear();
+        } else {
+            lastpath = item.path.clone();
+        }
+        crate_items.push(&*item);
+    }
+
+    #[derive(Serialize)]
+    struct CrateData<'a> {
+        doc: String,
+        #[serde(rename = "i")]
+        items: Vec<&'a IndexItem>,
+        #[serde(rename = "p")]
+        paths: Vec<(ItemType, String)>,
+        // The String is alias name and the vec is the list of the elements with this alias.
+        //
+        // To be noted: the `usize` elements are indexes to `items`.
+        #[serde(rename = "a")]
+        #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+        aliases: &'a BTreeMap<String, Vec<usize>>,
+    }
+
+    // Collect the index into a string
+    let entry = format!(
+        r#""{}":{}"#,
+        krate.name,
+        to_js_json(&CrateData { doc: crate_doc, items: crate_items, paths: crate_paths, aliases })
+    );
+    SearchIndex { entry, shards: Vec::new() }
+}
+
+/// Attaches the items of the index to their parents, and returns the paths of the parents, which
+/// the items refer to by index.
+fn attach_parents(cache: &mut Cache) -> Vec<(ItemType, String)> {
+    let mut defid_to_pathid = FxHashMap::default();
     let mut crate_paths = vec![];
 
     let Cache { ref mut search_index, ref orphan_impl_items, ref paths, ref mut aliases, .. } =
@@ -94,7 +154,6 @@ pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
 
     // Reduce `DefId` in paths into smaller sequential numbers,
     // and prune the paths that do not appear in the index.
-    let mut lastpath = String::new();
     let mut lastpathid = 0usize;
 
     for item in search_index {
@@ -114,54 +173,121 @@ pub fn build_index(krate: &clean::Crate, cache: &mut Cache) -> String {
                 }
             }
         });
+    }
 
-        // Omit the parent path if it is same to that of the prior item.
-        if lastpath == item.path {
-            item.path.clear();
-        } else {
-            lastpath = item.path.clone();
-        }
-        crate_items.push(&*item);
+    crate_paths
+}
+
+/// The key of the shard holding the items named `name`: the first character of the name in
+/// lowercase, or `_` if it isn't alphanumeric. `static/main.js` computes the same keys.
+fn shard_key(name: &str) -> String {
+    match name.chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some(c) if c.is_ascii_alphanumeric() => c.to_string(),
+        _ => "_".to_string(),
     }
+}
 
-    let crate_doc = krate
-        .module
-        .as_ref()
-        .map(|module| shorten(plain_summary_line(module.doc_value())))
-        .unwrap_or(String::new());
+/// Splits the index of the crate into shards by item name, for `--shard-search-index`.
+///
+/// The entry of the crate in `search-index.js` then only has its description and the keys of its
+/// shards. The tables shared by the items of all the shards are in the `paths` shard: the paths of
+/// the parents of the items, and the paths of their modules, which the items refer to by index
+/// instead of repeating them.
+fn build_sharded_index(
+    krate: &clean::Crate,
+    cache: &Cache,
+    crate_doc: String,
+    crate_paths: Vec<(ItemType, String)>,
+) -> SearchIndex {
+    // The items are serialized like in the full index, with the index of their module path
+    // instead of the path itself.
+    type ShardItem<'a> =
+        (ItemType, &'a str, usize, &'a str, Option<usize>, &'a Option<IndexItemFunctionType>);
 
-    #[derive(Serialize)]
-    struct CrateData<'a> {
-        doc: String,
+    #[derive(Default, Serialize)]
+    struct Shard<'a> {
         #[serde(rename = "i")]
-        items: Vec<&'a IndexItem>,
-        #[serde(rename = "p")]
-        paths: Vec<(ItemType, String)>,
-        // The String is alias name and the vec is the list of the elements with this alias.
-        //
-        // To be noted: the `usize` elements are indexes to `items`.
+        items: Vec<ShardItem<'a>>,
+        // The indexes are those of the items of the shard.
         #[serde(rename = "a")]
         #[serde(skip_serializing_if = "BTreeMap::is_empty")]
-        aliases: &'a BTreeMap<String, Vec<usize>>,
+        aliases: BTreeMap<&'a str, Vec<usize>>,
     }
 
-    // Collect the index into a string
-    format!(
+    #[derive(Serialize)]
+    struct Paths<'a> {
+        #[serde(rename = "p")]
+        paths: Vec<(ItemType, String)>,
+        #[serde(rename = "m")]
+        modules: Vec<&'a str>,
+    }
+
+    let mut modules = Vec::new();
+    let mut module_ids = FxHashMap::default();
+    let mut shards: BTreeMap<String, Shard<'_>> = BTreeMap::new();
+    // The shard of each item of `cache.search_index`, and its index there.
+    let mut locations = Vec::with_capacity(cache.search_index.len());
+
+    for item in &cache.search_index {
+        let module = *module_ids.entry(&*item.path).or_insert_with(|| {
+            modules.push(&*item.path);
+            modules.len() - 1
+        });
+        let key = shard_key(&item.name);
+        let shard = shards.entry(key.clone()).or_default();
+        locations.push((key, shard.items.len()));
+        shard.items.push((
+            item.ty,
+            &item.name,
+            module,
+            &item.desc,
+            item.parent_idx,
+            &item.search_type,
+        ));
+    }
+    for (alias, items) in &cache.aliases {
+        for &item in items {
+            let (ref key, index) = locations[item];
+            let shard = shards.get_mut(key).unwrap();
+            shard.aliases.entry(&**alias).or_default().push(index);
+        }
+    }
+
+    let shard_file = |key: &str, json: String| {
+        format!("loadSearchShard(\"{}\",\"{}\",JSON.parse('{}'));", krate.name, key, json)
+    };
+    let mut files = vec![(
+        "paths".to_string(),
+        shard_file("paths", to_js_json(&Paths { paths: crate_paths, modules })),
+    )];
+    for (key, shard) in &shards {
+        files.push((key.clone(), shard_file(key, to_js_json(shard))));
+    }
+
+    #[derive(Serialize)]
+    struct CrateEntry<'a> {
+        doc: String,
+        #[serde(rename = "s")]
+        shards: Vec<&'a str>,
+    }
+
+    let entry = format!(
         r#""{}":{}"#,
         krate.name,
-        serde_json::to_string(&CrateData {
-            doc: crate_doc,
-            items: crate_items,
-            paths: crate_paths,
-            aliases,
-        })
+        to_js_json(&CrateEntry { doc: crate_doc, shards: shards.keys().map(|k| &**k).collect() })
+    );
+    SearchIndex { entry, shards: files }
+}
+
+/// Serializes `data` to JSON, escaped to be put in a JavaScript string.
+fn to_js_json<T: Serialize>(data: &T) -> String {
+    serde_json::to_string(data)
         .expect("failed serde conversion")
         // All these `replace` calls are because we have to go through JS string for JSON content.
         .replace(r"\", r"\\")
         .replace("'", r"\'")
         // We need to escape double quotes for the JSON.
         .replace("\\\"", "\\\\\"")
-    )
 }
 
 crate fn get_index_search_type(item: &clean::Item) -> Option<IndexItemFunctionType> {
@@ -26,6 +26,7 @@
 //! both occur before the crate is rendered.
 
 pub mod cache;
+crate mod span_map;
 
 #[cfg(test)]
 mod tests;
@@ -80,7 +81,8 @@ use crate::html::format::{print_abi_with_space, Buffer, PrintWithSpace};
 use crate::html::markdown::{self, ErrorCodes, IdMap, Markdown, MarkdownHtml, MarkdownSummaryLine};
 use crate::html::sources;
 use crate::html::{highlight, layout, static_files};
-use cache::{build_index, ExternalLocation};
+use crate::scrape_examples::CallSnippet;
+use cache::{build_index, ExternalLocation, SearchIndex};
 
 /// A pair of name and its optional document.
 pub type NameDoc = (String, Option<String>);
@@ -379,7 +381,7 @@ impl FormatRenderer for Context {
     fn init(
         mut krate: clean::Crate,
         options: RenderOptions,
-        _render_info: RenderInfo,
+        render_info: RenderInfo,
         edition: Edition,
         cache: &mut Cache,
     ) -> Result<(Context, clean::Crate), Error> {
@@ -396,6 +398,7 @@ impl FormatRenderer for Context {
             resource_suffix,
             static_root_path,
             generate_search_filter,
+            shard_search_index,
             ..
         } = options;
 
@@ -483,10 +486,10 @@ impl FormatRenderer for Context {
 
         let dst = output;
         scx.ensure_dir(&dst)?;
-        krate = sources::render(&dst, &mut scx, krate)?;
+        krate = sources::render(&dst, &mut scx, krate, &render_info.span_map, cache)?;
 
         // Build our search index
-        let index = build_index(&krate, cache);
+        let index = build_index(&krate, cache, shard_search_index);
 
         let cache = Arc::new(cache);
         let mut cx = Context {
@@ -677,7 +680,7 @@ impl FormatRenderer for Context {
 fn write_shared(
     cx: &Context,
     krate: &clean::Crate,
-    search_index: String,
+    search_index: SearchIndex,
     options: &RenderOptions,
     cache: &Cache,
 ) -> Result<(), Error> {
@@ -1021,7 +1024,7 @@ themePicker.onblur = handleThemeButtonsBlur;
     // Update the search index
     let dst = cx.dst.join(&format!("search-index{}.js", cx.shared.resource_suffix));
     let (mut all_indexes, mut krates) = try_err!(collect_json(&dst, &krate.name), &dst);
-    all_indexes.push(search_index);
+    all_indexes.push(search_index.entry);
 
     // Sort the indexes by crate so the file will be generated identically even
     // with rustdoc running in parallel.
@@ -1034,6 +1037,20 @@ themePicker.onblur = handleThemeButtonsBlur;
         v.push_str("\\\n}');\naddSearchOptions(searchIndex);initSearch(searchIndex);");
         cx.shared.fs.write(&dst, &v)?;
     }
+
+    // The shards of the index of the crate, if it's split. The previous ones are removed in any
+    // case, as they may not all be overwritten.
+    let shards_dir = cx.dst.join("search-index").join(&krate.name);
+    if shards_dir.exists() {
+        try_err!(fs::remove_dir_all(&shards_dir), &shards_dir);
+    }
+    if !search_index.shards.is_empty() {
+        cx.shared.ensure_dir(&shards_dir)?;
+        for (key, shard) in &search_index.shards {
+            let dst = shards_dir.join(&format!("{}{}.js", key, cx.shared.resource_suffix));
+            cx.shared.fs.write(&dst, shard.as_bytes())?;
+        }
+    }
     if options.enable_index_page {
         if let Some(index_page) = options.index_page.clone() {
             let mut md_opts = options.clone();
@@ -1691,7 +1708,7 @@ fn print_item(cx: &Context, item: &clean::Item, buf: &mut Buffer, cache: &Cache)
     match item.inner {
         clean::ModuleItem(ref m) => item_module(buf, cx, item, &m.items),
         clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
-            item_function(buf, cx, item, f)
+            item_function(buf, cx, item, f, cache)
         }
         clean::TraitItem(ref t) => item_trait(buf, cx, item, t, cache),
         clean::StructItem(ref s) => item_struct(buf, cx, item, s, cache),
@@ -1854,6 +1871,63 @@ fn document_stability(w: &mut Buffer, cx: &Context, item: &clean::Item, is_hidde
     }
 }
 
+/// The number of scraped examples shown before the rest are folded.
+const NUM_VISIBLE_EXAMPLES: usize = 3;
+
+/// Renders the snippets of the scraped examples that call `item`.
+fn render_call_locations(w: &mut Buffer, item: &clean::Item, cache: &Cache) {
+    let snippets = match cache.call_locations.get(&item.def_id) {
+        Some(snippets) if !snippets.is_empty() => snippets,
+        _ => return,
+    };
+    write!(
+        w,
+        "<div class=\"docblock scraped-examples\">\
+            <h4 class=\"scraped-examples-title\">Examples found in repository</h4>"
+    );
+    let (visible, hidden) = snippets.split_at(snippets.len().min(NUM_VISIBLE_EXAMPLES));
+    for snippet in visible {
+        render_call_snippet(w, snippet);
+    }
+    if !hidden.is_empty() {
+        write!(w, "<details class=\"more-scraped-examples\"><summary>More examples</summary>");
+        for snippet in hidden {
+            render_call_snippet(w, snippet);
+        }
+        write!(w, "</details>");
+    }
+    write!(w, "</div>");
+}
+
+/// Renders a snippet of a scraped example, highlighting the lines with the calls.
+fn render_call_snippet(w: &mut Buffer, snippet: &CallSnippet) {
+    let call_lines: FxHashSet<usize> = snippet
+        .calls
+        .iter()
+        .filter_map(|&(lo, _)| snippet.code.get(..lo as usize))
+        .map(|before| before.matches('\n').count())
+        .collect();
+    write!(
+        w,
+        "<div class=\"scraped-example\">\
+            <div class=\"scraped-example-title\">{} (line {})</div>\
+            <div class=\"code-wrapper\">\
+                <pre class=\"line-numbers\">",
+        Escape(&snippet.file),
+        snippet.line
+    );
+    for i in 0..snippet.code.lines().count() {
+        if call_lines.contains(&i) {
+            write!(w, "<span class=\"line-highlighted\">{}</span>\n", snippet.line + i);
+        } else {
+            write!(w, "<span>{}</span>\n", snippet.line + i);
+        }
+    }
+    write!(w, "</pre>");
+    w.write_str(&highlight::render_with_highlighting(snippet.code.clone(), None, None, None, None));
+    write!(w, "</div></div>");
+}
+
 fn document_non_exhaustive_header(item: &clean::Item) -> &str {
     if item.is_non_exhaustive() { " (Non-exhaustive)" } else { "" }
 }
@@ -2295,7 +2369,13 @@ fn item_static(w: &mut Buffer, cx: &Context, it: &clean::Item, s: &clean::Static
     document(w, cx, it)
 }
 
-fn item_function(w: &mut Buffer, cx: &Context, it: &clean::Item, f: &clean::Function) {
+fn item_function(
+    w: &mut Buffer,
+    cx: &Context,
+    it: &clean::Item,
+    f: &clean::Function,
+    cache: &Cache,
+) {
     let header_len = format!(
         "{}{}{}{}{:#}fn {}{:#}",
         it.visibility.print_with_space(),
@@ -2325,7 +2405,8 @@ fn item_function(w: &mut Buffer, cx: &Context, it: &clean::Item, f: &clean::Func
             .print(),
         spotlight = spotlight_decl(&f.decl),
     );
-    document(w, cx, it)
+    document(w, cx, it);
+    render_call_locations(w, it, cache)
 }
 
 fn render_implementor(
@@ -3696,6 +3777,7 @@ fn render_impl(
                     document_stability(w, cx, item, is_hidden);
                     if show_def_docs {
                         document_full(w, item, cx, "", is_hidden);
+                        render_call_locations(w, item, cache);
                     }
                 }
             } else {
@@ -4412,7 +4494,7 @@ fn sidebar_enum(buf: &mut Buffer, it: &clean::Item, e: &clean::Enum) {
     }
 }
 
-fn item_ty_to_strs(ty: &ItemType) -> (&'static str, &'static str) {
+crate fn item_ty_to_strs(ty: &ItemType) -> (&'static str, &'static str) {
     match *ty {
         ItemType::ExternCrate | ItemType::Import => ("reexports", "Re-exports"),
         ItemType::Module => ("modules", "Modules"),
@@ -4507,6 +4589,7 @@ fn item_macro(w: &mut Buffer, cx: &Context, it: &clean::Item, t: &clean::Macro)
             Some("macro"),
             None,
             None,
+            None,
         ))
     });
     document(w, cx, it)
@@ -0,0 +1,229 @@
+//! Collects the definitions that the identifiers of the local source files refer to, so that the
+//! rendered source pages can link them to their definitions.
+//!
+//! The links come from the resolved paths of the HIR, which covers uses of items and local
+//! variables. Method calls, field accesses and type-relative paths like `Vec::new` are only
+//! resolved by type-checking, which rustdoc doesn't otherwise do: they're linked in the bodies that
+//! type-check, while the other bodies fall back to their resolved paths.
+
+use crate::clean;
+use crate::core::DocContext;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::{self as hir, HirId, Item, ItemKind, Path};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{self, TypeckResults};
+use rustc_span::{FileName, Span};
+
+use std::mem;
+use std::path::PathBuf;
+
+/// Where an identifier of the source code links to.
+#[derive(Clone, Debug)]
+crate enum LinkFromSrc {
+    /// A definition in the local crate, which links to its line in the source pages.
+    Local { file: PathBuf, line: usize },
+    /// An item of another crate, which links to its documentation.
+    External(DefId),
+}
+
+/// The links of each local source file, keyed by the byte range of the identifier in the file.
+crate type SpanMap = FxHashMap<PathBuf, FxHashMap<(u32, u32), LinkFromSrc>>;
+
+/// Walks the HIR of the local crate to find where its identifiers link to.
+crate fn collect_spans(cx: &DocContext<'_>) -> SpanMap {
+    let mut collector = SpanMapVisitor { cx, maybe_typeck_results: None, map: SpanMap::default() };
+    intravisit::walk_crate(&mut collector, cx.tcx.hir().krate());
+    collector.map
+}
+
+struct SpanMapVisitor<'a, 'tcx> {
+    cx: &'a DocContext<'tcx>,
+    /// The results of type-checking the current body, if it type-checks.
+    maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    map: SpanMap,
+}
+
+impl<'a, 'tcx> SpanMapVisitor<'a, 'tcx> {
+    /// Returns the file and line of `span`, if it's in a file of the local crate.
+    fn file_and_line(&self, span: Span) -> Option<(PathBuf, usize)> {
+        if span.is_dummy() {
+            return None;
+        }
+        let loc = self.cx.sess().source_map().lookup_char_pos(span.source_callsite().lo());
+        match &loc.file.name {
+            FileName::Real(name) if loc.file.cnum == LOCAL_CRATE => {
+                Some((name.local_path().to_path_buf(), loc.line))
+            }
+            _ => None,
+        }
+    }
+
+    fn add_link(&mut self, span: Span, link: LinkFromSrc) {
+        // Code coming from macro expansions or desugarings isn't in the source as written.
+        if span.from_expansion() || span.is_dummy() {
+            return;
+        }
+        let source_map = self.cx.sess().source_map();
+        let lo = source_map.lookup_byte_offset(span.lo());
+        let path = match &lo.sf.name {
+            FileName::Real(name) => name.local_path().to_path_buf(),
+            _ => return,
+        };
+        let hi = lo.pos + (span.hi() - span.lo());
+        self.map.entry(path).or_default().insert((lo.pos.0, hi.0), link);
+    }
+
+    /// Finds what the identifier at `span`, resolved to `res`, links to.
+    fn link_res(&mut self, span: Span, res: Res) {
+        let link = match res {
+            Res::Local(hir_id) => match self.file_and_line(self.cx.tcx.hir().span(hir_id)) {
+                Some((file, line)) => LinkFromSrc::Local { file, line },
+                None => return,
+            },
+            Res::Def(_, def_id) if def_id.is_local() => {
+                let def_span = match self.cx.tcx.hir().span_if_local(def_id) {
+                    Some(def_span) => def_span,
+                    None => return,
+                };
+                match self.file_and_line(def_span) {
+                    Some((file, line)) => LinkFromSrc::Local { file, line },
+                    None => return,
+                }
+            }
+            Res::Def(..) => match self.documented_res(res) {
+                Some(res) => LinkFromSrc::External(clean::utils::register_res(self.cx, res)),
+                None => return,
+            },
+            _ => return,
+        };
+        self.add_link(span, link);
+    }
+
+    /// Links the identifier at `span` to the item `def_id`, found by type-checking.
+    fn link_def_id(&mut self, span: Span, def_id: DefId) {
+        self.link_res(span, Res::Def(self.cx.tcx.def_kind(def_id), def_id));
+    }
+
+    /// Returns the item whose documentation page documents the external `res`: constructors
+    /// and fields are documented with their type, trait items with their trait, and inherent
+    /// associated items with the type of their impl.
+    fn documented_res(&self, res: Res) -> Option<Res> {
+        let tcx = self.cx.tcx;
+        match res {
+            Res::Def(DefKind::Ctor(..) | DefKind::Field, def_id) => {
+                let parent = tcx.parent(def_id)?;
+                self.documented_res(Res::Def(tcx.def_kind(parent), parent))
+            }
+            Res::Def(DefKind::AssocTy | DefKind::AssocFn | DefKind::AssocConst, def_id) => {
+                if let Some(trait_def_id) = tcx.trait_of_item(def_id) {
+                    return Some(Res::Def(DefKind::Trait, trait_def_id));
+                }
+                let adt = tcx.type_of(tcx.impl_of_method(def_id)?).ty_adt_def()?;
+                self.documented_res(Res::Def(tcx.def_kind(adt.did), adt.did))
+            }
+            Res::Def(
+                DefKind::Fn
+                | DefKind::TyAlias
+                | DefKind::Enum
+                | DefKind::Variant
+                | DefKind::Trait
+                | DefKind::TraitAlias
+                | DefKind::Struct
+                | DefKind::Union
+                | DefKind::Mod
+                | DefKind::ForeignTy
+                | DefKind::Const
+                | DefKind::Static
+                | DefKind::Macro(..),
+                _,
+            ) => Some(res),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for SpanMapVisitor<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::All(self.cx.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body_id: hir::BodyId) {
+        let tcx = self.cx.tcx;
+        // Type-checking a body can only succeed if the crate had no errors so far. Rustdoc doesn't
+        // type-check bodies otherwise (they may be meant for another platform), so their errors
+        // are discarded and only bodies that type-check get their method calls linked.
+        let typeck_results = if tcx.sess.has_errors() {
+            None
+        } else {
+            let def_id = tcx.hir().body_owner_def_id(body_id);
+            match tcx.sess.diagnostic().with_diagnostics_discarded(|| tcx.typeck(def_id)) {
+                (typeck_results, false) if typeck_results.tainted_by_errors.is_none() => {
+                    Some(typeck_results)
+                }
+                _ => None,
+            }
+        };
+        let old_maybe_typeck_results = mem::replace(&mut self.maybe_typeck_results, typeck_results);
+        self.visit_body(tcx.hir().body(body_id));
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(typeck_results) = self.maybe_typeck_results {
+            match expr.kind {
+                hir::ExprKind::MethodCall(segment, ..)
+                | hir::ExprKind::Path(hir::QPath::TypeRelative(_, segment)) => {
+                    if let Some(def_id) = typeck_results.type_dependent_def_id(expr.hir_id) {
+                        self.link_def_id(segment.ident.span, def_id);
+                    }
+                }
+                hir::ExprKind::Field(base, ident) => {
+                    let index = typeck_results.field_indices().get(expr.hir_id).copied();
+                    let base_ty = typeck_results.expr_ty_adjusted(base).peel_refs();
+                    if let (Some(index), ty::Adt(adt, _)) = (index, &base_ty.kind) {
+                        let field = &adt.non_enum_variant().fields[index];
+                        self.link_def_id(ident.span, field.did);
+                    }
+                }
+                _ => {}
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+
+    fn visit_path(&mut self, path: &'tcx Path<'tcx>, _id: HirId) {
+        let last = path.segments.len().saturating_sub(1);
+        for (i, segment) in path.segments.iter().enumerate() {
+            let res = match segment.res {
+                Some(res) if res != Res::Err => res,
+                // The resolution of the last segment is the one of the whole path.
+                _ if i == last => path.res,
+                _ => continue,
+            };
+            self.link_res(segment.ident.span, res);
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_item(&mut self, item: &'tcx Item<'tcx>) {
+        // Link `mod foo;` to the file of the module.
+        if let ItemKind::Mod(ref module) = item.kind {
+            let source_map = self.cx.sess().source_map();
+            if !module.inner.is_dummy()
+                && source_map.lookup_byte_offset(module.inner.lo()).sf.start_pos
+                    != source_map.lookup_byte_offset(item.span.lo()).sf.start_pos
+            {
+                if let Some((file, _)) = self.file_and_line(module.inner) {
+                    self.add_link(item.ident.span, LinkFromSrc::Local { file, line: 1 });
+                }
+            }
+        }
+        intravisit::walk_item(self, item);
+    }
+}
@@ -2,12 +2,16 @@ use crate::clean;
 use crate::docfs::PathError;
 use crate::error::Error;
 use crate::fold::DocFolder;
-use crate::html::format::Buffer;
-use crate::html::highlight;
+use crate::formats::cache::Cache;
+use crate::html::format::{href_with_root_path, Buffer};
+use crate::html::highlight::{self, SourceLinks};
 use crate::html::layout;
+use crate::html::render::span_map::{LinkFromSrc, SpanMap};
 use crate::html::render::{SharedContext, BASIC_KEYWORDS};
+use rustc_data_structures::fx::FxHashMap;
 use rustc_hir::def_id::LOCAL_CRATE;
-use rustc_span::source_map::FileName;
+use rustc_span::source_map::{FileName, RealFileName};
+use std::collections::BTreeSet;
 use std::ffi::OsStr;
 use std::fs;
 use std::path::{Component, Path, PathBuf};
@@ -16,12 +20,35 @@ crate fn render(
     dst: &Path,
     scx: &mut SharedContext,
     krate: clean::Crate,
+    span_map: &SpanMap,
+    cache: &Cache,
 ) -> Result<clean::Crate, Error> {
     info!("emitting source files");
-    let dst = dst.join("src").join(&krate.name);
+    let crate_name = krate.name.clone();
+    let dst = dst.join("src").join(&crate_name);
     scx.ensure_dir(&dst)?;
-    let mut folder = SourceCollector { dst, scx };
-    Ok(folder.fold_crate(krate))
+    let mut folder = SourceCollector { dst, scx, crate_name, span_map, cache };
+    let krate = folder.fold_crate(krate);
+
+    // Jump-to-definition links can lead to files that don't contain any documented item, so
+    // make sure those are rendered as well.
+    let linked_files: BTreeSet<&PathBuf> = span_map
+        .iter()
+        .flat_map(|(file, links)| {
+            let targets = links.values().filter_map(|link| match link {
+                LinkFromSrc::Local { file, .. } => Some(file),
+                LinkFromSrc::External(_) => None,
+            });
+            Some(file).into_iter().chain(targets)
+        })
+        .collect();
+    for file in linked_files {
+        if !folder.scx.include_sources {
+            break;
+        }
+        folder.emit_source_or_warn(&FileName::Real(RealFileName::Named(file.clone())));
+    }
+    Ok(krate)
 }
 
 /// Helper struct to render all source code to HTML pages
@@ -30,6 +57,11 @@ struct SourceCollector<'a> {
 
     /// Root destination to place all HTML output into
     dst: PathBuf,
+
+    crate_name: String,
+    /// The jump-to-definition links of the source files.
+    span_map: &'a SpanMap,
+    cache: &'a Cache,
 }
 
 impl<'a> DocFolder for SourceCollector<'a> {
@@ -42,29 +74,33 @@ impl<'a> DocFolder for SourceCollector<'a> {
             // skip non-local files
             && item.source.cnum == LOCAL_CRATE
         {
-            // If it turns out that we couldn't read this file, then we probably
-            // can't read any of the files (generating html output from json or
-            // something like that), so just don't include sources for the
-            // entire crate. The other option is maintaining this mapping on a
-            // per-file basis, but that's probably not worth it...
-            self.scx.include_sources = match self.emit_source(&item.source.filename) {
-                Ok(()) => true,
-                Err(e) => {
-                    println!(
-                        "warning: source code was requested to be rendered, \
-                              but processing `{}` had an error: {}",
-                        item.source.filename, e
-                    );
-                    println!("         skipping rendering of source code");
-                    false
-                }
-            };
+            self.emit_source_or_warn(&item.source.filename);
         }
         self.fold_item_recur(item)
     }
 }
 
 impl<'a> SourceCollector<'a> {
+    fn emit_source_or_warn(&mut self, filename: &FileName) {
+        // If it turns out that we couldn't read this file, then we probably
+        // can't read any of the files (generating html output from json or
+        // something like that), so just don't include sources for the
+        // entire crate. The other option is maintaining this mapping on a
+        // per-file basis, but that's probably not worth it...
+        self.scx.include_sources = match self.emit_source(filename) {
+            Ok(()) => true,
+            Err(e) => {
+                println!(
+                    "warning: source code was requested to be rendered, \
+                          but processing `{}` had an error: {}",
+                    filename, e
+                );
+                println!("         skipping rendering of source code");
+                false
+            }
+        };
+    }
+
     /// Renders the given filename into its corresponding HTML source file.
     fn emit_source(&mut self, filename: &FileName) -> Result<(), Error> {
         let p = match *filename {
@@ -91,24 +127,22 @@ impl<'a> SourceCollector<'a> {
         // Create the intermediate directories
         let mut cur = self.dst.clone();
         let mut root_path = String::from("../../");
-        let mut href = String::new();
         clean_path(&self.scx.src_root, &p, false, |component| {
             cur.push(component);
             root_path.push_str("../");
-            href.push_str(&component.to_string_lossy());
-            href.push('/');
         });
         self.scx.ensure_dir(&cur)?;
         let mut fname = p.file_name().expect("source has no filename").to_os_string();
         fname.push(".html");
         cur.push(&fname);
-        href.push_str(&fname.to_string_lossy());
+        let href = source_href(&self.scx.src_root, &p);
 
         let title = format!(
             "{} -- source",
             cur.file_name().expect("failed to get file name").to_string_lossy()
         );
         let desc = format!("Source to the Rust file `{}`.", filename);
+        let links = self.span_map.get(&p).map(|links| self.source_links(links, &root_path));
         let page = layout::Page {
             title: &title,
             css_class: "source",
@@ -124,13 +158,53 @@ impl<'a> SourceCollector<'a> {
             &self.scx.layout,
             &page,
             "",
-            |buf: &mut _| print_src(buf, contents),
+            |buf: &mut _| print_src(buf, contents, links.as_ref()),
             &self.scx.style_files,
         );
         self.scx.fs.write(&cur, v.as_bytes())?;
         self.scx.local_sources.insert(p, href);
         Ok(())
     }
+
+    /// Computes the URLs of the jump-to-definition links of a source page, whose path to the
+    /// documentation root is `root_path`.
+    fn source_links(
+        &self,
+        links: &FxHashMap<(u32, u32), LinkFromSrc>,
+        root_path: &str,
+    ) -> SourceLinks {
+        links
+            .iter()
+            .filter_map(|(&range, link)| {
+                let href = match link {
+                    LinkFromSrc::Local { file, line } => format!(
+                        "{}src/{}/{}#{}",
+                        root_path,
+                        self.crate_name,
+                        source_href(&self.scx.src_root, file),
+                        line
+                    ),
+                    LinkFromSrc::External(def_id) => {
+                        href_with_root_path(*def_id, self.cache, root_path)?.0
+                    }
+                };
+                Some((range, href))
+            })
+            .collect()
+    }
+}
+
+/// Returns the path of the source page of the file `p`, relative to the source directory of the
+/// crate.
+fn source_href(src_root: &Path, p: &Path) -> String {
+    let mut href = String::new();
+    clean_path(src_root, p, false, |component| {
+        href.push_str(&component.to_string_lossy());
+        href.push('/');
+    });
+    href.push_str(&p.file_name().expect("source has no filename").to_string_lossy());
+    href.push_str(".html");
+    href
 }
 
 /// Takes a path to a source file and cleans the path to it. This canonicalizes
@@ -162,7 +236,7 @@ where
 
 /// Wrapper struct to render the source code of a file. This will do things like
 /// adding line numbers to the left-hand side.
-fn print_src(buf: &mut Buffer, s: String) {
+fn print_src(buf: &mut Buffer, s: String, links: Option<&SourceLinks>) {
     let lines = s.lines().count();
     let mut cols = 0;
     let mut tmp = lines;
@@ -175,5 +249,5 @@ fn print_src(buf: &mut Buffer, s: String) {
         write!(buf, "<span id=\"{0}\">{0:1$}</span>\n", i, cols);
     }
     write!(buf, "</pre>");
-    write!(buf, "{}", highlight::render_with_highlighting(s, None, None, None));
+    write!(buf, "{}", highlight::render_with_highlighting(s, None, None, None, links));
 }
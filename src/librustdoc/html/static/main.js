@@ -567,6 +567,9 @@ function defocusSearchBar() {
         var NO_TYPE_FILTER = -1;
         var currentResults, index, searchIndex;
         var ALIASES = {};
+        // The crates whose index is split into shards, which are loaded when searching.
+        var shards = {};
+        var shardSearchTimeout = null;
         var params = getQueryStringParams();
 
         // Populate search bar with query string search term when provided,
@@ -1742,20 +1745,159 @@ function defocusSearchBar() {
             }
 
             var filterCrates = getFilterCrates();
+            loadShards(query, filterCrates);
             showResults(execSearch(query, index, filterCrates));
         }
 
+        /**
+         * Returns the key of the shard holding the items with the given name. It has to match
+         * `shard_key` in `html/render/cache.rs`.
+         */
+        function shardKey(name) {
+            var c = name.charAt(0).toLowerCase();
+            return /^[a-z0-9]$/.test(c) ? c : "_";
+        }
+
+        function loadShard(crate, key) {
+            var crateShards = shards[crate];
+            if (crateShards.requested[key] === true) {
+                return;
+            }
+            crateShards.requested[key] = true;
+            var script = document.createElement("script");
+            script.src = rootPath + "search-index/" + crate + "/" + key +
+                (window.resourceSuffix || "") + ".js";
+            // The shards are run in the order they're requested, so that the `paths` one, which
+            // the others need, comes first.
+            script.async = false;
+            document.head.appendChild(script);
+        }
+
+        /**
+         * Loads the shards of the searched crates that aren't loaded yet: the one holding the
+         * items named like the query first, then all the others, as the searched name may be the
+         * one of a type in the signatures of any item. The results are updated as they arrive.
+         */
+        function loadShards(query, filterCrates) {
+            var name = query.query.split(":").pop().trim();
+            for (var crate in shards) {
+                if (!hasOwnProperty(shards, crate) ||
+                        (filterCrates !== undefined && crate !== filterCrates)) {
+                    continue;
+                }
+                var keys = shards[crate].keys;
+                loadShard(crate, "paths");
+                if (keys.indexOf(shardKey(name)) !== -1) {
+                    loadShard(crate, shardKey(name));
+                }
+                for (var i = 0; i < keys.length; ++i) {
+                    loadShard(crate, keys[i]);
+                }
+            }
+        }
+
+        window.loadSearchShard = function(crate, key, data) {
+            var crateShards = shards[crate];
+            if (!crateShards) {
+                return;
+            }
+            if (key === "paths") {
+                var paths = data.p;
+                for (var i = 0; i < paths.length; ++i) {
+                    paths[i] = {ty: paths[i][0], name: paths[i][1]};
+                }
+                crateShards.paths = paths;
+                crateShards.modules = data.m;
+                return;
+            }
+            addItems(crate, data.i, crateShards.paths, data.a, crateShards.modules, index);
+            // Many shards may arrive at once, so the search is only run again once they stop.
+            clearTimeout(shardSearchTimeout);
+            shardSearchTimeout = setTimeout(function() {
+                if (search_input.value.length !== 0) {
+                    search(undefined, true);
+                }
+            }, 100);
+        };
+
+        /**
+         * Adds the items of a crate to the search index.
+         *
+         * `modules` is the table of the module paths of the items of a sharded index, which they
+         * refer to by index. Otherwise, an empty path is the one of the previous item.
+         */
+        function addItems(crate, items, paths, aliases, modules, searchWords) {
+            // an array of [(Number) item type,
+            //              (String) name,
+            //              (String) full path or empty string for previous path,
+            //              (String) description,
+            //              (Number | null) the parent path index to `paths`]
+            //              (Object | null) the type of the function (if any)
+            var currentIndex = searchIndex.length;
+            var len = items.length;
+            var lastPath = "";
+            var i;
+
+            // convert `items` into an object form, and construct word indices.
+            //
+            // before any analysis is performed lets gather the search terms to
+            // search against apart from the rest of the data.  This is a quick
+            // operation that is cached for the life of the page state so that
+            // all other search operations have access to this cached data for
+            // faster analysis operations
+            for (i = 0; i < len; ++i) {
+                var rawRow = items[i];
+                if (modules) {
+                    rawRow[2] = modules[rawRow[2]];
+                } else if (!rawRow[2]) {
+                    rawRow[2] = lastPath;
+                }
+                var row = {
+                    crate: crate,
+                    ty: rawRow[0],
+                    name: rawRow[1],
+                    path: rawRow[2],
+                    desc: rawRow[3],
+                    parent: paths[rawRow[4]],
+                    type: rawRow[5],
+                };
+                searchIndex.push(row);
+                if (typeof row.name === "string") {
+                    var word = row.name.toLowerCase();
+                    searchWords.push(word);
+                } else {
+                    searchWords.push("");
+                }
+                lastPath = row.path;
+            }
+
+            if (aliases) {
+                if (!ALIASES.hasOwnProperty(crate)) {
+                    ALIASES[crate] = {};
+                }
+                var j, local_aliases;
+                for (var alias_name in aliases) {
+                    if (!aliases.hasOwnProperty(alias_name)) { continue; }
+
+                    if (!ALIASES[crate].hasOwnProperty(alias_name)) {
+                        ALIASES[crate][alias_name] = [];
+                    }
+                    local_aliases = aliases[alias_name];
+                    for (j = 0; j < local_aliases.length; ++j) {
+                        ALIASES[crate][alias_name].push(local_aliases[j] + currentIndex);
+                    }
+                }
+            }
+        }
+
         function buildIndex(rawSearchIndex) {
             searchIndex = [];
             var searchWords = [];
             var i;
-            var currentIndex = 0;
 
             for (var crate in rawSearchIndex) {
                 if (!hasOwnProperty(rawSearchIndex, crate)) { continue; }
 
-                var crateSize = 0;
-
                 searchWords.push(crate);
                 searchIndex.push({
                     crate: crate,
@@ -1765,15 +1907,19 @@ function defocusSearchBar() {
                     desc: rawSearchIndex[crate].doc,
                     type: null,
                 });
-                currentIndex += 1;
 
-                // an array of [(Number) item type,
-                //              (String) name,
-                //              (String) full path or empty string for previous path,
-                //              (String) description,
-                //              (Number | null) the parent path index to `paths`]
-                //              (Object | null) the type of the function (if any)
-                var items = rawSearchIndex[crate].i;
+                // an array of the keys of the shards of the index of the crate, if it's split
+                var shardKeys = rawSearchIndex[crate].s;
+                if (shardKeys) {
+                    shards[crate] = {
+                        keys: shardKeys,
+                        requested: {},
+                        paths: [],
+                        modules: [],
+                    };
+                    continue;
+                }
+
                 // an array of [(Number) item type,
                 //              (String) name]
                 var paths = rawSearchIndex[crate].p;
@@ -1787,56 +1933,7 @@ function defocusSearchBar() {
                     paths[i] = {ty: paths[i][0], name: paths[i][1]};
                 }
 
-                // convert `items` into an object form, and construct word indices.
-                //
-                // before any analysis is performed lets gather the search terms to
-                // search against apart from the rest of the data.  This is a quick
-                // operation that is cached for the life of the page state so that
-                // all other search operations have access to this cached data for
-                // faster analysis operations
-                len = items.length;
-                var lastPath = "";
-                for (i = 0; i < len; ++i) {
-                    var rawRow = items[i];
-                    if (!rawRow[2]) {
-                        rawRow[2] = lastPath;
-                    }
-                    var row = {
-                        crate: crate,
-                        ty: rawRow[0],
-                        name: rawRow[1],
-                        path: rawRow[2],
-                        desc: rawRow[3],
-                        parent: paths[rawRow[4]],
-                        type: rawRow[5],
-                    };
-                    searchIndex.push(row);
-                    if (typeof row.name === "string") {
-                        var word = row.name.toLowerCase();
-                        searchWords.push(word);
-                    } else {
-                        searchWords.push("");
-                    }
-                    lastPath = row.path;
-                    crateSize += 1;
-                }
-
-                if (aliases) {
-                    ALIASES[crate] = {};
-                    var j, local_aliases;
-                    for (var alias_name in aliases) {
-                        if (!aliases.hasOwnProperty(alias_name)) { continue; }
-
-                        if (!ALIASES[crate].hasOwnProperty(alias_name)) {
-                            ALIASES[crate][alias_name] = [];
-                        }
-                        local_aliases = aliases[alias_name];
-                        for (j = 0; j < local_aliases.length; ++j) {
-                            ALIASES[crate][alias_name].push(local_aliases[j] + currentIndex);
-                        }
-                    }
-                }
-                currentIndex += crateSize;
+                addItems(crate, rawSearchIndex[crate].i, paths, aliases, null, searchWords);
             }
             return searchWords;
         }
@@ -58,6 +58,7 @@ use rustc_session::{early_error, early_warn};
 mod externalfiles;
 
 mod clean;
+mod commonmark;
 mod config;
 mod core;
 mod docfs;
@@ -71,6 +72,8 @@ pub mod html;
 mod json;
 mod markdown;
 mod passes;
+mod scrape_examples;
+mod semver;
 mod theme;
 mod visit_ast;
 mod visit_lib;
@@ -354,6 +357,65 @@ fn opts() -> Vec<RustcOptGroup> {
                 "disables generating the crate selector on the search box",
             )
         }),
+        unstable("generate-link-to-definition", |o| {
+            o.optflag(
+                "",
+                "generate-link-to-definition",
+                "make the identifiers in source code pages link to their definition",
+            )
+        }),
+        unstable("shard-search-index", |o| {
+            o.optflag(
+                "",
+                "shard-search-index",
+                "split the search index into files that are only loaded when searching",
+            )
+        }),
+        unstable("scrape-examples-output-path", |o| {
+            o.optopt(
+                "",
+                "scrape-examples-output-path",
+                "collect the calls this crate makes to the functions of other crates into PATH, \
+                 instead of documenting it",
+                "PATH",
+            )
+        }),
+        unstable("scrape-examples-target-crate", |o| {
+            o.optmulti(
+                "",
+                "scrape-examples-target-crate",
+                "crate whose functions' calls to collect with --scrape-examples-output-path",
+                "CRATE",
+            )
+        }),
+        unstable("scrape-tests", |o| {
+            o.optflag("", "scrape-tests", "also collect the calls made by the crate's tests")
+        }),
+        unstable("with-examples", |o| {
+            o.optmulti(
+                "",
+                "with-examples",
+                "show the calls collected with --scrape-examples-output-path in the documentation",
+                "PATH",
+            )
+        }),
+        unstable("save-api-model", |o| {
+            o.optopt(
+                "",
+                "save-api-model",
+                "save a model of the public API of the crate into PATH, instead of documenting it",
+                "PATH",
+            )
+        }),
+        unstable("semver-baseline", |o| {
+            o.optopt(
+                "",
+                "semver-baseline",
+                "report the API changes from the model saved in PATH, and the version bump they \
+                 need, instead of documenting the crate",
+                "PATH",
+            )
+        }),
         unstable("persist-doctests", |o| {
             o.optopt(
                 "",
@@ -369,6 +431,29 @@ fn opts() -> Vec<RustcOptGroup> {
                 "calculate percentage of public items with documentation",
             )
         }),
+        unstable("coverage-details", |o| {
+            o.optflag(
+                "",
+                "coverage-details",
+                "with --show-coverage, list the items without documentation or examples",
+            )
+        }),
+        unstable("coverage-threshold", |o| {
+            o.optopt(
+                "",
+                "coverage-threshold",
+                "with --show-coverage, fail if less than PERCENT of the items are documented",
+                "PERCENT",
+            )
+        }),
+        unstable("coverage-examples-threshold", |o| {
+            o.optopt(
+                "",
+                "coverage-examples-threshold",
+                "with --show-coverage, fail if less than PERCENT of the items have examples",
+                "PERCENT",
+            )
+        }),
         unstable("enable-per-target-ignores", |o| {
             o.optflag(
                 "",
@@ -376,6 +461,25 @@ fn opts() -> Vec<RustcOptGroup> {
                 "parse ignore-foo for ignoring doctests on a per-target basis",
             )
         }),
+        unstable("merge-doctests", |o| {
+            o.optflag("", "merge-doctests", "compile doctests that allow it into a single binary")
+        }),
+        unstable("doctest-filter", |o| {
+            o.optmulti(
+                "",
+                "doctest-filter",
+                "only run the doctests of the items at or under PATH",
+                "PATH",
+            )
+        }),
+        unstable("doctest-events", |o| {
+            o.optopt(
+                "",
+                "doctest-events",
+                "write a JSON event for each doctest that starts and finishes to PATH",
+                "PATH",
+            )
+        }),
         unstable("runtool", |o| {
             o.optopt(
                 "",
@@ -486,10 +590,18 @@ fn main_options(options: config::Options) -> MainResult {
         (false, false) => {}
     }
 
+    if let Some(semver_options) = &options.semver_options {
+        if options.api_model_input() {
+            return wrap_return(&diag, semver::run_with_model(&options.input, semver_options));
+        }
+    }
+
     // need to move these items separately because we lose them by the time the closure is called,
     // but we can't crates the Handler ahead of time because it's not Send
     let diag_opts = (options.error_format, options.edition, options.debugging_opts.clone());
     let show_coverage = options.show_coverage;
+    let scrape_examples = options.scrape_examples_options.is_some();
+    let semver_options = options.semver_options.clone();
 
     // First, parse the crate and extract all relevant information.
     info!("starting to run rustc");
@@ -511,6 +623,10 @@ fn main_options(options: config::Options) -> MainResult {
 
     krate.version = crate_version;
 
+    if let Some(semver_options) = semver_options {
+        return wrap_return(&diag, semver::run(&krate, &semver_options));
+    }
+
     let out = Output { krate, renderinfo, renderopts };
 
     if show_coverage {
@@ -519,6 +635,11 @@ fn main_options(options: config::Options) -> MainResult {
         return Ok(());
     }
 
+    if scrape_examples {
+        // The calls were written out while the crate was analyzed, there are no docs to generate.
+        return Ok(());
+    }
+
     let Output { krate, renderinfo, renderopts } = out;
     info!("going to format");
     let (error_format, edition, debugging_options) = diag_opts;
@@ -530,5 +651,10 @@ fn main_options(options: config::Options) -> MainResult {
         Some(config::OutputFormat::Json) => sess.time("render_json", || {
             run_renderer::<json::JsonRenderer>(krate, renderopts, renderinfo, &diag, edition)
         }),
+        Some(config::OutputFormat::Markdown) => sess.time("render_markdown", || {
+            run_renderer::<commonmark::MarkdownRenderer>(
+                krate, renderopts, renderinfo, &diag, edition,
+            )
+        }),
     }
 }
@@ -136,6 +136,7 @@ pub fn test(mut options: Options) -> Result<(), String> {
     find_testable_code(&input_str, &mut collector, codes, options.enable_per_target_ignores, None);
 
     options.test_args.insert(0, "rustdoctest".to_string());
+    collector.run_merged_doctests(&options.test_args);
     testing::test_main(
         &options.test_args,
         collector.tests,
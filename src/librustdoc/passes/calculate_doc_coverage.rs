@@ -5,11 +5,16 @@ use crate::fold::{self, DocFolder};
 use crate::html::markdown::{find_testable_code, ErrorCodes};
 use crate::passes::doc_test_lints::{should_have_doc_example, Tests};
 use crate::passes::Pass;
+use rustc_errors::Handler;
+use rustc_hir::def_id::DefId;
+use rustc_middle::ty::TyCtxt;
+use rustc_session::getopts;
 use rustc_span::symbol::sym;
 use rustc_span::FileName;
 use serde::Serialize;
 
 use std::collections::BTreeMap;
+use std::mem;
 use std::ops;
 
 pub const CALCULATE_DOC_COVERAGE: Pass = Pass {
@@ -18,12 +23,87 @@ pub const CALCULATE_DOC_COVERAGE: Pass = Pass {
     description: "counts the number of items with and without documentation",
 };
 
+/// Options for `--show-coverage`.
+#[derive(Clone, Debug, Default)]
+crate struct CoverageOptions {
+    /// Whether to list the items without documentation or examples, given by `--coverage-details`.
+    crate show_details: bool,
+    /// The percentage of documented items below which the run fails.
+    crate threshold: Option<f64>,
+    /// The percentage of items with examples below which the run fails.
+    crate examples_threshold: Option<f64>,
+}
+
+impl CoverageOptions {
+    crate fn new(
+        matches: &getopts::Matches,
+        diag: &Handler,
+        show_coverage: bool,
+    ) -> Result<CoverageOptions, i32> {
+        let show_details = matches.opt_present("coverage-details");
+        let threshold = parse_threshold(matches, diag, "coverage-threshold")?;
+        let examples_threshold = parse_threshold(matches, diag, "coverage-examples-threshold")?;
+        if !show_coverage && (show_details || threshold.is_some() || examples_threshold.is_some()) {
+            diag.struct_err(
+                "must use --show-coverage with --coverage-details, --coverage-threshold and \
+                 --coverage-examples-threshold",
+            )
+            .emit();
+            return Err(1);
+        }
+        Ok(CoverageOptions { show_details, threshold, examples_threshold })
+    }
+}
+
+fn parse_threshold(
+    matches: &getopts::Matches,
+    diag: &Handler,
+    name: &str,
+) -> Result<Option<f64>, i32> {
+    match matches.opt_str(name) {
+        Some(s) => match s.parse::<f64>() {
+            Ok(threshold) if (0.0..=100.0).contains(&threshold) => Ok(Some(threshold)),
+            _ => {
+                diag.struct_err(&format!(
+                    "--{} must be a percentage between 0 and 100, found `{}`",
+                    name, s
+                ))
+                .emit();
+                Err(1)
+            }
+        },
+        None => Ok(None),
+    }
+}
+
 fn calculate_doc_coverage(krate: clean::Crate, ctx: &DocContext<'_>) -> clean::Crate {
-    let mut calc = CoverageCalculator::new();
+    let options = ctx.renderinfo.borrow().coverage_options.clone();
+    let mut calc = CoverageCalculator::new(ctx.tcx, krate.name.clone(), options.show_details);
     let krate = calc.fold_crate(krate);
 
     calc.print_results(ctx.renderinfo.borrow().output_format);
 
+    let total = calc.total();
+    if let Some(threshold) = options.threshold {
+        // A crate without any item to document is fully documented.
+        let percentage = total.percentage().unwrap_or(100.0);
+        if percentage < threshold {
+            ctx.sess().err(&format!(
+                "{:.1}% of the items are documented, which is below the threshold of {}%",
+                percentage, threshold
+            ));
+        }
+    }
+    if let Some(threshold) = options.examples_threshold {
+        let percentage = total.examples_percentage().unwrap_or(100.0);
+        if percentage < threshold {
+            ctx.sess().err(&format!(
+                "{:.1}% of the items have examples, which is below the threshold of {}%",
+                percentage, threshold
+            ));
+        }
+    }
+
     krate
 }
 
@@ -94,8 +174,37 @@ impl ops::AddAssign for ItemCount {
     }
 }
 
-struct CoverageCalculator {
+/// An item without documentation or examples, listed with `--coverage-details`.
+#[derive(Serialize, Debug)]
+struct MissingItem {
+    path: String,
+    line: usize,
+    column: usize,
+}
+
+#[derive(Default, Serialize, Debug)]
+struct MissingItems {
+    undocumented: Vec<MissingItem>,
+    missing_examples: Vec<MissingItem>,
+}
+
+/// The coverage of a file, as printed in JSON.
+#[derive(Serialize)]
+struct FileCoverage<'a> {
+    #[serde(flatten)]
+    count: &'a ItemCount,
+    #[serde(flatten)]
+    missing: Option<&'a MissingItems>,
+}
+
+struct CoverageCalculator<'tcx> {
+    tcx: TyCtxt<'tcx>,
     items: BTreeMap<FileName, ItemCount>,
+    /// The items without documentation or examples of each file, if they're listed.
+    missing: Option<BTreeMap<FileName, MissingItems>>,
+    /// The path of the parent of the current item. Inside an impl, it's the path of the type the
+    /// impl is for instead of the module containing the impl.
+    path: Vec<String>,
 }
 
 fn limit_filename_len(filename: String) -> String {
@@ -108,9 +217,44 @@ fn limit_filename_len(filename: String) -> String {
     }
 }
 
-impl CoverageCalculator {
-    fn new() -> CoverageCalculator {
-        CoverageCalculator { items: Default::default() }
+impl<'tcx> CoverageCalculator<'tcx> {
+    fn new(tcx: TyCtxt<'tcx>, crate_name: String, show_details: bool) -> CoverageCalculator<'tcx> {
+        CoverageCalculator {
+            tcx,
+            items: Default::default(),
+            missing: if show_details { Some(Default::default()) } else { None },
+            path: vec![crate_name],
+        }
+    }
+
+    /// Returns the path of the item `def_id`, starting with the name of its crate.
+    fn def_path(&self, def_id: DefId) -> String {
+        format!(
+            "{}{}",
+            self.tcx.crate_name(def_id.krate),
+            self.tcx.def_path(def_id).to_string_no_crate()
+        )
+    }
+
+    /// Returns the path the items of `impl_` are listed under: the path of the type the impl is
+    /// for, like `krate::Type`, or `<krate::Type as krate::Trait>` for a trait impl.
+    fn impl_path(&self, impl_: &clean::Impl) -> String {
+        let type_path = |type_: &clean::Type| match *type_ {
+            clean::ResolvedPath { did, is_generic: false, .. } => self.def_path(did),
+            _ => format!("{:#}", type_.print()),
+        };
+        match impl_.trait_ {
+            Some(ref trait_) => format!("<{} as {}>", type_path(&impl_.for_), type_path(trait_)),
+            None => type_path(&impl_.for_),
+        }
+    }
+
+    fn total(&self) -> ItemCount {
+        let mut total = ItemCount::default();
+        for &count in self.items.values() {
+            total += count;
+        }
+        total
     }
 
     fn to_json(&self) -> String {
@@ -118,18 +262,39 @@ impl CoverageCalculator {
             &self
                 .items
                 .iter()
-                .map(|(k, v)| (k.to_string(), v))
-                .collect::<BTreeMap<String, &ItemCount>>(),
+                .map(|(k, v)| {
+                    let missing = self.missing.as_ref().and_then(|missing| missing.get(k));
+                    (k.to_string(), FileCoverage { count: v, missing })
+                })
+                .collect::<BTreeMap<String, FileCoverage<'_>>>(),
         )
         .expect("failed to convert JSON data to string")
     }
 
+    fn print_missing_items(&self, title: &str, items: impl Fn(&MissingItems) -> &Vec<MissingItem>) {
+        let missing = match self.missing {
+            Some(ref missing) => missing,
+            None => return,
+        };
+        let count: usize = missing.values().map(|m| items(m).len()).sum();
+        if count == 0 {
+            return;
+        }
+        println!();
+        println!("{} ({}):", title, count);
+        for (file, m) in missing {
+            for item in items(m) {
+                println!("    {} ({}:{}:{})", item.path, file, item.line, item.column);
+            }
+        }
+    }
+
     fn print_results(&self, output_format: Option<OutputFormat>) {
         if output_format.map(|o| o.is_json()).unwrap_or_else(|| false) {
             println!("{}", self.to_json());
             return;
         }
-        let mut total = ItemCount::default();
+        let total = self.total();
 
         fn print_table_line() {
             println!("+-{0:->35}-+-{0:->10}-+-{0:->10}-+-{0:->10}-+-{0:->10}-+", "");
@@ -162,8 +327,6 @@ impl CoverageCalculator {
                     percentage,
                     count.examples_percentage().unwrap_or(0.),
                 );
-
-                total += count;
             }
         }
 
@@ -175,11 +338,19 @@ impl CoverageCalculator {
             total.examples_percentage().unwrap_or(0.0),
         );
         print_table_line();
+
+        self.print_missing_items("Undocumented items", |m| &m.undocumented);
+        self.print_missing_items("Items without examples", |m| &m.missing_examples);
     }
 }
 
-impl fold::DocFolder for CoverageCalculator {
+impl fold::DocFolder for CoverageCalculator<'_> {
     fn fold_item(&mut self, i: clean::Item) -> Option<clean::Item> {
+        let name = match i.inner {
+            clean::ImplItem(_) => None,
+            _ => i.name.clone().filter(|name| !name.is_empty()),
+        };
+
         match i.inner {
             _ if !i.def_id.is_local() => {
                 // non-local items are skipped because they can be out of the users control,
@@ -240,15 +411,47 @@ impl fold::DocFolder for CoverageCalculator {
                 );
 
                 let has_doc_example = tests.found_tests != 0;
+                let should_have_doc_example = should_have_doc_example(&i.inner);
                 debug!("counting {:?} {:?} in {}", i.type_(), i.name, i.source.filename);
                 self.items.entry(i.source.filename.clone()).or_default().count_item(
                     has_docs,
                     has_doc_example,
-                    should_have_doc_example(&i.inner),
+                    should_have_doc_example,
                 );
+
+                if let Some(ref mut missing) = self.missing {
+                    let missing = missing.entry(i.source.filename.clone()).or_default();
+                    let path = &self.path;
+                    let item = || MissingItem {
+                        path: path.iter().chain(&name).cloned().collect::<Vec<_>>().join("::"),
+                        line: i.source.loline,
+                        column: i.source.locol + 1,
+                    };
+                    if !has_docs {
+                        missing.undocumented.push(item());
+                    }
+                    if should_have_doc_example && !has_doc_example {
+                        missing.missing_examples.push(item());
+                    }
+                }
             }
         }
 
-        self.fold_item_recur(i)
+        if let clean::ImplItem(ref impl_) = i.inner {
+            let impl_path = vec![self.impl_path(impl_)];
+            let old_path = mem::replace(&mut self.path, impl_path);
+            let ret = self.fold_item_recur(i);
+            self.path = old_path;
+            return ret;
+        }
+
+        if let Some(ref name) = name {
+            self.path.push(name.clone());
+        }
+        let ret = self.fold_item_recur(i);
+        if name.is_some() {
+            self.path.pop();
+        }
+        ret
     }
 }
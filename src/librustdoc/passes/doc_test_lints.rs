@@ -55,7 +55,7 @@ impl Tests {
 }
 
 impl crate::doctest::Tester for Tests {
-    fn add_test(&mut self, _: String, _: LangString, _: usize) {
+    fn add_test(&mut self, _: String, _: LangString, _: usize, _: usize) {
         self.found_tests += 1;
     }
 }
@@ -43,6 +43,7 @@ mod check_code_block_syntax;
 pub use self::check_code_block_syntax::CHECK_CODE_BLOCK_SYNTAX;
 
 mod calculate_doc_coverage;
+crate use self::calculate_doc_coverage::CoverageOptions;
 pub use self::calculate_doc_coverage::CALCULATE_DOC_COVERAGE;
 
 /// A single pass over the cleaned documentation.
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
+use rustc_ast::NestedMetaItem;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_span::symbol::sym;
+
 use crate::clean::cfg::Cfg;
-use crate::clean::{Crate, Item};
+use crate::clean::{Attributes, AttributesExt, Crate, Item};
 use crate::core::DocContext;
 use crate::fold::DocFolder;
 use crate::passes::Pass;
@@ -9,28 +13,97 @@ use crate::passes::Pass;
 pub const PROPAGATE_DOC_CFG: Pass = Pass {
     name: "propagate-doc-cfg",
     run: propagate_doc_cfg,
-    description: "propagates `#[doc(cfg(...))]` to child items",
+    description: "propagates `#[doc(cfg(...))]` and `#[cfg(...)]` to child items",
 };
 
-pub fn propagate_doc_cfg(cr: Crate, _: &DocContext<'_>) -> Crate {
-    CfgPropagator { parent_cfg: None }.fold_crate(cr)
+pub fn propagate_doc_cfg(cr: Crate, cx: &DocContext<'_>) -> Crate {
+    // With `#![feature(doc_auto_cfg)]`, the `#[cfg]` attributes of the items are shown as well,
+    // except for the ones hidden by `#![doc(cfg_hide(...))]`.
+    let hidden_cfg = if cx.tcx.features().doc_auto_cfg {
+        Some(cr.module.as_ref().map(|m| hidden_cfg(cx, &m.attrs)).unwrap_or_default())
+    } else {
+        None
+    };
+    CfgPropagator { parent_cfg: None, hidden_cfg }.fold_crate(cr)
+}
+
+/// Returns the configuration options hidden with `#![doc(cfg_hide(...))]`, along with `doc` and
+/// `doctest`, which are only set by rustdoc.
+fn hidden_cfg(cx: &DocContext<'_>, attrs: &Attributes) -> FxHashSet<Cfg> {
+    let mut hidden = FxHashSet::default();
+    hidden.insert(Cfg::Cfg(sym::doc, None));
+    hidden.insert(Cfg::Cfg(sym::doctest, None));
+    for attr in attrs.other_attrs.lists(sym::doc).filter(|a| a.has_name(sym::cfg_hide)) {
+        for nested in attr.meta_item_list().unwrap_or(&[]) {
+            match nested.meta_item().map(Cfg::parse) {
+                Some(Ok(cfg)) => {
+                    hidden.insert(cfg);
+                }
+                Some(Err(e)) => cx.sess().diagnostic().span_err(e.span, e.msg),
+                None => cx.sess().diagnostic().span_err(nested.span(), "unexpected literal"),
+            }
+        }
+    }
+    hidden
+}
+
+/// Returns the configuration given by the `#[cfg]` attributes of an item, without the hidden
+/// options.
+fn auto_cfg(attrs: &Attributes, hidden: &FxHashSet<Cfg>) -> Option<Cfg> {
+    let cfg = attrs
+        .other_attrs
+        .iter()
+        .filter(|attr| attr.has_name(sym::cfg))
+        .filter_map(|attr| match attr.meta_item_list()?.as_slice() {
+            [NestedMetaItem::MetaItem(mi)] => Cfg::parse(mi).ok(),
+            _ => None,
+        })
+        .fold(Cfg::True, |x, y| x & y);
+    cfg.strip_hidden(hidden)
+}
+
+/// Whether the item has an explicit `#[doc(cfg(...))]`, which overrides its `#[cfg]` attributes.
+fn has_doc_cfg(attrs: &Attributes) -> bool {
+    attrs.other_attrs.iter().any(|attr| {
+        attr.has_name(sym::doc)
+            && attr.meta().map_or(false, |mi| Attributes::extract_cfg(&mi).is_some())
+    })
 }
 
 struct CfgPropagator {
     parent_cfg: Option<Arc<Cfg>>,
+    /// The hidden configuration options, if the `#[cfg]` attributes are shown.
+    hidden_cfg: Option<FxHashSet<Cfg>>,
 }
 
 impl DocFolder for CfgPropagator {
     fn fold_item(&mut self, mut item: Item) -> Option<Item> {
         let old_parent_cfg = self.parent_cfg.clone();
 
+        if let Some(ref hidden_cfg) = self.hidden_cfg {
+            if !has_doc_cfg(&item.attrs) {
+                if let Some(cfg) = auto_cfg(&item.attrs, hidden_cfg) {
+                    let cfg = match item.attrs.cfg.take() {
+                        Some(rc) => Cfg::clone(&rc) & cfg,
+                        None => cfg,
+                    };
+                    if cfg != Cfg::True {
+                        item.attrs.cfg = Some(Arc::new(cfg));
+                    }
+                }
+            }
+        }
+
         let new_cfg = match (self.parent_cfg.take(), item.attrs.cfg.take()) {
             (None, None) => None,
-            (Some(rc), None) | (None, Some(rc)) => Some(rc),
-            (Some(mut a), Some(b)) => {
+            (Some(rc), None) => Some(rc),
+            (None, Some(b)) => {
+                let b = Arc::try_unwrap(b).unwrap_or_else(|rc| Cfg::clone(&rc));
+                Some(Arc::new(b.simplify()))
+            }
+            (Some(a), Some(b)) => {
                 let b = Arc::try_unwrap(b).unwrap_or_else(|rc| Cfg::clone(&rc));
-                *Arc::make_mut(&mut a) &= b;
-                Some(a)
+                Some(Arc::new((Cfg::clone(&a) & b).simplify()))
             }
         };
         self.parent_cfg = new_cfg.clone();
@@ -0,0 +1,264 @@
+//! Finds the calls to a crate's functions in other crates, like its examples, so that they can be
+//! shown in its documentation.
+//!
+//! This happens in two steps. First, rustdoc is run on each example with
+//! `--scrape-examples-output-path`, which type-checks the example and writes the calls it makes to
+//! the functions of the `--scrape-examples-target-crate` crates into a file. Then, the documented
+//! crate is given those files with `--with-examples`, and the snippets containing the calls are
+//! rendered below the documentation of the called functions.
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_errors::Handler;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::DefId;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
+use rustc_hir::{self as hir, HirId};
+use rustc_middle::hir::map::Map;
+use rustc_middle::ty::{TyCtxt, TypeckResults};
+use rustc_span::{FileName, Span};
+use serde::{Deserialize, Serialize};
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Options for scraping the calls of an example, given by `--scrape-examples-output-path`.
+#[derive(Clone, Debug)]
+crate struct ScrapeExamplesOptions {
+    /// Where to write the calls that were found.
+    crate output_path: PathBuf,
+    /// The crates whose functions are looked for.
+    crate target_crates: Vec<String>,
+    /// Whether to also look into the tests of the scraped crate.
+    crate scrape_tests: bool,
+}
+
+impl ScrapeExamplesOptions {
+    crate fn new(matches: &getopts::Matches, diag: &Handler) -> Result<Option<Self>, i32> {
+        let output_path = matches.opt_str("scrape-examples-output-path");
+        let target_crates = matches.opt_strs("scrape-examples-target-crate");
+        let scrape_tests = matches.opt_present("scrape-tests");
+        match output_path {
+            Some(_) if target_crates.is_empty() => {
+                diag.struct_err(
+                    "must use --scrape-examples-target-crate with --scrape-examples-output-path",
+                )
+                .emit();
+                Err(1)
+            }
+            Some(output_path) => Ok(Some(ScrapeExamplesOptions {
+                output_path: PathBuf::from(output_path),
+                target_crates,
+                scrape_tests,
+            })),
+            None if !target_crates.is_empty() || scrape_tests => {
+                diag.struct_err(
+                    "must use --scrape-examples-output-path with --scrape-examples-target-crate \
+                     and --scrape-tests",
+                )
+                .emit();
+                Err(1)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// A snippet of an example that calls a documented function.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+crate struct CallSnippet {
+    /// The example file the snippet comes from.
+    crate file: String,
+    /// The line of `file` that `code` starts at.
+    crate line: usize,
+    /// The source of the item that contains the calls.
+    crate code: String,
+    /// The byte ranges of the called names in `code`.
+    crate calls: Vec<(u32, u32)>,
+}
+
+/// The snippets calling each function, keyed by the path of the function.
+crate type AllCallLocations = FxHashMap<String, Vec<CallSnippet>>;
+
+/// Returns the key of the function `def_id` in `AllCallLocations`. It's the same when the function
+/// is looked up from the crate defining it and from the example calling it.
+fn function_key(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    format!("{}{}", tcx.crate_name(def_id.krate), tcx.def_path(def_id).to_string_no_crate())
+}
+
+/// Finds the calls of the crate being compiled to the functions of the target crates, and writes
+/// them to the output path.
+crate fn run(tcx: TyCtxt<'_>, options: &ScrapeExamplesOptions) {
+    let mut finder =
+        FindCalls { tcx, options, maybe_typeck_results: None, calls: FxHashMap::default() };
+    tcx.hir().krate().visit_all_item_likes(&mut finder.as_deep_visitor());
+
+    let mut call_locations = AllCallLocations::default();
+    for ((key, _, _), snippet) in finder.calls {
+        call_locations.entry(key).or_default().push(snippet);
+    }
+    let result = serde_json::to_string(&call_locations)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(&options.output_path, json).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        tcx.sess.fatal(&format!(
+            "failed to write the scraped examples to `{}`: {}",
+            options.output_path.display(),
+            e
+        ));
+    }
+}
+
+struct FindCalls<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    options: &'a ScrapeExamplesOptions,
+    maybe_typeck_results: Option<&'tcx TypeckResults<'tcx>>,
+    /// The snippets found so far, keyed by the called function and by the file and start of the
+    /// snippet.
+    calls: FxHashMap<(String, String, u32), CallSnippet>,
+}
+
+impl<'a, 'tcx> FindCalls<'a, 'tcx> {
+    fn record_call(&mut self, call_id: HirId, callee_span: Span, def_id: DefId) {
+        let tcx = self.tcx;
+        let crate_name = tcx.crate_name(def_id.krate);
+        if !self.options.target_crates.iter().any(|krate| *krate == *crate_name.as_str()) {
+            return;
+        }
+        if callee_span.from_expansion() {
+            return;
+        }
+
+        // The snippet is the whole item the call is in.
+        let item_span = tcx.hir().span(tcx.hir().get_parent_item(call_id));
+        if item_span.from_expansion() || !item_span.contains(callee_span) {
+            return;
+        }
+        let source_map = tcx.sess.source_map();
+        let loc = source_map.lookup_char_pos(item_span.lo());
+        let file = match &loc.file.name {
+            FileName::Real(name) => {
+                let path = name.local_path();
+                let path = env::current_dir()
+                    .ok()
+                    .and_then(|cur_dir| path.strip_prefix(cur_dir).ok().map(|p| p.to_owned()))
+                    .unwrap_or_else(|| path.to_owned());
+                path.display().to_string()
+            }
+            _ => return,
+        };
+        let code = match source_map.span_to_snippet(item_span) {
+            Ok(code) => code,
+            Err(_) => return,
+        };
+
+        let offset = item_span.lo().0;
+        let range = (callee_span.lo().0 - offset, callee_span.hi().0 - offset);
+        self.calls
+            .entry((function_key(tcx, def_id), file.clone(), offset))
+            .or_insert_with(|| CallSnippet { file, line: loc.line, code, calls: Vec::new() })
+            .calls
+            .push(range);
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for FindCalls<'a, 'tcx> {
+    type Map = Map<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::OnlyBodies(self.tcx.hir())
+    }
+
+    fn visit_nested_body(&mut self, body_id: hir::BodyId) {
+        let old_maybe_typeck_results = self
+            .maybe_typeck_results
+            .replace(self.tcx.typeck(self.tcx.hir().body_owner_def_id(body_id)));
+        let body = self.tcx.hir().body(body_id);
+        self.visit_body(body);
+        self.maybe_typeck_results = old_maybe_typeck_results;
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(typeck_results) = self.maybe_typeck_results {
+            match expr.kind {
+                hir::ExprKind::Call(callee, _) => {
+                    if let hir::ExprKind::Path(ref qpath) = callee.kind {
+                        let res = typeck_results.qpath_res(qpath, callee.hir_id);
+                        if let (Res::Def(DefKind::Fn | DefKind::AssocFn, def_id), Some(span)) =
+                            (res, callee_name_span(qpath))
+                        {
+                            self.record_call(expr.hir_id, span, def_id);
+                        }
+                    }
+                }
+                hir::ExprKind::MethodCall(segment, ..) => {
+                    if let Some(def_id) = typeck_results.type_dependent_def_id(expr.hir_id) {
+                        self.record_call(expr.hir_id, segment.ident.span, def_id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Returns the span of the name of the function called through `qpath`.
+fn callee_name_span(qpath: &hir::QPath<'_>) -> Option<Span> {
+    match qpath {
+        hir::QPath::Resolved(_, path) => path.segments.last().map(|segment| segment.ident.span),
+        hir::QPath::TypeRelative(_, segment) => Some(segment.ident.span),
+        hir::QPath::LangItem(..) => None,
+    }
+}
+
+/// Loads the calls given with `--with-examples`.
+crate fn load_call_locations(
+    with_examples: Vec<String>,
+    diag: &Handler,
+) -> Result<AllCallLocations, i32> {
+    let mut all_calls = AllCallLocations::default();
+    for path in with_examples {
+        let calls = fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|json| {
+            serde_json::from_str::<AllCallLocations>(&json).map_err(|e| e.to_string())
+        });
+        match calls {
+            Ok(calls) => {
+                for (function, snippets) in calls {
+                    all_calls.entry(function).or_default().extend(snippets);
+                }
+            }
+            Err(e) => {
+                diag.struct_err(&format!("failed to load examples from `{}`: {}", path, e)).emit();
+                return Err(1);
+            }
+        }
+    }
+    for snippets in all_calls.values_mut() {
+        snippets.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    }
+    Ok(all_calls)
+}
+
+/// Finds the calls to the functions of the local crate, for `Cache::call_locations`.
+crate fn resolve_call_locations(
+    tcx: TyCtxt<'_>,
+    call_locations: &AllCallLocations,
+) -> FxHashMap<DefId, Vec<CallSnippet>> {
+    let krate = tcx.hir().krate();
+    let hir_ids = krate
+        .items
+        .keys()
+        .chain(krate.trait_items.keys().map(|id| &id.hir_id))
+        .chain(krate.impl_items.keys().map(|id| &id.hir_id));
+    hir_ids
+        .filter_map(|&hir_id| {
+            let def_id = tcx.hir().local_def_id(hir_id).to_def_id();
+            if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+                return None;
+            }
+            let snippets = call_locations.get(&function_key(tcx, def_id))?;
+            Some((def_id, snippets.clone()))
+        })
+        .collect()
+}
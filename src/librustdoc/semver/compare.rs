@@ -0,0 +1,221 @@
+//! Classifies the differences between two models of a crate's public API.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use super::model::{ApiItem, ApiMember, ApiModel};
+
+/// The version bump a change needs.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+crate enum Category {
+    /// The change doesn't touch the API, like a documentation change.
+    Patch,
+    /// The change extends the API without breaking its users.
+    Minor,
+    /// The change can break code using the previous version.
+    Breaking,
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Category::Patch => "patch",
+            Category::Minor => "minor",
+            Category::Breaking => "major",
+        })
+    }
+}
+
+/// A change of the public API.
+#[derive(Clone, PartialEq, Eq, Debug)]
+crate struct ApiChange {
+    crate category: Category,
+    /// The kind and path of the changed item, like `fn foo::bar`.
+    crate item: String,
+    crate description: String,
+}
+
+/// Returns the changes from `old` to `new`, with the breaking changes first.
+crate fn compare(old: &ApiModel, new: &ApiModel) -> Vec<ApiChange> {
+    let mut changes = Changes::default();
+    for (key, old_item) in &old.items {
+        match new.items.get(key) {
+            Some(new_item) => changes.compare_items(key, old_item, new_item),
+            None => changes.push(Category::Breaking, key, "removed".to_string()),
+        }
+    }
+    for key in new.items.keys().filter(|key| !old.items.contains_key(*key)) {
+        changes.push(Category::Minor, key, "added".to_string());
+    }
+    let mut changes = changes.0;
+    changes.sort_by(|a, b| {
+        (Reverse(a.category), &a.item, &a.description).cmp(&(
+            Reverse(b.category),
+            &b.item,
+            &b.description,
+        ))
+    });
+    changes
+}
+
+/// Returns the version bump needed by `changes`.
+crate fn required_bump(changes: &[ApiChange]) -> Category {
+    changes.iter().map(|change| change.category).max().unwrap_or(Category::Patch)
+}
+
+#[derive(Default)]
+struct Changes(Vec<ApiChange>);
+
+impl Changes {
+    fn push(&mut self, category: Category, item: &str, description: String) {
+        self.0.push(ApiChange { category, item: item.to_string(), description });
+    }
+
+    fn compare_items(&mut self, key: &str, old: &ApiItem, new: &ApiItem) {
+        if old.signature != new.signature {
+            self.push(
+                Category::Breaking,
+                key,
+                format!("changed from `{}` to `{}`", old.signature, new.signature),
+            );
+        }
+        match (old.non_exhaustive, new.non_exhaustive) {
+            (false, true) => {
+                self.push(Category::Breaking, key, "now #[non_exhaustive]".to_string())
+            }
+            (true, false) => {
+                self.push(Category::Minor, key, "no longer #[non_exhaustive]".to_string())
+            }
+            _ => {}
+        }
+        match (old.has_private_members, new.has_private_members) {
+            (false, true) => self.push(
+                Category::Breaking,
+                key,
+                "has new private fields or hidden variants".to_string(),
+            ),
+            (true, false) => self.push(
+                Category::Minor,
+                key,
+                "no longer has private fields or hidden variants".to_string(),
+            ),
+            _ => {}
+        }
+        if !old.deprecated && new.deprecated {
+            self.push(Category::Minor, key, "deprecated".to_string());
+        }
+        if old.docs != new.docs {
+            self.push(Category::Patch, key, "documentation changed".to_string());
+        }
+
+        for (member, old_member) in &old.members {
+            match new.members.get(member) {
+                Some(new_member) => self.compare_members(key, member, old_member, new_member),
+                None => self.push(Category::Breaking, key, format!("`{}` removed", member)),
+            }
+        }
+        for (member, new_member) in &new.members {
+            if !old.members.contains_key(member) {
+                self.added_member(key, member, old, new, new_member);
+            }
+        }
+
+        self.compare_impls(key, old, new);
+    }
+
+    fn added_member(
+        &mut self,
+        key: &str,
+        member: &str,
+        old: &ApiItem,
+        new: &ApiItem,
+        new_member: &ApiMember,
+    ) {
+        let (category, description) = if key.starts_with("trait ") {
+            if new_member.required {
+                (Category::Breaking, format!("new required `{}`", member))
+            } else {
+                (Category::Minor, format!("new provided `{}`", member))
+            }
+        } else if key.starts_with("enum ") {
+            // Matches on an exhaustive enum have to handle the new variant.
+            if new.non_exhaustive {
+                (Category::Minor, format!("new `{}`", member))
+            } else {
+                (Category::Breaking, format!("new `{}` in an exhaustive enum", member))
+            }
+        } else if member.starts_with("field ") {
+            // Struct literals and patterns of a struct whose fields are all public and which
+            // isn't non-exhaustive have to list the new field.
+            if old.has_private_members || old.non_exhaustive {
+                (Category::Minor, format!("new `{}`", member))
+            } else {
+                (
+                    Category::Breaking,
+                    format!("new `{}` in a struct with only public fields", member),
+                )
+            }
+        } else {
+            (Category::Minor, format!("new `{}`", member))
+        };
+        self.push(category, key, description);
+    }
+
+    fn compare_members(&mut self, key: &str, member: &str, old: &ApiMember, new: &ApiMember) {
+        if old.signature != new.signature {
+            self.push(
+                Category::Breaking,
+                key,
+                format!("`{}` changed from `{}` to `{}`", member, old.signature, new.signature),
+            );
+        }
+        match (old.required, new.required) {
+            (false, true) => {
+                self.push(Category::Breaking, key, format!("`{}` no longer has a default", member))
+            }
+            (true, false) => self.push(Category::Minor, key, format!("`{}` has a default", member)),
+            _ => {}
+        }
+        match (old.non_exhaustive, new.non_exhaustive) {
+            (false, true) => {
+                self.push(Category::Breaking, key, format!("`{}` now #[non_exhaustive]", member))
+            }
+            (true, false) => {
+                self.push(Category::Minor, key, format!("`{}` no longer #[non_exhaustive]", member))
+            }
+            _ => {}
+        }
+        if !old.deprecated && new.deprecated {
+            self.push(Category::Minor, key, format!("`{}` deprecated", member));
+        }
+    }
+
+    fn compare_impls(&mut self, key: &str, old: &ApiItem, new: &ApiItem) {
+        for (trait_, old_impl) in &old.impls {
+            let new_impl = new.impls.get(trait_);
+            match (old_impl.negative, new_impl) {
+                (false, Some(new_impl)) if !new_impl.negative => {
+                    if old_impl.signature != new_impl.signature {
+                        self.push(
+                            Category::Breaking,
+                            key,
+                            format!("changed `{}` to `{}`", old_impl.signature, new_impl.signature),
+                        );
+                    }
+                }
+                (false, _) => {
+                    self.push(Category::Breaking, key, format!("lost `{}`", old_impl.signature))
+                }
+                (true, Some(new_impl)) if !new_impl.negative => {
+                    self.push(Category::Minor, key, format!("gained `{}`", new_impl.signature))
+                }
+                (true, _) => {}
+            }
+        }
+        for (trait_, new_impl) in &new.impls {
+            if !old.impls.contains_key(trait_) && !new_impl.negative {
+                self.push(Category::Minor, key, format!("gained `{}`", new_impl.signature));
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Checks the semver compatibility of a crate with a previous version of it.
+//!
+//! The public API of each version is summarized in an [`ApiModel`], which can be saved with
+//! `--save-api-model`. The model of the documented crate, or a saved model given as input, is then
+//! compared with the model given by `--semver-baseline`, and the changes are reported along with
+//! the version bump they need.
+
+mod compare;
+mod model;
+
+#[cfg(test)]
+mod tests;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rustc_errors::Handler;
+
+use crate::clean;
+
+use self::compare::{compare, required_bump, ApiChange, Category};
+use self::model::ApiModel;
+
+/// Options of the semver checker, given by `--save-api-model` and `--semver-baseline`.
+#[derive(Clone, Debug)]
+crate struct SemverOptions {
+    /// Where to save the API model of the crate.
+    crate save_model: Option<PathBuf>,
+    /// The saved API model of the previous version of the crate.
+    crate baseline: Option<PathBuf>,
+}
+
+impl SemverOptions {
+    crate fn new(matches: &getopts::Matches, diag: &Handler) -> Result<Option<Self>, i32> {
+        let save_model = matches.opt_str("save-api-model").map(PathBuf::from);
+        let baseline = matches.opt_str("semver-baseline").map(PathBuf::from);
+        if save_model.is_none() && baseline.is_none() {
+            return Ok(None);
+        }
+        if matches.opt_present("test") {
+            diag.struct_err("--save-api-model and --semver-baseline can't be used with --test")
+                .emit();
+            return Err(1);
+        }
+        Ok(Some(SemverOptions { save_model, baseline }))
+    }
+}
+
+/// Builds the API model of the documented crate, then saves it or compares it with the baseline.
+crate fn run(krate: &clean::Crate, options: &SemverOptions) -> Result<(), String> {
+    check(ApiModel::from_crate(krate), options)
+}
+
+/// Compares the API model saved in `input` with the baseline.
+crate fn run_with_model(input: &Path, options: &SemverOptions) -> Result<(), String> {
+    if options.baseline.is_none() {
+        return Err("an API model can only be given as input with --semver-baseline".to_string());
+    }
+    check(load_model(input)?, options)
+}
+
+fn check(model: ApiModel, options: &SemverOptions) -> Result<(), String> {
+    if let Some(path) = &options.save_model {
+        let json = serde_json::to_string(&model).map_err(|e| e.to_string())?;
+        fs::write(path, json)
+            .map_err(|e| format!("failed to save the API model to `{}`: {}", path.display(), e))?;
+    }
+    if let Some(path) = &options.baseline {
+        let baseline = load_model(path)?;
+        print_report(&baseline, &model, &compare(&baseline, &model));
+    }
+    Ok(())
+}
+
+fn load_model(path: &Path) -> Result<ApiModel, String> {
+    fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+        .map_err(|e| format!("failed to load the API model `{}`: {}", path.display(), e))
+}
+
+fn print_report(old: &ApiModel, new: &ApiModel, changes: &[ApiChange]) {
+    let version = |model: &ApiModel| model.version.clone().unwrap_or_else(|| "?".to_string());
+    println!(
+        "Comparing `{}` {} to `{}` {}",
+        old.crate_name,
+        version(old),
+        new.crate_name,
+        version(new)
+    );
+    for &(category, title) in &[
+        (Category::Breaking, "Breaking changes"),
+        (Category::Minor, "Minor changes"),
+        (Category::Patch, "Patch changes"),
+    ] {
+        let mut changes = changes.iter().filter(|change| change.category == category).peekable();
+        if changes.peek().is_none() {
+            continue;
+        }
+        println!("{}:", title);
+        for change in changes {
+            println!("    {}: {}", change.item, change.description);
+        }
+    }
+    if changes.is_empty() {
+        println!("No API changes");
+    }
+    println!("Required version bump: {}", required_bump(changes));
+}
@@ -0,0 +1,387 @@
+//! The public API of a crate, built from its cleaned AST.
+//!
+//! Everything is stored as plain-text signatures so that the model can be saved and compared with
+//! the model of another version of the crate, which doesn't share any `DefId` with this one.
+
+use std::collections::BTreeMap;
+
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::def_id::DefId;
+use rustc_span::hygiene::MacroKind;
+use serde::{Deserialize, Serialize};
+
+use crate::clean::{self, GetDefId};
+use crate::doctree::StructType;
+use crate::html::format::{
+    print_abi_with_space, print_generic_bounds, PrintWithSpace, WhereClause,
+};
+
+/// The public API of a crate.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+crate struct ApiModel {
+    crate crate_name: String,
+    /// The version of the crate, if given with `--crate-version`.
+    crate version: Option<String>,
+    /// The public items, keyed by their kind and path, like `struct foo::Bar`.
+    crate items: BTreeMap<String, ApiItem>,
+}
+
+/// A public item of a crate.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+crate struct ApiItem {
+    /// The declaration of the item, without its fields, variants or associated items.
+    crate signature: String,
+    crate docs: Option<String>,
+    crate deprecated: bool,
+    crate non_exhaustive: bool,
+    /// Whether some fields of a struct or union, or some variants of an enum, aren't public.
+    crate has_private_members: bool,
+    /// The fields, variants, trait items and inherent associated items, keyed by their kind and
+    /// name, like `fn new`.
+    crate members: BTreeMap<String, ApiMember>,
+    /// The trait implementations of a type, keyed by trait, or the implementations of a trait for
+    /// the types of other crates, keyed by type.
+    crate impls: BTreeMap<String, ApiImpl>,
+}
+
+/// A field, variant or associated item of a public item.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+crate struct ApiMember {
+    crate signature: String,
+    /// Whether the implementors of a trait have to provide this item.
+    crate required: bool,
+    crate deprecated: bool,
+    crate non_exhaustive: bool,
+}
+
+/// A trait implementation, including the synthesized auto trait implementations.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+crate struct ApiImpl {
+    crate signature: String,
+    /// Whether this is a negative implementation, like `impl !Send for Foo`.
+    crate negative: bool,
+}
+
+impl ApiModel {
+    crate fn from_crate(krate: &clean::Crate) -> ApiModel {
+        let mut builder = ModelBuilder::default();
+        if let Some(clean::ModuleItem(ref module)) = krate.module.as_ref().map(|m| &m.inner) {
+            for item in &module.items {
+                builder.add_item(item, &krate.name);
+            }
+        }
+        builder.add_impls();
+        ApiModel {
+            crate_name: krate.name.clone(),
+            version: krate.version.clone(),
+            items: builder.items,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ModelBuilder<'a> {
+    items: BTreeMap<String, ApiItem>,
+    /// The keys of the items in `items`, to find the types and traits of the impls.
+    keys: FxHashMap<DefId, String>,
+    impls: Vec<&'a clean::Impl>,
+}
+
+impl<'a> ModelBuilder<'a> {
+    fn add_item(&mut self, item: &'a clean::Item, parent: &str) {
+        let name = match (&item.name, &item.inner) {
+            (_, clean::StrippedItem(..)) => return,
+            (_, clean::ImplItem(ref impl_)) => {
+                self.impls.push(impl_);
+                return;
+            }
+            (_, clean::ImportItem(ref import)) => {
+                self.add_import(import, parent);
+                return;
+            }
+            (Some(name), _) => name,
+            (None, _) => return,
+        };
+        let path = format!("{}::{}", parent, name);
+        let mut api_item = ApiItem {
+            docs: item.collapsed_doc_value(),
+            deprecated: item.deprecation.is_some(),
+            non_exhaustive: item.is_non_exhaustive(),
+            ..ApiItem::default()
+        };
+        api_item.signature = match item.inner {
+            clean::ModuleItem(ref module) => {
+                for child in &module.items {
+                    self.add_item(child, &path);
+                }
+                format!("mod {}", name)
+            }
+            clean::FunctionItem(ref f) | clean::ForeignFunctionItem(ref f) => {
+                function_signature(name, &f.header, &f.generics, &f.decl)
+            }
+            clean::StructItem(ref s) => {
+                api_item.has_private_members = s.fields_stripped;
+                api_item.members = fields(&s.fields);
+                adt_signature("struct", name, s.struct_type, &s.generics)
+            }
+            clean::UnionItem(ref u) => {
+                api_item.has_private_members = u.fields_stripped;
+                api_item.members = fields(&u.fields);
+                adt_signature("union", name, u.struct_type, &u.generics)
+            }
+            clean::EnumItem(ref e) => {
+                api_item.has_private_members = e.variants_stripped;
+                api_item.members = e
+                    .variants
+                    .iter()
+                    .filter(|v| !v.is_stripped())
+                    .filter_map(|v| {
+                        let variant_name = v.name.as_ref()?;
+                        let signature = match v.inner {
+                            clean::VariantItem(ref variant) => {
+                                variant_signature(variant_name, variant)
+                            }
+                            _ => return None,
+                        };
+                        Some((format!("variant {}", variant_name), member(v, signature, false)))
+                    })
+                    .collect();
+                format!(
+                    "enum {}{:#}{:#}",
+                    name,
+                    e.generics.print(),
+                    WhereClause { gens: &e.generics, indent: 0, end_newline: false }
+                )
+            }
+            clean::TypedefItem(ref t, _) => format!(
+                "type {}{:#}{:#} = {:#}",
+                name,
+                t.generics.print(),
+                WhereClause { gens: &t.generics, indent: 0, end_newline: false },
+                t.type_.print()
+            ),
+            clean::OpaqueTyItem(ref t, _) => format!(
+                "type {}{:#} = impl {:#}",
+                name,
+                t.generics.print(),
+                print_generic_bounds(&t.bounds)
+            ),
+            clean::StaticItem(ref s) | clean::ForeignStaticItem(ref s) => {
+                format!("static {}{}: {:#}", s.mutability.print_with_space(), name, s.type_.print())
+            }
+            clean::ConstantItem(ref c) => format!("const {}: {:#}", name, c.type_.print()),
+            clean::TraitItem(ref t) => {
+                api_item.members = t
+                    .items
+                    .iter()
+                    .filter_map(|i| {
+                        let (key, signature, required) = assoc_item(i, true)?;
+                        Some((key, member(i, signature, required)))
+                    })
+                    .collect();
+                format!(
+                    "{}{}trait {}{:#}{}{:#}{:#}",
+                    t.unsafety.print_with_space(),
+                    if t.is_auto { "auto " } else { "" },
+                    name,
+                    t.generics.print(),
+                    if t.bounds.is_empty() { "" } else { ": " },
+                    print_generic_bounds(&t.bounds),
+                    WhereClause { gens: &t.generics, indent: 0, end_newline: false }
+                )
+            }
+            clean::TraitAliasItem(ref t) => format!(
+                "trait {}{:#} = {:#}",
+                name,
+                t.generics.print(),
+                print_generic_bounds(&t.bounds)
+            ),
+            clean::MacroItem(_) => format!("macro_rules! {}", name),
+            clean::ProcMacroItem(ref m) => match m.kind {
+                MacroKind::Bang => format!("{}!", name),
+                MacroKind::Attr => format!("#[{}]", name),
+                MacroKind::Derive if m.helpers.is_empty() => format!("#[derive({})]", name),
+                MacroKind::Derive => {
+                    format!("#[derive({})] with attributes {}", name, m.helpers.join(", "))
+                }
+            },
+            clean::ForeignTypeItem => format!("type {}", name),
+            _ => return,
+        };
+        let key = format!("{} {}", item.type_(), path);
+        self.keys.insert(item.def_id, key.clone());
+        self.items.insert(key, api_item);
+    }
+
+    fn add_import(&mut self, import: &clean::Import, parent: &str) {
+        let (key, source) = match import {
+            clean::Import::Simple(name, source) => {
+                (format!("use {}::{}", parent, name), path_name(&source.path))
+            }
+            clean::Import::Glob(source) => {
+                let source = format!("{}::*", path_name(&source.path));
+                (format!("use {}::{}", parent, source), source)
+            }
+        };
+        let signature = format!("pub use {}", source);
+        self.items.insert(key, ApiItem { signature, ..ApiItem::default() });
+    }
+
+    /// Adds the items of the inherent impls to the members of their types, and the trait impls to
+    /// the impls of their types or, for the types of other crates, of their traits.
+    fn add_impls(&mut self) {
+        for impl_ in std::mem::take(&mut self.impls) {
+            // Blanket impls like `impl<T> From<T> for T` come from other crates and apply to all
+            // types alike.
+            if impl_.blanket_impl.is_some() {
+                continue;
+            }
+            let type_key = impl_.for_.def_id().and_then(|did| self.keys.get(&did)).cloned();
+            let trait_ = match impl_.trait_ {
+                Some(ref trait_) => trait_,
+                None => {
+                    let item = match type_key.and_then(|key| self.items.get_mut(&key)) {
+                        Some(item) => item,
+                        None => continue,
+                    };
+                    for i in &impl_.items {
+                        if let Some((key, signature, _)) = assoc_item(i, false) {
+                            item.members.insert(key, member(i, signature, false));
+                        }
+                    }
+                    continue;
+                }
+            };
+            let api_impl = ApiImpl {
+                signature: format!("{:#}", impl_.print()),
+                negative: impl_.polarity == Some(clean::ImplPolarity::Negative),
+            };
+            let trait_key = trait_.def_id().and_then(|did| self.keys.get(&did)).cloned();
+            if let Some(item) = type_key.and_then(|key| self.items.get_mut(&key)) {
+                item.impls.insert(format!("{:#}", trait_.print()), api_impl);
+            } else if let Some(item) = trait_key.and_then(|key| self.items.get_mut(&key)) {
+                item.impls.insert(format!("{:#}", impl_.for_.print()), api_impl);
+            }
+        }
+    }
+}
+
+fn member(item: &clean::Item, signature: String, required: bool) -> ApiMember {
+    ApiMember {
+        signature,
+        required,
+        deprecated: item.deprecation.is_some(),
+        non_exhaustive: item.is_non_exhaustive(),
+    }
+}
+
+fn fields(fields: &[clean::Item]) -> BTreeMap<String, ApiMember> {
+    fields
+        .iter()
+        .filter_map(|field| match (&field.name, &field.inner) {
+            (Some(name), clean::StructFieldItem(ty)) => {
+                Some((format!("field {}", name), member(field, format!("{:#}", ty.print()), false)))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the key, signature and whether it's required of an associated item.
+fn assoc_item(item: &clean::Item, in_trait: bool) -> Option<(String, String, bool)> {
+    let name = item.name.as_ref()?;
+    let (key, signature, required) = match item.inner {
+        clean::TyMethodItem(ref m) => {
+            ("fn", function_signature(name, &m.header, &m.generics, &m.decl), true)
+        }
+        clean::MethodItem(ref m) => {
+            ("fn", function_signature(name, &m.header, &m.generics, &m.decl), false)
+        }
+        clean::AssocConstItem(ref ty, ref default) => {
+            ("const", format!("const {}: {:#}", name, ty.print()), default.is_none())
+        }
+        clean::AssocTypeItem(ref bounds, ref default) if in_trait => (
+            "type",
+            format!(
+                "type {}{}{:#}",
+                name,
+                if bounds.is_empty() { "" } else { ": " },
+                print_generic_bounds(bounds)
+            ),
+            default.is_none(),
+        ),
+        _ => return None,
+    };
+    Some((format!("{} {}", key, name), signature, in_trait && required))
+}
+
+fn function_signature(
+    name: &str,
+    header: &hir::FnHeader,
+    generics: &clean::Generics,
+    decl: &clean::FnDecl,
+) -> String {
+    format!(
+        "{}{}{}{}fn {}{:#}{:#}{:#}",
+        header.constness.print_with_space(),
+        header.asyncness.print_with_space(),
+        header.unsafety.print_with_space(),
+        print_abi_with_space(header.abi),
+        name,
+        generics.print(),
+        decl.print(),
+        WhereClause { gens: generics, indent: 0, end_newline: false }
+    )
+}
+
+fn adt_signature(
+    kind: &str,
+    name: &str,
+    struct_type: StructType,
+    generics: &clean::Generics,
+) -> String {
+    format!(
+        "{} {}{:#}{}{:#}",
+        kind,
+        name,
+        generics.print(),
+        match struct_type {
+            StructType::Plain => " { .. }",
+            StructType::Tuple => "(..)",
+            StructType::Unit => ";",
+        },
+        WhereClause { gens: generics, indent: 0, end_newline: false }
+    )
+}
+
+fn variant_signature(name: &str, variant: &clean::Variant) -> String {
+    match variant.kind {
+        clean::VariantKind::CLike => name.to_string(),
+        clean::VariantKind::Tuple(ref tys) => format!(
+            "{}({})",
+            name,
+            tys.iter().map(|ty| format!("{:#}", ty.print())).collect::<Vec<_>>().join(", ")
+        ),
+        clean::VariantKind::Struct(ref s) => {
+            let mut fields = s
+                .fields
+                .iter()
+                .filter_map(|field| match (&field.name, &field.inner) {
+                    (Some(name), clean::StructFieldItem(ty)) => {
+                        Some(format!("{}: {:#}", name, ty.print()))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if s.fields_stripped {
+                fields.push("..".to_string());
+            }
+            format!("{} {{ {} }}", name, fields.join(", "))
+        }
+    }
+}
+
+fn path_name(path: &clean::Path) -> String {
+    path.segments.iter().map(|s| &s.name[..]).collect::<Vec<_>>().join("::")
+}
@@ -0,0 +1,182 @@
+use super::compare::{compare, required_bump, Category};
+use super::model::{ApiImpl, ApiItem, ApiMember, ApiModel};
+
+fn model(items: Vec<(&str, ApiItem)>) -> ApiModel {
+    ApiModel {
+        crate_name: "foo".to_string(),
+        version: None,
+        items: items.into_iter().map(|(key, item)| (key.to_string(), item)).collect(),
+    }
+}
+
+fn item(signature: &str) -> ApiItem {
+    ApiItem { signature: signature.to_string(), ..ApiItem::default() }
+}
+
+fn with_members(mut item: ApiItem, members: Vec<(&str, ApiMember)>) -> ApiItem {
+    item.members = members.into_iter().map(|(key, m)| (key.to_string(), m)).collect();
+    item
+}
+
+fn member(signature: &str, required: bool) -> ApiMember {
+    ApiMember { signature: signature.to_string(), required, ..ApiMember::default() }
+}
+
+fn impl_(signature: &str, negative: bool) -> ApiImpl {
+    ApiImpl { signature: signature.to_string(), negative }
+}
+
+/// Returns the category and description of the changes from `old` to `new`.
+fn changes(old: Vec<(&str, ApiItem)>, new: Vec<(&str, ApiItem)>) -> Vec<(Category, String)> {
+    compare(&model(old), &model(new))
+        .into_iter()
+        .map(|change| (change.category, format!("{}: {}", change.item, change.description)))
+        .collect()
+}
+
+#[test]
+fn test_no_changes() {
+    let items = || vec![("fn foo::f", item("fn f()")), ("struct foo::S", item("struct S;"))];
+    let changes = compare(&model(items()), &model(items()));
+    assert!(changes.is_empty());
+    assert_eq!(required_bump(&changes), Category::Patch);
+}
+
+#[test]
+fn test_added_and_removed_items() {
+    assert_eq!(
+        changes(vec![("fn foo::f", item("fn f()"))], vec![("fn foo::g", item("fn g()"))]),
+        vec![
+            (Category::Breaking, "fn foo::f: removed".to_string()),
+            (Category::Minor, "fn foo::g: added".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_changed_signature() {
+    assert_eq!(
+        changes(vec![("fn foo::f", item("fn f()"))], vec![("fn foo::f", item("fn f(x: u32)"))]),
+        vec![(
+            Category::Breaking,
+            "fn foo::f: changed from `fn f()` to `fn f(x: u32)`".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_documentation_change() {
+    let mut new = item("fn f()");
+    new.docs = Some("Does things.".to_string());
+    let changes =
+        compare(&model(vec![("fn foo::f", item("fn f()"))]), &model(vec![("fn foo::f", new)]));
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].category, Category::Patch);
+    assert_eq!(required_bump(&changes), Category::Patch);
+}
+
+#[test]
+fn test_new_trait_items() {
+    let old = item("trait T");
+    let new = with_members(
+        item("trait T"),
+        vec![
+            ("fn required", member("fn required()", true)),
+            ("fn provided", member("fn provided()", false)),
+        ],
+    );
+    assert_eq!(
+        changes(vec![("trait foo::T", old)], vec![("trait foo::T", new)]),
+        vec![
+            (Category::Breaking, "trait foo::T: new required `fn required`".to_string()),
+            (Category::Minor, "trait foo::T: new provided `fn provided`".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_trait_item_loses_default() {
+    let old = with_members(item("trait T"), vec![("fn f", member("fn f()", false))]);
+    let new = with_members(item("trait T"), vec![("fn f", member("fn f()", true))]);
+    assert_eq!(
+        changes(vec![("trait foo::T", old)], vec![("trait foo::T", new)]),
+        vec![(Category::Breaking, "trait foo::T: `fn f` no longer has a default".to_string())]
+    );
+}
+
+#[test]
+fn test_new_variants() {
+    let old = || with_members(item("enum E"), vec![("variant A", member("A", false))]);
+    let new = || {
+        with_members(
+            item("enum E"),
+            vec![("variant A", member("A", false)), ("variant B", member("B", false))],
+        )
+    };
+    assert_eq!(
+        changes(vec![("enum foo::E", old())], vec![("enum foo::E", new())]),
+        vec![(
+            Category::Breaking,
+            "enum foo::E: new `variant B` in an exhaustive enum".to_string()
+        )]
+    );
+
+    let non_exhaustive = |mut item: ApiItem| {
+        item.non_exhaustive = true;
+        item
+    };
+    assert_eq!(
+        changes(
+            vec![("enum foo::E", non_exhaustive(old()))],
+            vec![("enum foo::E", non_exhaustive(new()))]
+        ),
+        vec![(Category::Minor, "enum foo::E: new `variant B`".to_string())]
+    );
+}
+
+#[test]
+fn test_new_fields() {
+    let new = || with_members(item("struct S { .. }"), vec![("field x", member("u32", false))]);
+    assert_eq!(
+        changes(vec![("struct foo::S", item("struct S { .. }"))], vec![("struct foo::S", new())]),
+        vec![(
+            Category::Breaking,
+            "struct foo::S: new `field x` in a struct with only public fields".to_string()
+        )]
+    );
+
+    let mut old = item("struct S { .. }");
+    old.has_private_members = true;
+    let mut new = new();
+    new.has_private_members = true;
+    assert_eq!(
+        changes(vec![("struct foo::S", old)], vec![("struct foo::S", new)]),
+        vec![(Category::Minor, "struct foo::S: new `field x`".to_string())]
+    );
+}
+
+#[test]
+fn test_impls() {
+    let with_impls = |impls: Vec<(&str, ApiImpl)>| {
+        let mut item = item("struct S;");
+        item.impls = impls.into_iter().map(|(key, i)| (key.to_string(), i)).collect();
+        item
+    };
+    let old = with_impls(vec![
+        ("Send", impl_("impl Send for S", false)),
+        ("Sync", impl_("impl !Sync for S", true)),
+    ]);
+    let new = with_impls(vec![
+        ("Send", impl_("impl !Send for S", true)),
+        ("Sync", impl_("impl Sync for S", false)),
+        ("Clone", impl_("impl Clone for S", false)),
+    ]);
+    assert_eq!(
+        changes(vec![("struct foo::S", old)], vec![("struct foo::S", new)]),
+        vec![
+            (Category::Breaking, "struct foo::S: lost `impl Send for S`".to_string()),
+            (Category::Minor, "struct foo::S: gained `impl Clone for S`".to_string()),
+            (Category::Minor, "struct foo::S: gained `impl Sync for S`".to_string()),
+        ]
+    );
+}
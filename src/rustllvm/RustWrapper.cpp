@@ -207,6 +207,12 @@ static Attribute::AttrKind fromRust(LLVMRustAttribute Kind) {
     return Attribute::ReadNone;
   case InaccessibleMemOnly:
     return Attribute::InaccessibleMemOnly;
+  case StackProtect:
+    return Attribute::StackProtect;
+  case StackProtectStrong:
+    return Attribute::StackProtectStrong;
+  case StackProtectReq:
+    return Attribute::StackProtectReq;
   }
   report_fatal_error("bad AttributeKind");
 }
@@ -697,13 +703,14 @@ extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateCompileUnit(
     const char *Producer, size_t ProducerLen, bool isOptimized,
     const char *Flags, unsigned RuntimeVer,
     const char *SplitName, size_t SplitNameLen,
-    LLVMRustDebugEmissionKind Kind) {
+    LLVMRustDebugEmissionKind Kind,
+    uint64_t DWOId, bool SplitDebugInlining) {
   auto *File = unwrapDI<DIFile>(FileRef);
 
   return wrap(Builder->createCompileUnit(Lang, File, StringRef(Producer, ProducerLen),
                                          isOptimized, Flags, RuntimeVer,
                                          StringRef(SplitName, SplitNameLen),
-                                         fromRust(Kind)));
+                                         fromRust(Kind), DWOId, SplitDebugInlining));
 }
 
 extern "C" LLVMMetadataRef LLVMRustDIBuilderCreateFile(
@@ -1480,6 +1487,12 @@ extern "C" void LLVMRustUnsetComdat(LLVMValueRef V) {
   GV->setComdat(nullptr);
 }
 
+extern "C" void LLVMRustGlobalAddMetadata(LLVMValueRef Global, unsigned Kind,
+                                          LLVMValueRef MD) {
+  MDNode *N = cast<MDNode>(unwrap<MetadataAsValue>(MD)->getMetadata());
+  unwrap<GlobalObject>(Global)->addMetadata(Kind, *N);
+}
+
 enum class LLVMRustLinkage {
   ExternalLinkage = 0,
   AvailableExternallyLinkage = 1,
@@ -85,6 +85,9 @@ enum LLVMRustAttribute {
   ReturnsTwice = 25,
   ReadNone = 26,
   InaccessibleMemOnly = 27,
+  StackProtect = 28,
+  StackProtectStrong = 29,
+  StackProtectReq = 30,
 };
 
 typedef struct OpaqueRustString *RustStringRef;
@@ -0,0 +1,19 @@
+// Verifies that `-Z stack-protector` sets the matching LLVM attribute on functions.
+//
+// revisions: NONE BASIC STRONG ALL
+// compile-flags: -C no-prepopulate-passes
+//[NONE]   compile-flags: -Z stack-protector=none
+//[BASIC]  compile-flags: -Z stack-protector=basic
+//[STRONG] compile-flags: -Z stack-protector=strong
+//[ALL]    compile-flags: -Z stack-protector=all
+
+#![crate_type = "lib"]
+
+// CHECK: define void @foo() {{.*}}#[[ATTRS:[0-9]+]]
+#[no_mangle]
+pub fn foo() {}
+
+// NONE-NOT: attributes #[[ATTRS]] = {{.*}} ssp{{(strong|req)?}} {{.*}}"target-cpu"
+// BASIC:    attributes #[[ATTRS]] = {{.*}} ssp {{.*}}"target-cpu"
+// STRONG:   attributes #[[ATTRS]] = {{.*}} sspstrong {{.*}}"target-cpu"
+// ALL:      attributes #[[ATTRS]] = {{.*}} sspreq {{.*}}"target-cpu"
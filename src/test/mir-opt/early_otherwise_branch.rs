@@ -0,0 +1,23 @@
+// compile-flags: -Z mir-opt-level=2
+// EMIT_MIR early_otherwise_branch.opt1.EarlyOtherwiseBranch.diff
+fn opt1(x: Option<u32>, y: Option<u32>) -> u32 {
+    match (x, y) {
+        (Some(_), Some(_)) => 0,
+        _ => 1,
+    }
+}
+
+// The discriminants of `Option<u32>` and `Option<u64>` are not comparable in general, so this
+// must not be optimized.
+// EMIT_MIR early_otherwise_branch.noopt_types.EarlyOtherwiseBranch.diff
+fn noopt_types(x: Option<u32>, y: Option<u64>) -> u32 {
+    match (x, y) {
+        (Some(_), Some(_)) => 0,
+        _ => 1,
+    }
+}
+
+fn main() {
+    opt1(None, Some(0));
+    noopt_types(None, Some(0));
+}
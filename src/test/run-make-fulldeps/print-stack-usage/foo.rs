@@ -0,0 +1,35 @@
+#![crate_type = "lib"]
+#![feature(core_intrinsics)]
+
+use std::intrinsics::volatile_load;
+
+#[inline(never)]
+fn leaf() {
+    let xs = [0u32; 16];
+
+    // force LLVM to allocate `xs` on the stack
+    unsafe { volatile_load(&xs.as_ptr()); }
+}
+
+pub fn bounded() {
+    leaf();
+    leaf();
+}
+
+pub fn recursive(n: u32) {
+    if n > 0 {
+        leaf();
+        recursive(n - 1);
+    }
+}
+
+pub fn indirect(f: fn()) {
+    leaf();
+    f();
+}
+
+pub fn copies(dst: &mut [u8; 4096], src: &[u8; 4096], len: usize) {
+    leaf();
+    // LLVM lowers the intrinsic to a call to `memcpy`
+    unsafe { std::intrinsics::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), len) }
+}
@@ -0,0 +1,19 @@
+#![crate_name = "foo"]
+
+/// ```
+/// assert!(true);
+/// ```
+pub fn foo() {}
+
+pub mod bar {
+    /// ```
+    /// assert!(false);
+    /// assert!(true);
+    /// ```
+    pub struct Baz;
+
+    /// ```ignore
+    /// assert!(false);
+    /// ```
+    pub struct Ignored;
+}
@@ -0,0 +1,32 @@
+#![crate_name = "foo"]
+
+/// A foo.
+///
+/// Made by [`make`](bar::make).
+///
+/// # Examples
+///
+/// ```
+/// # fn main() {
+/// let foo = foo::bar::make();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Foo {
+    /// The value.
+    pub x: u32,
+}
+
+impl Foo {
+    /// Returns the value.
+    pub fn get(&self) -> u32 {
+        self.x
+    }
+}
+
+pub mod bar {
+    /// Returns a [`Foo`](crate::Foo).
+    pub fn make() -> crate::Foo {
+        crate::Foo { x: 0 }
+    }
+}
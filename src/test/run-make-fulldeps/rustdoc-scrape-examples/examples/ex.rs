@@ -0,0 +1,7 @@
+extern crate foobar;
+
+fn main() {
+    let x = foobar::ok();
+    let foo = foobar::Foo::new(x);
+    foo.bar();
+}
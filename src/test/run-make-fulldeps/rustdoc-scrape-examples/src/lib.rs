@@ -0,0 +1,26 @@
+// @has foobar/fn.ok.html '//*[@class="scraped-example-title"]' 'examples/ex.rs (line 3)'
+// @has - '//*[@class="line-numbers"]/*[@class="line-highlighted"]' '4'
+/// Returns a number.
+pub fn ok() -> u32 {
+    0
+}
+
+// @!has foobar/fn.unused.html '//*[@class="scraped-example"]'
+/// Isn't called by the example.
+pub fn unused() {}
+
+/// A struct.
+pub struct Foo(u32);
+
+// @has foobar/struct.Foo.html '//*[@class="scraped-example-title"]' 'examples/ex.rs (line 3)'
+// @has - '//*[@class="line-numbers"]/*[@class="line-highlighted"]' '5'
+// @has - '//*[@class="line-numbers"]/*[@class="line-highlighted"]' '6'
+impl Foo {
+    /// Makes a `Foo`.
+    pub fn new(x: u32) -> Foo {
+        Foo(x)
+    }
+
+    /// Does nothing.
+    pub fn bar(&self) {}
+}
@@ -0,0 +1,21 @@
+pub fn unchanged() {}
+
+pub fn changed(x: u64) -> u64 {
+    x
+}
+
+pub fn added() {}
+
+pub trait Tr {
+    fn a(&self);
+    fn b(&self);
+}
+
+pub enum E {
+    A,
+    B,
+}
+
+pub struct S {
+    _inner: std::rc::Rc<u32>,
+}
@@ -0,0 +1,19 @@
+pub fn unchanged() {}
+
+pub fn removed() {}
+
+pub fn changed(x: u32) -> u32 {
+    x
+}
+
+pub trait Tr {
+    fn a(&self);
+}
+
+pub enum E {
+    A,
+}
+
+pub struct S {
+    _inner: u32,
+}
@@ -0,0 +1,78 @@
+#![feature(test)]
+
+use std::hint::black_box;
+use std::mem;
+
+trait Count {
+    fn count(&self) -> u32;
+}
+
+trait Greet {
+    fn greet(&self, times: u32) -> u32;
+}
+
+trait Measure {
+    fn measure(&self) -> u32;
+}
+
+struct Counter(u32);
+
+impl Count for Counter {
+    fn count(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Greet for Counter {
+    fn greet(&self, times: u32) -> u32 {
+        self.0 * times
+    }
+}
+
+impl Measure for Counter {
+    fn measure(&self) -> u32 {
+        self.0 + 1
+    }
+}
+
+static COUNTER: &dyn Count = &Counter(2);
+
+fn add_one(x: i32) -> i32 {
+    x + 1
+}
+
+fn main() {
+    let counter = Counter(1);
+    let code = match std::env::args().nth(1).as_deref() {
+        Some("ok") => {
+            let f = black_box(add_one as fn(i32) -> i32);
+            let count = black_box(&counter as &dyn Count);
+            let greet = black_box(&counter as &dyn Greet);
+            // Vtables of constants are created by the interpreter.
+            let promoted = black_box(&Counter(3) as &dyn Count);
+            let boxed = black_box(Box::new(Counter(4)) as Box<dyn Count>);
+            let sum = f(1) as u32
+                + count.count()
+                + greet.greet(2)
+                + promoted.count()
+                + COUNTER.count()
+                + boxed.count();
+            sum as i32 - 14
+        }
+        Some("icall") => {
+            let f: fn(i64) -> i64 = unsafe { mem::transmute(add_one as fn(i32) -> i32) };
+            black_box(f)(1) as i32
+        }
+        Some("vcall") => {
+            let greet: &dyn Greet = unsafe { mem::transmute(&counter as &dyn Count) };
+            black_box(greet).greet(2) as i32
+        }
+        Some("vtable") => {
+            // `measure` has the same type as `count`, but is not in a vtable of `Count`.
+            let count: &dyn Count = unsafe { mem::transmute(&counter as &dyn Measure) };
+            black_box(count).count() as i32
+        }
+        _ => 1,
+    };
+    std::process::exit(code);
+}
@@ -0,0 +1,13 @@
+struct Point {
+    int x;
+    int y;
+};
+
+int add(int a, int b) {
+    return a + b;
+}
+
+void draw(const struct Point *point, void *context) {
+    (void)point;
+    (void)context;
+}
@@ -0,0 +1,15 @@
+use std::ffi::c_void;
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[no_mangle]
+pub extern "C" fn draw(_point: *const Point, _context: *mut c_void) {}
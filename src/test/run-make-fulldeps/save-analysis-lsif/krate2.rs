@@ -0,0 +1,5 @@
+#![crate_name = "krate2"]
+#![crate_type = "lib"]
+
+pub fn hello() {
+}
@@ -0,0 +1,19 @@
+/// A shape with an area.
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+}
+
+fn main() {
+    krate2::hello();
+    let square = Square(2.0);
+    let double = |area: f64| area * 2.0;
+    double(square.area());
+}
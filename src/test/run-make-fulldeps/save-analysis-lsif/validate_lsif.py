@@ -0,0 +1,57 @@
+#!/usr/bin/env python
+
+import sys
+import json
+
+elements = [json.loads(line) for line in sys.stdin]
+by_id = {}
+for element in elements:
+    assert element["id"] not in by_id
+    by_id[element["id"]] = element
+
+    # Every edge must point to vertices that were already emitted.
+    if element["type"] == "edge":
+        targets = element.get("inVs", []) + [element.get("inV", element["outV"])]
+        for target in [element["outV"]] + targets:
+            assert target in by_id and by_id[target]["type"] == "vertex", element
+
+assert elements[0]["label"] == "metaData"
+# Ranges count characters, not the UTF-16 code units of LSP.
+assert "positionEncoding" not in elements[0]
+
+
+def edges(label, out_v):
+    return [e for e in elements if e["label"] == label and e["outV"] == out_v]
+
+
+def result_set(identifier):
+    for moniker in elements:
+        if moniker["label"] == "moniker" and moniker["identifier"].endswith(identifier):
+            edge = [e for e in elements if e["label"] == "moniker" and e["inV"] == moniker["id"]]
+            return moniker["kind"], edge[0]["outV"]
+    assert False, identifier
+
+
+# Items from other crates are imported through their monikers.
+kind, _ = result_set("::hello[0]")
+assert kind == "import"
+
+# Local items have a definition, a hover with their docs, and references.
+kind, shape = result_set("::Shape[0]")
+assert kind == "export"
+[definition] = edges("textDocument/definition", shape)
+assert len(edges("item", definition["inV"])) == 1
+[hover] = edges("textDocument/hover", shape)
+contents = by_id[hover["inV"]]["result"]["contents"]
+assert any("A shape with an area." in c for c in contents if isinstance(c, str))
+
+# Traits list their impls, and trait methods the methods implementing them.
+[implementation] = edges("textDocument/implementation", shape)
+assert len(edges("item", implementation["inV"])) == 1
+kind, area = result_set("::Shape[0]::area[0]")
+[implementation] = edges("textDocument/implementation", area)
+assert len(edges("item", implementation["inV"])) == 1
+
+# Local variables, including closure parameters, are not visible to other crates.
+monikers = [e["identifier"] for e in elements if e["label"] == "moniker"]
+assert not any(m.endswith(("square", "double", "area")) for m in monikers), monikers
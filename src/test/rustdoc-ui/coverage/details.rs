@@ -0,0 +1,27 @@
+// compile-flags:-Z unstable-options --show-coverage --coverage-details
+// check-pass
+
+//! Lists the items without documentation or examples.
+
+/// Documented, with an example.
+///
+/// ```
+/// let x = 1;
+/// ```
+pub struct Documented;
+
+pub struct Undocumented;
+
+/// Documented, without an example.
+pub fn no_example() {}
+
+pub mod module {
+    /// Documented, without an example.
+    pub trait Trait {
+        fn method();
+    }
+
+    impl super::Documented {
+        pub fn method(&self) {}
+    }
+}
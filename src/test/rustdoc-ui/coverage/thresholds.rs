@@ -0,0 +1,13 @@
+// compile-flags:-Z unstable-options --output-format json --show-coverage --coverage-details
+// compile-flags:--coverage-threshold 75 --coverage-examples-threshold 50
+
+// The run fails when the coverage is below the thresholds.
+
+/// Documented, with an example.
+///
+/// ```
+/// let x = 1;
+/// ```
+pub struct Documented;
+
+pub struct Undocumented;
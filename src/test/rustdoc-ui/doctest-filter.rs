@@ -0,0 +1,37 @@
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options
+// compile-flags:--doctest-filter=foo::Bar --doctest-filter=doctest_filter::baz
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+// Only the doctests of the items selected with `--doctest-filter` and of their children are run.
+
+pub mod foo {
+    /// ```
+    /// assert!(true);
+    /// ```
+    pub struct Bar;
+
+    impl Bar {
+        /// ```
+        /// assert!(true);
+        /// ```
+        pub fn new() -> Bar {
+            Bar
+        }
+    }
+
+    /// ```
+    /// panic!("not selected");
+    /// ```
+    pub struct Barrier;
+}
+
+/// ```
+/// assert!(true);
+/// ```
+pub fn baz() {}
+
+/// ```
+/// panic!("not selected");
+/// ```
+pub fn qux() {}
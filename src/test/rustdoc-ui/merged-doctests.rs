@@ -0,0 +1,47 @@
+// edition:2018
+// compile-flags:--test --test-args=--test-threads=1 -Z unstable-options --merge-doctests
+// normalize-stdout-test: "src/test/rustdoc-ui" -> "$$DIR"
+// check-pass
+
+// Doctests compiled into a single binary are run by libtest in one process, with the locations of
+// the documentation, and the ones that can't be merged are compiled on their own.
+
+/// ```
+/// assert_eq!(merged_doctests::add(1, 2), 3);
+/// ```
+///
+/// ```should_panic
+/// assert_eq!(merged_doctests::add(1, 2), 4);
+/// ```
+///
+/// ```
+/// assert!(file!().ends_with("merged-doctests.rs"));
+/// assert_eq!(line!(), 19);
+/// ```
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+/// ```
+/// fn main() {
+///     assert_eq!(merged_doctests::add(2, 2), 4);
+/// }
+/// ```
+///
+/// ```standalone
+/// assert_eq!(std::env::args().count(), 1);
+/// ```
+///
+/// ```
+/// #![allow(unused_variables)]
+/// let x = 1;
+/// ```
+///
+/// ```compile_fail
+/// let x: u32 = "not a number";
+/// ```
+///
+/// ```edition2015
+/// assert_eq!(::merged_doctests::add(0, 0), 0);
+/// ```
+pub struct Standalone;
@@ -0,0 +1,7 @@
+pub struct Extern;
+
+impl Extern {
+    pub fn method(&self) {}
+}
+
+pub fn extern_fn() {}
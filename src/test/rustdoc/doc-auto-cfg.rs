@@ -0,0 +1,52 @@
+// aux-build:doc-auto-cfg.rs
+// compile-flags: --cfg feature="sync" --cfg feature="send" --cfg internal
+
+#![crate_name = "foo"]
+#![feature(doc_auto_cfg, doc_cfg)]
+#![doc(cfg_hide(internal))]
+
+extern crate doc_auto_cfg;
+
+// @has 'foo/struct.Foo.html'
+// @has '-' '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//      'This is supported on crate feature sync only.'
+#[cfg(feature = "sync")]
+pub struct Foo;
+
+// @has 'foo/bar/struct.Bar.html'
+// @has '-' '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//      'This is supported on crate features sync and send only.'
+#[cfg(feature = "sync")]
+pub mod bar {
+    #[cfg(feature = "send")]
+    pub struct Bar;
+}
+
+// `all(sync, any(sync, send))` is simplified to `sync`.
+// @has 'foo/struct.Baz.html'
+// @has '-' '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//      'This is supported on crate feature sync only.'
+#[cfg(all(feature = "sync", any(feature = "sync", feature = "send")))]
+pub struct Baz;
+
+// Hidden cfgs, and `doc`, which is always set by rustdoc, don't show up.
+// @has 'foo/struct.Hidden.html'
+// @count - '//*[@class="stab portability"]' 0
+#[cfg(all(internal, doc))]
+pub struct Hidden;
+
+// An explicit `#[doc(cfg)]` takes precedence.
+// @has 'foo/struct.Explicit.html'
+// @has '-' '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//      'This is supported on Unix only.'
+#[cfg(feature = "sync")]
+#[doc(cfg(unix))]
+pub struct Explicit;
+
+// Re-exports add their own `#[cfg]`.
+// @has 'foo/struct.Reexported.html'
+// @has '-' '//*[@id="main"]/*[@class="stability"]/*[@class="stab portability"]' \
+//      'This is supported on crate feature send only.'
+#[cfg(feature = "send")]
+#[doc(inline)]
+pub use doc_auto_cfg::Reexported;
@@ -0,0 +1,23 @@
+// compile-flags: -Z unstable-options --generate-link-to-definition
+
+// Rustdoc doesn't type-check function bodies, so a body that doesn't type-check doesn't fail
+// the build: only its plain paths are linked.
+
+#![crate_name = "foo"]
+
+pub struct Local;
+
+impl Local {
+    pub fn get(&self) -> u32 {
+        0
+    }
+}
+
+// @has src/foo/link-to-definition-type-error.rs.html
+// @has - '//a[@href="../../src/foo/link-to-definition-type-error.rs.html#8"]' 'Local'
+// @!has - '//a[@href="../../src/foo/link-to-definition-type-error.rs.html#11"]' 'get'
+pub fn broken() -> u32 {
+    let local = Local;
+    let x: u32 = "not a number";
+    local.get() + x
+}
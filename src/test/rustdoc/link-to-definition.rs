@@ -0,0 +1,53 @@
+// aux-build:link-to-definition-aux.rs
+// build-aux-docs
+// compile-flags: -Z unstable-options --generate-link-to-definition
+
+#![crate_name = "foo"]
+
+extern crate link_to_definition_aux as aux;
+
+// @has src/foo/link-to-definition.rs.html
+// @has - '//a[@href="../../src/foo/link-to-definition/private.rs.html#1"]' 'private'
+#[path = "link-to-definition/private.rs"]
+mod private;
+
+pub struct Local;
+
+// @has - '//a[@href="../../src/foo/link-to-definition.rs.html#14"]' 'Local'
+// @has - '//a[@href="../../link_to_definition_aux/struct.Extern.html"]' 'Extern'
+// @has - '//a[@href="../../link_to_definition_aux/fn.extern_fn.html"]' 'extern_fn'
+// @has - '//a[@href="../../src/foo/link-to-definition/private.rs.html#1"]' 'helper'
+// @has - '//a[@href="../../src/foo/link-to-definition.rs.html#23"]' 'x'
+pub fn uses(_: Local, _: aux::Extern) -> u32 {
+    aux::extern_fn();
+    let x = private::helper();
+    x
+}
+
+// The file of the private module is rendered even though it has no documented item.
+// @has src/foo/link-to-definition/private.rs.html
+
+pub struct Point {
+    pub value: u32,
+}
+
+impl Point {
+    pub fn origin() -> Point {
+        Point { value: 0 }
+    }
+
+    pub fn get(&self) -> u32 {
+        self.value
+    }
+}
+
+// Method calls, field accesses and type-relative paths are resolved by type-checking the body.
+// @has - '//a[@href="../../src/foo/link-to-definition.rs.html#35"]' 'origin'
+// @has - '//a[@href="../../src/foo/link-to-definition.rs.html#39"]' 'get'
+// @has - '//a[@href="../../src/foo/link-to-definition.rs.html#31"]' 'value'
+// @has - '//a[@href="../../link_to_definition_aux/struct.Extern.html"]' 'method'
+pub fn type_dependent(e: aux::Extern) -> u32 {
+    e.method();
+    let point = Point::origin();
+    point.get() + point.value
+}
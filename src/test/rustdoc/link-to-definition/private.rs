@@ -0,0 +1,3 @@
+pub(crate) fn helper() -> u32 {
+    1
+}
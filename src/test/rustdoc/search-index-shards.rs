@@ -0,0 +1,33 @@
+// compile-flags: -Z unstable-options --shard-search-index
+
+#![crate_name = "foo"]
+
+//! The crate.
+
+// The main index only lists the shards of the crate.
+// @has search-index.js '"foo":{"doc":"The crate.","s":["_","b","i","n"]}'
+// @!has search-index.js 'Bar'
+
+// @has search-index/foo/paths.js 'loadSearchShard("foo","paths"'
+// @has search-index/foo/paths.js '"m":["foo","foo::inner"]'
+// @has search-index/foo/b.js '[3,"Bar",0,"A bar.",null,null]'
+// @has search-index/foo/b.js '[5,"baz",1,"",null,'
+// @has search-index/foo/n.js '"new"'
+// @has search-index/foo/_.js '"_private_looking"'
+
+/// A bar.
+pub struct Bar;
+
+impl Bar {
+    pub fn new() -> Bar {
+        Bar
+    }
+}
+
+pub fn _private_looking() {}
+
+pub mod inner {
+    pub fn baz(_: u32) -> super::Bar {
+        super::Bar
+    }
+}
@@ -0,0 +1,17 @@
+// aux-build:lint-tool-test.rs
+// ignore-stage1
+// check-pass
+
+#![feature(lint_reasons)]
+#![feature(plugin)]
+#![plugin(lint_tool_test)]
+//~^ WARN use of deprecated attribute `plugin`
+
+#[expect(clippy::test_lint)]
+fn lintme() {}
+
+#[expect(clippy::test_lint)]
+//~^ WARNING this lint expectation is unfulfilled
+fn main() {
+    lintme();
+}
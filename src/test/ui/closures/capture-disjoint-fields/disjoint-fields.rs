@@ -0,0 +1,96 @@
+// run-pass
+// Closures capture the paths below a variable that they use instead of the
+// whole variable, which leaves the rest of the variable to their parent.
+
+#![feature(capture_disjoint_fields)]
+#![allow(incomplete_features)]
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+struct Wrapper {
+    point: Box<Point>,
+    name: String,
+}
+
+fn by_ref() {
+    let mut p = Point { x: 1, y: 2 };
+    let mut c = || p.x += 10;
+    let py = &mut p.y;
+    *py += 20;
+    c();
+    assert_eq!((p.x, p.y), (11, 22));
+}
+
+fn through_box_and_ref(w: &mut Wrapper) {
+    let mut c = || w.point.x *= 2;
+    w.name.push('!');
+    c();
+}
+
+fn through_mut_ref(p: &mut Point) {
+    let mut c = || p.x = 7;
+    p.y = 8;
+    c();
+}
+
+fn by_value() {
+    let w = Wrapper { point: Box::new(Point { x: 3, y: 4 }), name: String::from("w") };
+    let c = move || w.name.len();
+    assert_eq!(w.point.x + w.point.y, 7);
+    assert_eq!(c(), 1);
+}
+
+fn let_and_match() {
+    let mut p = Point { x: 1, y: 2 };
+    let c = || {
+        let x = p.x;
+        match p.x {
+            1 => x,
+            _ => 0,
+        }
+    };
+    p.y += 1;
+    assert_eq!(c(), 1);
+    assert_eq!(p.y, 3);
+}
+
+fn tuples() {
+    let mut t = (String::from("a"), String::from("b"));
+    let mut c = || t.0.push('!');
+    t.1.push('?');
+    c();
+    assert_eq!(t, (String::from("a!"), String::from("b?")));
+}
+
+fn nested() {
+    let mut p = Point { x: 0, y: 0 };
+    let mut outer = || {
+        let mut inner = || p.x += 1;
+        inner();
+        inner();
+    };
+    let py = &mut p.y;
+    *py = 5;
+    outer();
+    assert_eq!((p.x, p.y), (2, 5));
+}
+
+fn main() {
+    by_ref();
+
+    let mut w = Wrapper { point: Box::new(Point { x: 5, y: 6 }), name: String::from("w") };
+    through_box_and_ref(&mut w);
+    assert_eq!((w.point.x, w.point.y, &w.name[..]), (10, 6, "w!"));
+
+    let mut p = Point { x: 0, y: 0 };
+    through_mut_ref(&mut p);
+    assert_eq!((p.x, p.y), (7, 8));
+
+    by_value();
+    let_and_match();
+    tuples();
+    nested();
+}
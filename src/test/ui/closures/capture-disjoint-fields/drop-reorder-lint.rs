@@ -0,0 +1,42 @@
+// Moving a single field of a variable into a closure would leave the rest of
+// the variable to be dropped at the end of the scope once closures capture
+// disjoint fields, so this is linted on until then.
+
+#![deny(disjoint_capture_drop_reorder)]
+#![allow(dead_code)]
+
+struct Pair {
+    name: String,
+    id: u32,
+}
+
+struct Nested {
+    pair: Pair,
+    count: u32,
+}
+
+fn main() {
+    let pair = Pair { name: String::from("pair"), id: 7 };
+    let c = move || pair.id; //~ ERROR drop order of `pair` will change
+    assert_eq!(c(), 7);
+
+    let pair = Pair { name: String::from("pair"), id: 7 };
+    let c = move || {
+        let _ = &pair;
+        pair.id
+    };
+    assert_eq!(c(), 7);
+
+    let id = Pair { name: String::from("pair"), id: 7 }.id;
+    let c = move || id;
+    assert_eq!(c(), 7);
+
+    // Only `name` needs to be dropped, and it is moved into the closure.
+    let pair = Pair { name: String::from("pair"), id: 7 };
+    let c = move || pair.name;
+    assert_eq!(c(), "pair");
+
+    let nested = Nested { pair: Pair { name: String::from("pair"), id: 7 }, count: 1 };
+    let c = move || nested.pair.id; //~ ERROR drop order of `nested` will change
+    assert_eq!(c(), 7);
+}
@@ -0,0 +1,21 @@
+// Closures that use the same field of a variable still conflict, even when
+// they only capture that field.
+
+#![feature(capture_disjoint_fields)]
+//~^ WARNING the feature `capture_disjoint_fields` is incomplete
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let mut p = Point { x: 10, y: 10 };
+
+    let mut c1 = || p.x += 1;
+    let mut c2 = || p.x -= 1; //~ ERROR cannot borrow `p.x` as mutable more than once at a time
+    let py = &mut p.y;
+    *py += 1;
+    c1();
+    c2();
+}
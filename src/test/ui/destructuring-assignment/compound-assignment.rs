@@ -0,0 +1,23 @@
+#![feature(destructuring_assignment)]
+#![allow(unused_assignments)]
+
+struct S { x: u8, y: u8 }
+
+fn main() {
+    let (mut a, mut b) = (1, 2);
+
+    (a, b) += (3, 4); //~ ERROR invalid left-hand side of assignment
+    //~^ ERROR binary assignment operation `+=` cannot be applied
+
+    [a, b] -= [3, 4]; //~ ERROR invalid left-hand side of assignment
+    //~^ ERROR binary assignment operation `-=` cannot be applied
+
+    S { x: a, y: b } *= S { x: 3, y: 4 }; //~ ERROR invalid left-hand side of assignment
+    //~^ ERROR binary assignment operation `*=` cannot be applied
+
+    _ += 1; //~ ERROR in expressions, `_` can only be used on the left-hand side of an assignment
+    //~| ERROR invalid left-hand side of assignment
+    (a, b) = (3, _); //~ ERROR in expressions, `_` can only be used on the left-hand side of an assignment
+
+    (a, .., b, ..) = (0, 1); //~ ERROR `..` can only be used once per tuple pattern
+}
@@ -0,0 +1,38 @@
+// run-pass
+
+#![feature(destructuring_assignment)]
+
+fn main() {
+    let (mut a, mut b);
+    (a, b) = (0, 1);
+    assert_eq!((a, b), (0, 1));
+    (b, a) = (a, b);
+    assert_eq!((a, b), (1, 0));
+    (a, .., b) = (1, 2);
+    assert_eq!((a, b), (1, 2));
+    (.., a) = (1, 2);
+    assert_eq!((a, b), (2, 2));
+    (..) = (3, 4);
+    assert_eq!((a, b), (2, 2));
+    (b, ..) = (5, 6, 7);
+    assert_eq!(b, 5);
+    (a, _) = (8, 9);
+    assert_eq!(a, 8);
+
+    // Nested destructuring.
+    let mut c;
+    ((a, b), c) = ((3, 4), 5);
+    assert_eq!((a, b, c), (3, 4, 5));
+
+    // Assignment to places other than locals.
+    let mut x = (0, 0);
+    (x.0, x.1) = (10, 20);
+    assert_eq!(x, (10, 20));
+    let mut v = vec![0, 0];
+    (v[0], v[1]) = (30, 40);
+    assert_eq!(v, [30, 40]);
+
+    // The destructuring assignment is an expression of type `()`.
+    let unit: () = (a, b) = (0, 0);
+    assert_eq!(unit, ());
+}
@@ -0,0 +1,20 @@
+// run-pass
+
+// `_` is only an expression on the left-hand side of an assignment: an `expr` fragment still
+// doesn't start with it, so macros keep matching it with their other arms.
+
+#![feature(destructuring_assignment)]
+
+macro_rules! is_expr {
+    ($e:expr) => {
+        true
+    };
+    (_) => {
+        false
+    };
+}
+
+fn main() {
+    assert!(is_expr!(1));
+    assert!(!is_expr!(_));
+}
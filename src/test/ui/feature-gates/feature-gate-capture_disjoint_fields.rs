@@ -0,0 +1,15 @@
+// Without `#![feature(capture_disjoint_fields)]`, closures capture whole variables.
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+fn main() {
+    let mut p = Point { x: 10, y: 10 };
+
+    let c = || p.x + 1;
+    let py = &mut p.y; //~ ERROR cannot borrow `p.y` as mutable because it is also borrowed as immutable
+    *py += 1;
+    drop(c);
+}
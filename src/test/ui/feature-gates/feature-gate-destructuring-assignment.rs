@@ -0,0 +1,5 @@
+fn main() {
+    let (mut a, mut b) = (0, 1);
+    (a, b) = (2, 3); //~ ERROR destructuring assignments are unstable
+    let _ = (a, b);
+}
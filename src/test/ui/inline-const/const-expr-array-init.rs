@@ -0,0 +1,20 @@
+// run-pass
+// Inline `const` blocks are constants, so they can initialize arrays of
+// elements that aren't `Copy`.
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn empty_vecs<T>() -> [Vec<T>; 3] {
+    [const { Vec::<T>::new() }; 3]
+}
+
+fn main() {
+    let mut strings: [String; 4] = [const { String::new() }; 4];
+    strings[1].push_str("hello");
+    assert_eq!(strings, ["", "hello", "", ""]);
+
+    let mut vecs = empty_vecs::<u8>();
+    vecs[2].push(1);
+    assert_eq!(vecs, [vec![], vec![], vec![1]]);
+}
@@ -0,0 +1,21 @@
+// run-pass
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn foo() -> i32 {
+    const {
+        let x = 5 + 10;
+        x / 3
+    }
+}
+
+fn size_of<T>() -> usize {
+    const { std::mem::size_of::<T>() }
+}
+
+fn main() {
+    assert_eq!(5, foo());
+    assert_eq!(4, size_of::<u32>());
+    assert_eq!(16, size_of::<(u64, u32)>());
+}
@@ -0,0 +1,10 @@
+// Inline `const` blocks can't refer to the local variables around them.
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+fn main() {
+    let n = 3;
+    let _ = const { n + 1 };
+    //~^ ERROR attempt to use a non-constant value in a constant [E0435]
+}
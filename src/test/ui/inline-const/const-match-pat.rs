@@ -0,0 +1,23 @@
+// run-pass
+
+#![allow(incomplete_features)]
+#![feature(inline_const)]
+
+const MMIO_BIT1: u8 = 4;
+const MMIO_BIT2: u8 = 5;
+
+fn classify(x: u8) -> &'static str {
+    match x {
+        const { 1u8 << MMIO_BIT1 } => "bit 1",
+        const { 1u8 << MMIO_BIT2 } => "bit 2",
+        const { u8::MAX - 9 }..=const { u8::MAX } => "top ten",
+        _ => "other",
+    }
+}
+
+fn main() {
+    assert_eq!(classify(16), "bit 1");
+    assert_eq!(classify(32), "bit 2");
+    assert_eq!(classify(250), "top ten");
+    assert_eq!(classify(0), "other");
+}
@@ -0,0 +1,10 @@
+#![feature(let_else)]
+
+fn main() {
+    let (a, b) = (true, false);
+    let true = a && b else { return };
+    //~^ ERROR a `&&` expression cannot be directly assigned in `let...else`
+    let Some(y) = if a { Some(1) } else { None } else { return };
+    //~^ ERROR right curly brace `}` before `else` in a `let...else` statement not allowed
+    let _ = y;
+}
@@ -0,0 +1,9 @@
+#![feature(let_else)]
+
+fn main() {
+    let Some(x) = Some(1) else { //~ ERROR `else` clause of `let...else` does not diverge
+        Some(2)
+    };
+    let Some(y) = Some(1) else {}; //~ ERROR `else` clause of `let...else` does not diverge
+    let _ = (x, y);
+}
@@ -0,0 +1,105 @@
+// run-pass
+
+#![feature(let_else)]
+
+use std::cell::Cell;
+
+enum Shape {
+    Circle(u32),
+    Square { side: u32 },
+}
+
+fn radius(shape: &Shape) -> Option<u32> {
+    let Shape::Circle(r) = shape else {
+        return None;
+    };
+    Some(*r)
+}
+
+fn side(shape: Shape) -> u32 {
+    let Shape::Square { side } = shape else { panic!("not a square") };
+    side
+}
+
+fn first_word(s: &str) -> &str {
+    let Some(i) = s.find(' ') else { return s };
+    &s[..i]
+}
+
+fn sum_until_none(values: &[Option<u32>]) -> u32 {
+    let mut sum = 0;
+    for value in values {
+        let Some(v) = value else { break };
+        sum += v;
+    }
+    sum
+}
+
+fn typed(value: Option<&[u8]>) -> usize {
+    let Some(bytes): Option<&[u8]> = value else { return 0 };
+    bytes.len()
+}
+
+fn shadowed(x: Option<Option<u8>>) -> u8 {
+    let Some(x) = x else { return 0 };
+    let Some(x) = x else { return 1 };
+    x
+}
+
+fn moved(s: Option<String>) -> String {
+    let Some(s) = s else { return String::new() };
+    s + "!"
+}
+
+fn coerced(value: &[u8; 3]) -> u8 {
+    let [first, ..]: &[u8] = value else { return 0 };
+    *first
+}
+
+fn in_block_with_expected_type(x: Option<u32>) -> u32 {
+    let y: u32 = {
+        let Some(y) = x else { return 7 };
+        y
+    };
+    y + 1
+}
+
+struct Guard<'a>(&'a Cell<u32>);
+
+impl Guard<'_> {
+    fn value(&self) -> Option<u32> {
+        Some(self.0.get())
+    }
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+fn temporaries(dropped: &Cell<u32>) -> u32 {
+    let Some(v) = Guard(dropped).value() else { return 0 };
+    // The `Guard` temporary is dropped at the end of the statement.
+    assert_eq!(dropped.get(), v + 1);
+    v
+}
+
+fn main() {
+    assert_eq!(radius(&Shape::Circle(3)), Some(3));
+    assert_eq!(radius(&Shape::Square { side: 2 }), None);
+    assert_eq!(side(Shape::Square { side: 2 }), 2);
+    assert_eq!(first_word("hello world"), "hello");
+    assert_eq!(first_word("hello"), "hello");
+    assert_eq!(sum_until_none(&[Some(1), Some(2), None, Some(4)]), 3);
+    assert_eq!(typed(Some(b"abc")), 3);
+    assert_eq!(typed(None), 0);
+    assert_eq!(shadowed(None), 0);
+    assert_eq!(shadowed(Some(None)), 1);
+    assert_eq!(shadowed(Some(Some(2))), 2);
+    assert_eq!(moved(Some("hi".to_string())), "hi!");
+    assert_eq!(coerced(&[4, 5, 6]), 4);
+    assert_eq!(in_block_with_expected_type(Some(1)), 2);
+    assert_eq!(in_block_with_expected_type(None), 7);
+    assert_eq!(temporaries(&Cell::new(3)), 3);
+}
@@ -0,0 +1,10 @@
+// build-fail
+// compile-flags: -Z move-size-limit=1000
+
+#![deny(large_assignments)]
+
+fn main() {
+    let data = [0u8; 2048];
+    let pair = (data, 1u8); //~ ERROR moving 2048 bytes
+    let _first = pair.0; //~ ERROR moving 2048 bytes
+}
@@ -0,0 +1,51 @@
+// build-fail
+// edition:2018
+// compile-flags: -Z future-size-limit=1024
+
+#![deny(large_futures)]
+
+async fn other() {}
+
+async fn big() { //~ ERROR this future takes 4098 bytes
+    let buf = [0u8; 4096];
+    other().await;
+    drop(buf);
+}
+
+async fn small() {
+    let buf = [0u8; 16];
+    other().await;
+    drop(buf);
+}
+
+fn main() {
+    executor::block_on(big());
+    executor::block_on(small());
+}
+
+mod executor {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    pub fn block_on<F: Future>(mut future: F) -> F::Output {
+        let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| unimplemented!("clone"),
+            |_| unimplemented!("wake"),
+            |_| unimplemented!("wake_by_ref"),
+            |_| (),
+        );
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut context = Context::from_waker(&waker);
+
+        loop {
+            if let Poll::Ready(val) = future.as_mut().poll(&mut context) {
+                break val;
+            }
+        }
+    }
+}
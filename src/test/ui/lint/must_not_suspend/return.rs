@@ -0,0 +1,8 @@
+#![feature(must_not_suspend)]
+
+#[must_not_suspend = "Who knows?"] //~ ERROR attribute should be
+fn foo() -> i32 {
+    0
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+// edition:2018
+#![feature(must_not_suspend)]
+#![deny(must_not_suspend)]
+
+#[must_not_suspend]
+struct No {}
+
+async fn other() {}
+
+pub async fn uhoh() {
+    let _guard = (No {}, 1); //~ ERROR `No` in tuple element 0 held across
+    other().await;
+}
+
+pub async fn scoped() {
+    {
+        let _guard = (No {}, 1);
+    }
+    other().await;
+}
+
+fn main() {
+}
@@ -0,0 +1,23 @@
+// build-pass
+
+// Some MIR lints are only emitted during codegen, by `optimized_mir`, and they can fulfill an
+// expectation too.
+
+#![feature(lint_reasons)]
+#![deny(unfulfilled_lint_expectations)]
+
+#[expect(arithmetic_overflow)]
+fn overflow() -> u8 {
+    200 + 100
+}
+
+#[expect(unconditional_panic)]
+fn out_of_bounds() -> u8 {
+    let array = [1, 2, 3];
+    array[4]
+}
+
+fn main() {
+    overflow();
+    out_of_bounds();
+}
@@ -0,0 +1,56 @@
+// check-pass
+
+#![feature(lint_reasons)]
+
+// Early lint
+#[expect(unused_parens)]
+fn early() -> u32 {
+    (1)
+}
+
+// Late lint
+#[expect(non_snake_case)]
+fn Late() {}
+
+// MIR lint, emitted by borrowck
+#[expect(unused_mut)]
+fn mir() {
+    let mut x = 0;
+    drop(x);
+}
+
+// Lint group
+#[expect(unused)]
+fn group() {
+    let x = 0;
+}
+
+#[expect(unused_mut, reason = "the variable might be changed one day")]
+//~^ WARNING this lint expectation is unfulfilled
+//~| NOTE `#[warn(unfulfilled_lint_expectations)]` on by default
+//~| NOTE the `unused_mut` lint was never emitted here
+//~| NOTE the variable might be changed one day
+fn unfulfilled() {
+    let x = 0;
+    drop(x);
+}
+
+// Every lint name is a separate expectation
+#[expect(non_snake_case, unused_parens)]
+//~^ WARNING this lint expectation is unfulfilled
+//~| NOTE the `unused_parens` lint was never emitted here
+fn Partial() {}
+
+#[allow(unfulfilled_lint_expectations)]
+#[expect(dead_code)]
+fn silenced() {}
+
+fn main() {
+    early();
+    Late();
+    mir();
+    group();
+    unfulfilled();
+    Partial();
+    silenced();
+}
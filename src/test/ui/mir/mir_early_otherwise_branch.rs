@@ -0,0 +1,89 @@
+// run-pass
+// compile-flags: -Z mir-opt-level=2
+// Checks that matches on tuples of enums, which `EarlyOtherwiseBranch` turns into an up-front
+// comparison of the discriminants, still pick the right arm.
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum E {
+    A(u32),
+    B(u32),
+    C,
+}
+
+enum Ref<'a> {
+    Empty,
+    Some(&'a Ref<'a>),
+}
+
+fn opt1(x: Option<u32>, y: Option<u32>) -> u32 {
+    match (x, y) {
+        (Some(a), Some(b)) => a + b,
+        _ => 0,
+    }
+}
+
+fn opt2(x: E, y: E) -> u32 {
+    match (x, y) {
+        (E::A(a), E::A(b)) => a + b,
+        (E::B(a), E::B(b)) => a * b,
+        (E::C, E::C) => 1,
+        _ => 0,
+    }
+}
+
+fn opt3(x: &E, y: &E) -> u32 {
+    match (x, y) {
+        (E::A(a), E::A(b)) => a + b,
+        (E::B(a), E::B(b)) => a * b,
+        _ => 7,
+    }
+}
+
+fn no_opt_different_variants(x: Option<u32>, y: Option<u32>) -> u32 {
+    match (x, y) {
+        (Some(a), None) => a,
+        (None, Some(b)) => b,
+        _ => 0,
+    }
+}
+
+fn no_opt_projection(r: &Ref<'_>) -> bool {
+    match r {
+        Ref::Some(Ref::Some(_)) => true,
+        _ => false,
+    }
+}
+
+fn main() {
+    assert_eq!(opt1(Some(1), Some(2)), 3);
+    assert_eq!(opt1(Some(1), None), 0);
+    assert_eq!(opt1(None, Some(2)), 0);
+    assert_eq!(opt1(None, None), 0);
+
+    let all = [E::A(2), E::B(3), E::C];
+    for &x in &all {
+        for &y in &all {
+            let expected = match (x, y) {
+                (E::A(a), E::A(b)) => a + b,
+                (E::B(a), E::B(b)) => a * b,
+                (E::C, E::C) => 1,
+                _ => 0,
+            };
+            assert_eq!(opt2(x, y), expected);
+            assert_eq!(x == y, expected != 0, "{:?} == {:?}", x, y);
+            let expected = if expected == 0 || x == E::C { 7 } else { expected };
+            assert_eq!(opt3(&x, &y), expected);
+        }
+    }
+
+    assert_eq!(no_opt_different_variants(Some(1), None), 1);
+    assert_eq!(no_opt_different_variants(None, Some(2)), 2);
+    assert_eq!(no_opt_different_variants(Some(1), Some(2)), 0);
+
+    let empty = Ref::Empty;
+    let one = Ref::Some(&empty);
+    let two = Ref::Some(&one);
+    assert!(!no_opt_projection(&empty));
+    assert!(!no_opt_projection(&one));
+    assert!(no_opt_projection(&two));
+}
@@ -0,0 +1,20 @@
+// compile-flags: -Z print-mono-item-sizes
+// build-pass
+// ignore-pass
+// ^-- needed because `--pass check` does not emit the output needed.
+// normalize-stdout-test "\d+ (MIR statements|unoptimized LLVM instructions)" -> "N $1"
+// normalize-stdout-test "in `[^`]+`" -> "in `CGU`"
+
+// This file illustrates how the instances of a generic function are
+// grouped together. The statement and instruction counts depend on the
+// MIR optimizations and the LLVM version, so they are normalized away.
+
+#![crate_type = "lib"]
+
+pub fn rotate<T: Copy>(values: [T; 4]) -> [T; 4] {
+    [values[1], values[2], values[3], values[0]]
+}
+
+pub fn rotate_all(a: [u8; 4], b: [u16; 4], c: [u32; 4]) -> ([u8; 4], [u16; 4], [u32; 4]) {
+    (rotate(a), rotate(b), rotate(c))
+}
@@ -0,0 +1,31 @@
+// compile-flags: -Z print-type-sizes -Z print-type-sizes-format=json
+// compile-flags: -Z print-type-sizes-filter=S,E1
+// build-pass (FIXME(62277): could be check-pass?)
+
+// This file illustrates the JSON output of `-Z print-type-sizes`, limited
+// to some of the types with `-Z print-type-sizes-filter`. The types are
+// the same as in `padding.rs`.
+
+#![feature(start)]
+#![allow(dead_code)]
+
+struct S {
+    a: bool,
+    b: bool,
+    g: i32,
+}
+
+enum E1 {
+    A(i32, i8),
+    B(S),
+}
+
+enum E2 {
+    A(i8, i32),
+    B(S),
+}
+
+#[start]
+fn start(_: isize, _: *const *const u8) -> isize {
+    0
+}
@@ -0,0 +1,7 @@
+// compile-flags: -Z stack-protector=strong --target nvptx64-nvidia-cuda
+// needs-llvm-components: nvptx
+// error-pattern: error: `-Z stack-protector` is not supported on `nvptx64-nvidia-cuda`
+
+#![feature(no_core)]
+#![no_core]
+#![no_main]
@@ -9,11 +9,11 @@ trait T {
 fn main() {
     let _: usize = foo(_, _);
     //~^ ERROR expected expression
-    //~| ERROR expected expression
+    //~| ERROR in expressions, `_` can only be used on the left-hand side of an assignment
     let _: S = S(_, _);
     //~^ ERROR expected expression
-    //~| ERROR expected expression
+    //~| ERROR in expressions, `_` can only be used on the left-hand side of an assignment
     let _: usize = T::baz(_, _);
     //~^ ERROR expected expression
-    //~| ERROR expected expression
+    //~| ERROR in expressions, `_` can only be used on the left-hand side of an assignment
 }
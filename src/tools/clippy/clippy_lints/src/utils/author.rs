@@ -517,6 +517,10 @@ impl<'tcx> Visitor<'tcx> for PrintVisitor {
                 self.current = value_pat;
                 self.visit_expr(value);
             },
+            ExprKind::ConstBlock(_) => {
+                println!("ConstBlock(_) = {};", current);
+                println!("    // unimplemented: `ExprKind::ConstBlock` is not further destructured at the moment");
+            },
             ExprKind::Err => {
                 println!("Err = {}", current);
             },
@@ -21,19 +21,171 @@
 //! $ "${TARGET}"/llvm/bin/llvm-cov show --Xdemangler="${TARGET}"/stage0-tools-bin/rust-demangler \
 //!   --instr-profile=main.profdata ./main --show-line-counts-or-regions
 //! ```
+//!
+//! With `--filter`, the tool works like `c++filt` instead: every line of the input is copied to
+//! the output, with any legacy (`_ZN...E`) or v0 (`_R...`) symbol found in it replaced by its
+//! demangled form. This is useful for profiler output, backtraces, linker errors and `nm`
+//! listings:
+//!
+//! ```shell
+//! $ perf report --stdio | rust-demangler --filter --strip-hash
+//! ```
+//!
+//! `--strip-hash` omits the `::h<hash>` suffix of legacy symbols, and
+//! `--strip-crate-disambiguators` omits the `[<disambiguator>]` after crate names in v0 symbols.
+
+use rustc_demangle::{demangle, try_demangle};
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+#[cfg(test)]
+mod tests;
+
+const USAGE: &str = "\
+Usage: rust-demangler [OPTIONS]
 
-use rustc_demangle::demangle;
-use std::io::{self, Read, Write};
+Reads mangled Rust symbol names from standard input, one per line, and prints their demangled
+translations to standard output.
+
+Options:
+    -f, --filter                      demangle symbols found anywhere in each line, copying
+                                      everything else unchanged
+        --strip-hash                  omit the hash suffix of legacy symbols
+        --strip-crate-disambiguators  omit crate disambiguators in v0 symbols
+    -h, --help                        print this help message
+";
+
+/// Command-line options.
+#[derive(Debug, Default)]
+struct Options {
+    /// Find symbols anywhere in a line, instead of treating each line as one mangled name.
+    filter: bool,
+    /// Omit the `::h<hash>` suffix of legacy symbols.
+    strip_hash: bool,
+    /// Omit the `[<disambiguator>]` of crate names in v0 symbols.
+    strip_crate_disambiguators: bool,
+}
+
+impl Options {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Options, String> {
+        let mut options = Options::default();
+        for arg in args {
+            match &arg[..] {
+                "-f" | "--filter" => options.filter = true,
+                "--strip-hash" => options.strip_hash = true,
+                "--strip-crate-disambiguators" => options.strip_crate_disambiguators = true,
+                _ => return Err(arg),
+            }
+        }
+        Ok(options)
+    }
+}
 
 fn main() -> io::Result<()> {
-    let mut buffer = String::new();
-    io::stdin().read_to_string(&mut buffer)?;
-    let lines = buffer.lines();
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        print!("{}", USAGE);
+        return Ok(());
+    }
+    let options = match Options::parse(args.into_iter()) {
+        Ok(options) => options,
+        Err(arg) => {
+            eprint!("error: unexpected argument `{}`\n\n{}", arg, USAGE);
+            process::exit(1);
+        }
+    };
+
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut input = stdin.lock();
+    let mut output = io::BufWriter::new(stdout.lock());
+    let mut line = Vec::new();
     let mut demangled = Vec::new();
-    for mangled in lines {
-        demangled.push(demangle(mangled).to_string());
+    loop {
+        line.clear();
+        if input.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        demangled.clear();
+        if options.filter {
+            // Copy everything, including the line terminator, as is.
+            filter_line(&line, &options, &mut demangled);
+        } else {
+            let mangled = String::from_utf8(line.clone())
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let mangled = mangled.trim_end_matches('\n').trim_end_matches('\r');
+            demangled.extend_from_slice(demangle_symbol(mangled, &options).as_bytes());
+            demangled.push(b'\n');
+        }
+        output.write_all(&demangled)?;
     }
-    demangled.push("".to_string());
-    io::stdout().write_all(demangled.join("\n").as_bytes())?;
-    Ok(())
+    output.flush()
+}
+
+/// Demangles a whole line as a single symbol. Anything that isn't a valid symbol is returned
+/// unchanged.
+fn demangle_symbol(mangled: &str, options: &Options) -> String {
+    let demangled = demangle(mangled);
+    if alternate_format(mangled, options) {
+        format!("{:#}", demangled)
+    } else {
+        demangled.to_string()
+    }
+}
+
+/// Copies `line` to `out`, replacing every mangled symbol in it by its demangled form.
+///
+/// Symbols are looked for in maximal runs of characters that can appear in a mangled name
+/// (possibly with an LLVM `.llvm.<hash>` style suffix), like `c++filt` does. To avoid
+/// "demangling" regular words, only runs starting with the `_ZN` (legacy) or `_R` (v0)
+/// prefix, optionally preceded by another underscore as on macOS, are considered.
+fn filter_line(line: &[u8], options: &Options, out: &mut Vec<u8>) {
+    let mut i = 0;
+    while i < line.len() {
+        if !is_symbol_char(line[i]) {
+            out.push(line[i]);
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < line.len() && is_symbol_char(line[i]) {
+            i += 1;
+        }
+        // Symbol characters are all ASCII, so this can't fail.
+        let token = std::str::from_utf8(&line[start..i]).unwrap();
+        match demangle_token(token, options) {
+            Some(demangled) => out.extend_from_slice(demangled.as_bytes()),
+            None => out.extend_from_slice(token.as_bytes()),
+        }
+    }
+}
+
+fn demangle_token(token: &str, options: &Options) -> Option<String> {
+    let unprefixed = token.strip_prefix('_')?;
+    let unprefixed = unprefixed.strip_prefix('_').unwrap_or(unprefixed);
+    if !unprefixed.starts_with("ZN") && !unprefixed.starts_with('R') {
+        return None;
+    }
+    let demangled = try_demangle(token).ok()?;
+    if alternate_format(token, options) {
+        Some(format!("{:#}", demangled))
+    } else {
+        Some(demangled.to_string())
+    }
+}
+
+/// The alternate format of `rustc_demangle` omits the hash of legacy symbols and the crate
+/// disambiguators of v0 symbols. Each mangling only has one of the two, so which option
+/// applies depends on the mangling of `mangled`.
+fn alternate_format(mangled: &str, options: &Options) -> bool {
+    if mangled.trim_start_matches('_').starts_with('R') {
+        options.strip_crate_disambiguators
+    } else {
+        options.strip_hash
+    }
+}
+
+fn is_symbol_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || c == b'.'
 }
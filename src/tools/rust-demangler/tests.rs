@@ -0,0 +1,63 @@
+use super::*;
+
+const LEGACY: &str = "_ZN4core3fmt5write17h0123456789abcdefE";
+const V0: &str = "_RNvCs1234_7mycrate3foo";
+
+fn filter(line: &str, options: &Options) -> String {
+    let mut out = Vec::new();
+    filter_line(line.as_bytes(), options, &mut out);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn whole_line() {
+    let options = Options::default();
+    assert_eq!(demangle_symbol(LEGACY, &options), "core::fmt::write::h0123456789abcdef");
+    assert_eq!(demangle_symbol(V0, &options), "mycrate[3c1c0]::foo");
+    assert_eq!(demangle_symbol("not a symbol", &options), "not a symbol");
+}
+
+#[test]
+fn strip_options() {
+    let strip_hash = Options { strip_hash: true, ..Options::default() };
+    assert_eq!(demangle_symbol(LEGACY, &strip_hash), "core::fmt::write");
+    assert_eq!(demangle_symbol(V0, &strip_hash), "mycrate[3c1c0]::foo");
+
+    let strip_dis = Options { strip_crate_disambiguators: true, ..Options::default() };
+    assert_eq!(demangle_symbol(LEGACY, &strip_dis), "core::fmt::write::h0123456789abcdef");
+    assert_eq!(demangle_symbol(V0, &strip_dis), "mycrate::foo");
+}
+
+#[test]
+fn filter_embedded_symbols() {
+    let options = Options { filter: true, strip_hash: true, ..Options::default() };
+    assert_eq!(
+        filter(&format!("  12.5%  main  [.] {}+0x1f\n", LEGACY), &options),
+        "  12.5%  main  [.] core::fmt::write+0x1f\n",
+    );
+    assert_eq!(
+        filter(&format!("undefined reference to `{}'", V0), &options),
+        "undefined reference to `mycrate[3c1c0]::foo'",
+    );
+    assert_eq!(
+        filter(&format!("0000000000001000 T _{}\r\n", LEGACY), &options),
+        "0000000000001000 T core::fmt::write\r\n",
+    );
+}
+
+#[test]
+fn filter_leaves_other_text_alone() {
+    let options = Options { filter: true, ..Options::default() };
+    for line in &["Running tests", "_ZN_not_a_symbol R2D2 _R", "caf\u{e9} ZN3fooE", ""] {
+        assert_eq!(filter(line, &options), *line);
+    }
+}
+
+#[test]
+fn parse_options() {
+    let args = |args: &[&str]| Options::parse(args.iter().map(|s| s.to_string()));
+    let options = args(&["--filter", "--strip-hash"]).unwrap();
+    assert!(options.filter && options.strip_hash && !options.strip_crate_disambiguators);
+    assert!(args(&["-f", "--strip-crate-disambiguators"]).unwrap().strip_crate_disambiguators);
+    assert_eq!(args(&["--bogus"]).unwrap_err(), "--bogus");
+}